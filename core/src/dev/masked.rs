@@ -0,0 +1,82 @@
+use remus::{Block, Device, SharedDevice};
+
+/// Masked device.
+///
+/// # Usage
+///
+/// `Masked` wraps the internal device, restricting which of its bits may be
+/// written. Bits which are unused additionally always read as set.
+///
+/// This is useful for registers partly maintained by the hardware itself,
+/// whose remaining bits should not be clobbered by writes.
+#[derive(Debug)]
+pub struct Masked {
+    dev: SharedDevice,
+    unused: u8,
+    writable: u8,
+}
+
+impl Masked {
+    /// Constructs a new `Masked` with the provided `unused` and `writable`
+    /// bit masks.
+    pub fn with(dev: SharedDevice, unused: u8, writable: u8) -> Self {
+        Self {
+            dev,
+            unused,
+            writable,
+        }
+    }
+}
+
+impl Block for Masked {
+    fn reset(&mut self) {
+        self.dev.borrow_mut().reset();
+    }
+}
+
+impl Device for Masked {
+    fn contains(&self, index: usize) -> bool {
+        self.dev.borrow().contains(index)
+    }
+
+    fn len(&self) -> usize {
+        self.dev.borrow().len()
+    }
+
+    fn read(&self, index: usize) -> u8 {
+        self.unused | self.dev.borrow().read(index)
+    }
+
+    fn write(&mut self, index: usize, value: u8) {
+        let mut dev = self.dev.borrow_mut();
+        let prev = dev.read(index);
+        dev.write(index, prev ^ ((prev ^ value) & self.writable));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use remus::dev::Null;
+    use remus::mem::Ram;
+
+    use super::*;
+
+    #[test]
+    fn device_read_works() {
+        let masked = Masked::with(Null::<0x10>::with(0x05).to_shared(), 0x80, 0x78);
+        assert!((0x0..0x10)
+            .map(|addr| masked.read(addr))
+            .all(|byte| byte == 0x85));
+    }
+
+    #[test]
+    fn device_write_works() {
+        let ram = Ram::<0x10>::new().to_shared();
+        (0x0..0x10).for_each(|addr| ram.borrow_mut().write(addr, 0x07));
+        let mut masked = Masked::with(ram.clone(), 0x80, 0x78);
+        (0x0..0x10).for_each(|addr| masked.write(addr, 0xf8));
+        assert!((0x0..0x10)
+            .map(|addr| ram.borrow().read(addr))
+            .all(|byte| byte == 0x7f));
+    }
+}
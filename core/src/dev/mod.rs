@@ -3,8 +3,12 @@
 //! The following are specicialized [`Device`](remus::Device) implementations
 //! useful for Game Boy emulation.
 
+mod masked;
+mod overlay;
 mod readonly;
 mod unmapped;
 
+pub use self::masked::Masked;
+pub use self::overlay::Overlay;
 pub use self::readonly::ReadOnly;
-pub use self::unmapped::Unmapped;
+pub use self::unmapped::{mix, Unmapped};
@@ -0,0 +1,109 @@
+use std::collections::BTreeMap;
+
+use remus::{Block, Device, SharedDevice};
+
+/// Overlay device.
+///
+/// # Usage
+///
+/// `Overlay` patches the values read from the internal device, leaving the
+/// device itself untouched. Writes are passed through to the internal device,
+/// such that they take effect once a patch is cleared.
+///
+/// This can be used to live-edit memory, such as when debugging or applying
+/// cheat codes.
+#[derive(Debug)]
+pub struct Overlay {
+    dev: SharedDevice,
+    patches: BTreeMap<usize, u8>,
+}
+
+impl Overlay {
+    /// Patches the value read at `index`.
+    pub fn patch(&mut self, index: usize, value: u8) {
+        self.patches.insert(index, value);
+    }
+
+    /// Clears any patch at `index`.
+    pub fn clear_patch(&mut self, index: usize) {
+        self.patches.remove(&index);
+    }
+}
+
+impl Block for Overlay {
+    fn reset(&mut self) {
+        self.dev.borrow_mut().reset();
+    }
+}
+
+impl Device for Overlay {
+    fn contains(&self, index: usize) -> bool {
+        self.dev.borrow().contains(index)
+    }
+
+    fn len(&self) -> usize {
+        self.dev.borrow().len()
+    }
+
+    fn read(&self, index: usize) -> u8 {
+        match self.patches.get(&index) {
+            Some(&value) => value,
+            None => self.dev.borrow().read(index),
+        }
+    }
+
+    fn write(&mut self, index: usize, value: u8) {
+        self.dev.borrow_mut().write(index, value);
+    }
+}
+
+impl From<SharedDevice> for Overlay {
+    fn from(dev: SharedDevice) -> Self {
+        Self {
+            dev,
+            patches: BTreeMap::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use remus::dev::Null;
+
+    use super::*;
+
+    fn setup() -> Overlay {
+        let rom = Null::<0x100>::with(0x55).to_shared();
+        Overlay::from(rom)
+    }
+
+    #[test]
+    fn patch_works() {
+        let mut overlay = setup();
+        overlay.patch(0x42, 0xaa);
+        assert_eq!(overlay.read(0x41), 0x55);
+        assert_eq!(overlay.read(0x42), 0xaa);
+        assert_eq!(overlay.read(0x43), 0x55);
+    }
+
+    #[test]
+    fn clear_patch_works() {
+        let mut overlay = setup();
+        overlay.patch(0x42, 0xaa);
+        overlay.clear_patch(0x42);
+        assert!((0x000..0x100)
+            .map(|addr| overlay.read(addr))
+            .all(|byte| byte == 0x55));
+    }
+
+    #[test]
+    fn device_write_works() {
+        let ram = remus::mem::Ram::<0x100>::new().to_shared();
+        let mut overlay = Overlay::from(ram.clone());
+        overlay.patch(0x42, 0xaa);
+        overlay.write(0x42, 0x11);
+        // The patch persists over the written value
+        assert_eq!(overlay.read(0x42), 0xaa);
+        assert_eq!(ram.borrow().read(0x42), 0x11);
+    }
+}
@@ -1,4 +1,7 @@
-use log::warn;
+use std::cell::Cell;
+use std::rc::Rc;
+
+use log::trace;
 use remus::dev::Null;
 use remus::{Block, Device};
 
@@ -7,30 +10,55 @@ use remus::{Block, Device};
 /// # Usage
 ///
 /// The `Unmapped` device ialways yields the same "garbage" values when read,
-/// and ignores all writes. This can be useful to warn of unmapped accesses
+/// and ignores all writes. This can be useful to trace unmapped accesses
 /// instead of causing a panic.
 ///
 /// It behaves differently from [`Null`](remus::dev::Null) in that reads and
-/// writes are logged, instead of completely ignored. Furthermore, it has a
-/// default domain of the entire 16-bit address space.
+/// writes are logged (at trace level, as games routinely perform them),
+/// instead of completely ignored. Furthermore, it has a default domain of the
+/// entire 16-bit address space.
+///
+/// When seeded, reads instead yield pseudo-random values derived from the
+/// seed, the address, and the last value on the bus. These are
+/// deterministic, such that runs with the same seed are reproducible.
 #[derive(Debug)]
-pub struct Unmapped<const N: usize = 0x10000>(Null<N>);
+pub struct Unmapped<const N: usize = 0x10000> {
+    dev: Null<N>,
+    seed: Option<u64>,
+    bus: Rc<Cell<u8>>,
+}
 
 impl<const N: usize> Unmapped<N> {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Constructs a new `Unmapped` whose reads are derived from `seed`.
+    ///
+    /// NOTE: The last value on the bus is shared through `bus`, which must be
+    ///       kept up to date by the bus' owner.
+    pub fn with_seed(seed: u64, bus: Rc<Cell<u8>>) -> Self {
+        Self {
+            seed: Some(seed),
+            bus,
+            ..Default::default()
+        }
+    }
 }
 
 impl<const N: usize> Block for Unmapped<N> {
     fn reset(&mut self) {
-        self.0.reset();
+        self.dev.reset();
     }
 }
 
 impl<const N: usize> Default for Unmapped<N> {
     fn default() -> Self {
-        Self(Null::with(0xff))
+        Self {
+            dev: Null::with(0xff),
+            seed: None,
+            bus: Rc::default(),
+        }
     }
 }
 
@@ -40,19 +68,30 @@ impl<const N: usize> Device for Unmapped<N> {
     }
 
     fn len(&self) -> usize {
-        self.0.len()
+        self.dev.len()
     }
 
     fn read(&self, index: usize) -> u8 {
-        warn!("called `Device::read({index:#06x})` on an `Unmapped`");
-        self.0.read(index)
+        trace!("called `Device::read({index:#06x})` on an `Unmapped`");
+        let Some(seed) = self.seed else {
+            return self.dev.read(index);
+        };
+        // Mix the inputs
+        mix(seed ^ ((index as u64) << 8) ^ self.bus.get() as u64) as u8
     }
 
     fn write(&mut self, index: usize, value: u8) {
-        warn!("called `Device::write({index:#06x}, {value:#04x})` on an `Unmapped`");
+        trace!("called `Device::write({index:#06x}, {value:#04x})` on an `Unmapped`");
     }
 }
 
+/// Mixes a value into a pseudo-random one (using SplitMix64's finalizer).
+pub fn mix(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -84,6 +123,21 @@ mod tests {
             .all(|byte| byte == 0xff));
     }
 
+    #[test]
+    fn with_seed_works() {
+        let read = |seed, last| {
+            let unmapped = Unmapped::<0x10000>::with_seed(seed, Rc::new(Cell::new(last)));
+            (0x000..0x100)
+                .map(|addr| unmapped.read(addr))
+                .collect::<Vec<_>>()
+        };
+        // Reads are reproducible with the same seed
+        assert_eq!(read(0x5eed, 0x00), read(0x5eed, 0x00));
+        assert_ne!(read(0x5eed, 0x00), read(0xbeef, 0x00));
+        // Reads depend on the last value on the bus
+        assert_ne!(read(0x5eed, 0x00), read(0x5eed, 0x42));
+    }
+
     #[test]
     fn device_write_works() {
         let mut unmapped = Unmapped::<0x10000>::new();
@@ -0,0 +1,47 @@
+//! Clock interface.
+
+use std::cell::Cell;
+use std::fmt::Debug;
+use std::rc::Rc;
+use std::time::{Duration, SystemTime};
+
+/// Time source.
+///
+/// Consulted by any component that keeps wall-clock time, such as a
+/// cartridge's real-time clock.
+pub trait Clock: Debug {
+    /// Gets the current time, relative to an arbitrary (but fixed) epoch.
+    fn now(&self) -> Duration;
+}
+
+/// Clock backed by the system's wall-clock time.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+    }
+}
+
+/// Clock that only advances on command.
+///
+/// Clones share the same time, allowing a clock to be advanced after it has
+/// been handed to a component.
+#[derive(Clone, Debug, Default)]
+pub struct ManualClock(Rc<Cell<Duration>>);
+
+impl ManualClock {
+    /// Advances the clock by the provided duration.
+    pub fn advance(&self, by: Duration) {
+        self.0.set(self.0.get() + by);
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Duration {
+        self.0.get()
+    }
+}
@@ -0,0 +1,34 @@
+//! Callback hooks.
+
+use std::fmt::Debug;
+
+/// Optional user-provided callback.
+pub struct Hook<F: ?Sized>(Option<Box<F>>);
+
+impl<F: ?Sized> Hook<F> {
+    /// Gets a mutable reference to the callback, if any.
+    pub fn get(&mut self) -> Option<&mut F> {
+        self.0.as_deref_mut()
+    }
+}
+
+impl<F: ?Sized> Debug for Hook<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            Some(_) => write!(f, "Hook(Some(..))"),
+            None => write!(f, "Hook(None)"),
+        }
+    }
+}
+
+impl<F: ?Sized> Default for Hook<F> {
+    fn default() -> Self {
+        Self(None)
+    }
+}
+
+impl<F: ?Sized> From<Box<F>> for Hook<F> {
+    fn from(f: Box<F>) -> Self {
+        Self(Some(f))
+    }
+}
@@ -2,7 +2,10 @@
 
 use remus::Machine;
 
+pub mod clock;
+pub(crate) mod hook;
 pub mod joypad;
+pub(crate) mod png;
 pub mod screen;
 
 /// Emulator interface.
@@ -21,3 +24,49 @@ pub trait Emulator: Machine {
     /// Redraws the screen using the current state.
     fn redraw(&self, callback: impl FnMut(&Self::Screen));
 }
+
+/// Frontend interface.
+///
+/// Defines the input, video, and audio backends used to drive an emulator.
+///
+/// NOTE: Input and frames are typed by the [`Emulator`] being driven. For a
+///       [`GameBoy`](crate::dmg::GameBoy), keys are a
+///       [`JoypadState`](crate::dmg::JoypadState), and a frame's
+///       [`Screen`](crate::dmg::Screen) dereferences to a slice of colours.
+pub trait Frontend<E: Emulator + ?Sized> {
+    /// Polls for currently pressed keys, or `None` to stop emulation.
+    fn poll_input(&mut self) -> Option<Vec<E::Input>>;
+
+    /// Presents a completed frame.
+    fn present(&mut self, frame: &E::Screen);
+
+    /// Queues stereo audio samples for playback.
+    fn queue_audio(&mut self, samples: &[(f32, f32)]);
+}
+
+/// Headless frontend.
+///
+/// Discards all output, stopping emulation after a fixed number of frames.
+#[derive(Debug, Default)]
+pub struct Headless {
+    frames: usize,
+}
+
+impl Headless {
+    /// Constructs a new `Headless` frontend that runs for `frames` frames.
+    #[must_use]
+    pub fn new(frames: usize) -> Self {
+        Self { frames }
+    }
+}
+
+impl<E: Emulator + ?Sized> Frontend<E> for Headless {
+    fn poll_input(&mut self) -> Option<Vec<E::Input>> {
+        self.frames = self.frames.checked_sub(1)?;
+        Some(Vec::new())
+    }
+
+    fn present(&mut self, _: &E::Screen) {}
+
+    fn queue_audio(&mut self, _: &[(f32, f32)]) {}
+}
@@ -0,0 +1,134 @@
+//! PNG image encoder.
+//!
+//! Produces uncompressed (stored) images, which avoids pulling in a deflate
+//! implementation for the sake of the occasional screenshot.
+
+/// PNG file signature.
+const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+
+/// Maximum length of a stored deflate block.
+const BLOCK: usize = 0xffff;
+
+/// Encodes 8-bit RGBA pixels as a PNG image.
+///
+/// # Panics
+///
+/// Panics if the number of pixels does not match the provided dimensions.
+pub fn encode(width: u32, height: u32, rgba: &[u8]) -> Vec<u8> {
+    assert_eq!(rgba.len(), 4 * width as usize * height as usize);
+
+    // Prefix each row with its filter type (none)
+    let raw: Vec<u8> = rgba
+        .chunks(4 * width as usize)
+        .flat_map(|row| std::iter::once(0).chain(row.iter().copied()))
+        .collect();
+
+    // Wrap the rows in a zlib stream of stored blocks
+    let mut zlib = vec![0x78, 0x01];
+    let mut blocks = raw.chunks(BLOCK).peekable();
+    if blocks.peek().is_none() {
+        zlib.extend([0x01, 0x00, 0x00, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        let len = block.len() as u16;
+        zlib.push(blocks.peek().is_none() as u8);
+        zlib.extend(len.to_le_bytes());
+        zlib.extend((!len).to_le_bytes());
+        zlib.extend(block);
+    }
+    zlib.extend(adler32(&raw).to_be_bytes());
+
+    // Assemble the image
+    let mut ihdr = Vec::with_capacity(13);
+    ihdr.extend(width.to_be_bytes());
+    ihdr.extend(height.to_be_bytes());
+    // NOTE: 8-bit depth, truecolor with alpha, default compression and
+    //       filtering, no interlacing
+    ihdr.extend([8, 6, 0, 0, 0]);
+    let mut png = SIGNATURE.to_vec();
+    chunk(&mut png, b"IHDR", &ihdr);
+    chunk(&mut png, b"IDAT", &zlib);
+    chunk(&mut png, b"IEND", &[]);
+    png
+}
+
+/// Appends a chunk to the image.
+fn chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend((data.len() as u32).to_be_bytes());
+    png.extend(kind);
+    png.extend(data);
+    png.extend(crc32(kind.iter().chain(data)).to_be_bytes());
+}
+
+/// Computes the CRC-32 of a chunk.
+fn crc32<'a>(data: impl IntoIterator<Item = &'a u8>) -> u32 {
+    !data.into_iter().fold(!0u32, |crc, &byte| {
+        (0..8).fold(crc ^ byte as u32, |crc, _| {
+            (crc >> 1) ^ (0xedb8_8320 & (crc & 1).wrapping_neg())
+        })
+    })
+}
+
+/// Computes the Adler-32 checksum of a zlib stream.
+fn adler32(data: &[u8]) -> u32 {
+    let (a, b) = data.iter().fold((1u32, 0u32), |(a, b), &byte| {
+        let a = (a + byte as u32) % 65521;
+        (a, (b + a) % 65521)
+    });
+    (b << 16) | a
+}
+
+/// Decodes an image produced by [`encode`].
+///
+/// Returns the image's dimensions alongside its RGBA pixels.
+#[cfg(test)]
+pub fn decode(png: &[u8]) -> (u32, u32, Vec<u8>) {
+    assert_eq!(png[..8], SIGNATURE);
+    let width = u32::from_be_bytes(png[16..20].try_into().unwrap());
+    let height = u32::from_be_bytes(png[20..24].try_into().unwrap());
+    // Extract the zlib stream (IHDR spans 25 bytes)
+    let idat = &png[33..];
+    let len = u32::from_be_bytes(idat[..4].try_into().unwrap()) as usize;
+    assert_eq!(&idat[4..8], b"IDAT");
+    let mut zlib = &idat[10..8 + len - 4];
+    // Concatenate the stored blocks
+    let mut raw = Vec::new();
+    while !zlib.is_empty() {
+        let len = u16::from_le_bytes([zlib[1], zlib[2]]) as usize;
+        raw.extend(&zlib[5..5 + len]);
+        zlib = &zlib[5 + len..];
+    }
+    // Strip each row's filter type
+    let rgba = raw
+        .chunks(1 + 4 * width as usize)
+        .flat_map(|row| row[1..].iter().copied())
+        .collect();
+    (width, height, rgba)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crc32_works() {
+        assert_eq!(crc32(b"IEND"), 0xae42_6082);
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+    }
+
+    #[test]
+    fn adler32_works() {
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+    }
+
+    #[test]
+    fn encode_works() {
+        let rgba: Vec<u8> = (0..=u8::MAX).cycle().take(4 * 200 * 100).collect();
+        let png = encode(200, 100, &rgba);
+        assert_eq!(
+            png[png.len() - 12..],
+            [0, 0, 0, 0, b'I', b'E', b'N', b'D', 0xae, 0x42, 0x60, 0x82]
+        );
+        assert_eq!(decode(&png), (200, 100, rgba));
+    }
+}
@@ -4,11 +4,36 @@ use std::str::Utf8Error;
 use log::warn;
 use thiserror::Error;
 
+use super::{Issue, ValidationReport};
+
+/// Nintendo logo, as expected by the boot ROM.
+pub(super) const LOGO: [u8; 0x30] = [
+    0xce, 0xed, 0x66, 0x66, 0xcc, 0x0d, 0x00, 0x0b, 0x03, 0x73, 0x00, 0x83, 0x00, 0x0c, 0x00, 0x0d,
+    0x00, 0x08, 0x11, 0x1f, 0x88, 0x89, 0x00, 0x0e, 0xdc, 0xcc, 0x6e, 0xe6, 0xdd, 0xdd, 0xd9, 0x99,
+    0xbb, 0xbb, 0x67, 0x63, 0x6e, 0x0e, 0xec, 0xcc, 0xdd, 0xdc, 0x99, 0x9f, 0xbb, 0xb9, 0x33, 0x3e,
+];
+
+/// Builds a blank ROM of `len` bytes with a valid header.
+///
+/// The header declares the cartridge type and title provided, along with a
+/// ROM size matching `len`.
+#[cfg(test)]
+pub(crate) fn rom(len: usize, cart: u8, title: &[u8]) -> Vec<u8> {
+    let mut rom = vec![0; len];
+    rom[0x104..0x134].copy_from_slice(&LOGO);
+    rom[0x134..0x134 + title.len()].copy_from_slice(title);
+    rom[0x147] = cart;
+    rom[0x148] = (len >> 15).trailing_zeros() as u8;
+    rom[0x14d] = Header::hchk(&rom);
+    rom
+}
+
 /// Cartridge header.
 ///
 /// Information about the ROM and the cartridge containing it. Stored in the
 /// byte range `[0x100, 0x150)`.
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Header {
     /// Equality with boot ROM's Nintendo logo.
     pub logo: bool,
@@ -28,6 +53,10 @@ pub struct Header {
     pub ramsz: usize,
     /// Destination code (Japan/Worldwide)
     pub jpn: bool,
+    /// Old licensee code.
+    pub oldlic: u8,
+    /// New licensee code (only used when the old code is `0x33`).
+    pub newlic: [u8; 2],
     /// Revision number of this ROM.
     pub version: u8,
     /// 8-bit header checksum.
@@ -37,6 +66,147 @@ pub struct Header {
 }
 
 impl Header {
+    /// Gets the title of this ROM, without padding.
+    ///
+    /// On the CGB, the last 4 bytes of the title may instead hold a
+    /// manufacturer code. As there's no flag to indicate this, they're assumed
+    /// to be such a code whenever they're all uppercase alphanumeric.
+    #[must_use]
+    pub fn title(&self) -> String {
+        let bytes = self.title.as_bytes();
+        let mfr = bytes.get(11..15).filter(|code| {
+            code.iter()
+                .all(|byte| byte.is_ascii_uppercase() || byte.is_ascii_digit())
+        });
+        let title = match (self.cgb, mfr) {
+            (true, Some(_)) => &bytes[..11],
+            _ => bytes,
+        };
+        String::from_utf8_lossy(title)
+            .trim_end_matches(['\0', ' '])
+            .to_string()
+    }
+
+    /// Checks if this ROM's logo matches the one expected by the boot ROM.
+    ///
+    /// Otherwise, the boot ROM locks up. Such ROMs may still be run by
+    /// skipping the boot ROM (see
+    /// [`GameBoy::skip_boot`](crate::dmg::GameBoy::skip_boot)).
+    #[must_use]
+    pub fn logo_valid(&self) -> bool {
+        self.logo
+    }
+
+    /// Checks this header for compatibility issues, given the ROM's `len`.
+    ///
+    /// As this only inspects the header, it can be used to diagnose ROMs that
+    /// fail to load as a [`Cartridge`](super::Cartridge).
+    #[must_use]
+    pub fn validate(&self, len: usize) -> ValidationReport {
+        let mut issues = Vec::new();
+
+        // Check ROM size
+        if len != self.romsz {
+            issues.push(Issue::RomSize {
+                declared: self.romsz,
+                found: len,
+            });
+        }
+
+        // Check MBC support, and its maximum RAM size
+        let ram = match self.cart {
+            CartridgeType::NoMbc { ram, .. } => Some((ram, 0x2000)),
+            CartridgeType::Mbc1 { ram, .. } => Some((ram, 0x8000)),
+            CartridgeType::Mbc3 { ram, .. } => Some((ram, 0x10000)),
+            _ => None,
+        };
+        match ram {
+            Some((ram, max)) => {
+                if ram != (self.ramsz > 0) || self.ramsz > max {
+                    issues.push(Issue::RamSize(self.ramsz));
+                }
+            }
+            None => issues.push(Issue::Unsupported),
+        }
+
+        // Check logo
+        // NOTE: The boot ROM locks up if the logo doesn't match.
+        if !self.logo {
+            issues.push(Issue::Logo);
+        }
+
+        ValidationReport { issues }
+    }
+
+    /// Gets the name of this ROM's licensee (publisher).
+    ///
+    /// Unknown licensees are reported by their code.
+    #[must_use]
+    pub fn licensee(&self) -> String {
+        match self.oldlic {
+            0x33 => match &self.newlic {
+                b"00" => "None",
+                b"01" => "Nintendo",
+                b"08" => "Capcom",
+                b"13" => "Electronic Arts",
+                b"18" => "Hudson Soft",
+                b"20" => "KSS",
+                b"28" => "Kemco",
+                b"31" => "Nintendo",
+                b"34" => "Konami",
+                b"41" => "Ubi Soft",
+                b"51" => "Acclaim",
+                b"52" => "Activision",
+                b"54" => "Konami",
+                b"64" => "LucasArts",
+                b"69" => "Electronic Arts",
+                b"70" => "Infogrames",
+                b"78" => "THQ",
+                b"79" => "Accolade",
+                b"A4" => "Konami",
+                b"B2" => "Bandai",
+                b"C3" => "Square",
+                b"E9" => "Natsume",
+                code => return String::from_utf8_lossy(code).to_string(),
+            },
+            0x00 => "None",
+            0x01 => "Nintendo",
+            0x08 => "Capcom",
+            0x09 => "Hot-B",
+            0x0a => "Jaleco",
+            0x13 => "Electronic Arts",
+            0x18 => "Hudson Soft",
+            0x1f => "Virgin Interactive",
+            0x28 => "Kemco",
+            0x31 => "Nintendo",
+            0x34 => "Konami",
+            0x41 => "Ubi Soft",
+            0x51 => "Acclaim",
+            0x52 => "Activision",
+            0x67 => "Ocean",
+            0x69 => "Electronic Arts",
+            0x70 => "Infogrames",
+            0x78 => "THQ",
+            0x79 => "Accolade",
+            0xa4 => "Konami",
+            0xb2 => "Bandai",
+            0xc3 => "Squaresoft",
+            0xe9 => "Natsume",
+            code => return format!("{code:#04x}"),
+        }
+        .to_string()
+    }
+
+    /// Gets the region this ROM was released in.
+    #[must_use]
+    pub fn region(&self) -> &'static str {
+        if self.jpn {
+            "Japan"
+        } else {
+            "Overseas"
+        }
+    }
+
     /// Checks header integrity.
     pub fn check(rom: &[u8]) -> Result<(), Error> {
         // Extract the header bytes
@@ -94,7 +264,7 @@ impl Display for Header {
         writeln!(
             f,
             "│ {:^16} │",
-            match self.title.replace('\0', " ").trim() {
+            match self.title().as_str() {
                 "" => "Unknown",
                 title => title,
             }
@@ -117,7 +287,7 @@ impl Display for Header {
         writeln!(f, "│ ROM: {:>9} B │", self.romsz)?;
         writeln!(f, "│ RAM: {:>9} B │", self.ramsz)?;
         writeln!(f, "├──────────────────┤")?;
-        writeln!(f, "│ Japan: {:>9} │", self.jpn)?;
+        writeln!(f, "│ Region: {:>8} │", self.region())?;
         writeln!(
             f,
             "│ Version: {:>7} │",
@@ -146,13 +316,7 @@ impl TryFrom<&[u8]> for Header {
             .unwrap();
 
         // Parse Nintendo logo
-        let logo = header[0x04..=0x33]
-            == [
-                0xce, 0xed, 0x66, 0x66, 0xcc, 0x0d, 0x00, 0x0b, 0x03, 0x73, 0x00, 0x83, 0x00, 0x0c,
-                0x00, 0x0d, 0x00, 0x08, 0x11, 0x1f, 0x88, 0x89, 0x00, 0x0e, 0xdc, 0xcc, 0x6e, 0xe6,
-                0xdd, 0xdd, 0xd9, 0x99, 0xbb, 0xbb, 0x67, 0x63, 0x6e, 0x0e, 0xec, 0xcc, 0xdd, 0xdc,
-                0x99, 0x9f, 0xbb, 0xb9, 0x33, 0x3e,
-            ];
+        let logo = header[0x04..=0x33] == LOGO;
         // Parse title
         let tlen = if header[0x43] & 0x80 != 0 { 15 } else { 16 };
         let title = std::str::from_utf8(&header[0x34..0x34 + tlen])
@@ -193,6 +357,9 @@ impl TryFrom<&[u8]> for Header {
             0x01 => Ok(false),
             byte => Err(Error::DestinationCode(byte)),
         }?;
+        // Parse licensee codes
+        let oldlic = header[0x4b];
+        let newlic = header[0x44..=0x45].try_into().unwrap();
         // Parse mark ROM version number
         let version = header[0x4c];
         // Parse header checksum
@@ -224,6 +391,8 @@ impl TryFrom<&[u8]> for Header {
             romsz,
             ramsz,
             jpn,
+            oldlic,
+            newlic,
             version,
             hchk,
             gchk,
@@ -233,6 +402,7 @@ impl TryFrom<&[u8]> for Header {
 
 /// Cartridge information.
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum CartridgeType {
     NoMbc {
         ram: bool,
@@ -481,10 +651,38 @@ mod tests {
                 romsz: 0x8000,
                 ramsz: 0x2000,
                 jpn: false,
+                oldlic: 0x00,
+                newlic: [0x00; 2],
                 version: 0,
                 hchk: 0xdc,
                 gchk: 0x31bb,
             }
         );
     }
+
+    #[test]
+    fn title_works() {
+        // Patches the header's title, CGB flag, and licensee
+        fn patch(title: &[u8], cgb: u8) -> Header {
+            let mut rom = rom(0x8000, 0x00, title);
+            rom[0x143] = cgb;
+            rom[0x14a] = 0x01;
+            rom[0x14b] = 0x01;
+            rom[0x14d] = Header::hchk(&rom);
+            Header::try_from(&rom[..]).unwrap()
+        }
+
+        let header = patch(b"TETRIS", 0x00);
+        assert_eq!(header.title(), "TETRIS");
+        assert_eq!(header.licensee(), "Nintendo");
+        assert_eq!(header.region(), "Overseas");
+        // DMG titles never end with a manufacturer code
+        let header = patch(b"POKEMON_SLVAAXE", 0x00);
+        assert_eq!(header.title(), "POKEMON_SLVAAXE");
+        // CGB titles may end with a manufacturer code
+        let header = patch(b"POKEMON_SLVAAXE", 0x80);
+        assert_eq!(header.title(), "POKEMON_SLV");
+        let header = patch(b"TETRIS DX", 0x80);
+        assert_eq!(header.title(), "TETRIS DX");
+    }
 }
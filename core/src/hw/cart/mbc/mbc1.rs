@@ -1,12 +1,9 @@
 use std::cell::RefCell;
 use std::rc::Rc;
 
-use remus::bus::adapt::{Bank, View};
-use remus::bus::Bus;
-use remus::dev::Null;
 use remus::{Block, Device, SharedDevice};
 
-use super::Mbc;
+use super::{Mbc, MbcState};
 
 /// MBC1 cartridge type.
 #[derive(Debug)]
@@ -17,53 +14,23 @@ pub struct Mbc1 {
 
 impl Mbc1 {
     /// Constructs a new `Mbc1` with the provided configuration.
-    pub fn with(rom: SharedDevice, ram: SharedDevice, _battery: bool) -> Self {
+    ///
+    /// Multicart (MBC1M) cartridges are wired such that only the lower 4 bits
+    /// of the primary bank number are used, with the secondary bank number
+    /// selecting between the games on the cartridge.
+    pub fn with(rom: SharedDevice, ram: SharedDevice, _battery: bool, multicart: bool) -> Self {
+        // Prepare control registers
+        let ctl = Rc::new(RefCell::new(Control {
+            multicart,
+            ..Default::default()
+        }));
         // Prepare RAM
-        #[allow(clippy::vec_init_then_push)]
-        let ram = {
-            // Determine how many banks to create
-            let ramsz = ram.borrow().len();
-            let nbanks = ramsz / 0x4000;
-            // Create banks as `View`s of the RAM
-            let mut banks: Vec<SharedDevice> = Default::default();
-            banks.push(Null::<0>::new().to_shared()); // disable RAM at index 0
-            for i in 0..nbanks {
-                let range = (0x4000 * i)..(0x4000 * (i + 1));
-                banks.push(View::new(ram.clone(), range).to_shared());
-            }
-            // Create the RAM bank object
-            let bank = Bank::from(banks);
-            let bank = Rc::new(RefCell::new(bank));
-
-            Ram(bank)
+        let ram = Ram {
+            ram,
+            ctl: ctl.clone(),
         };
         // Prepare ROM
-        let rom = {
-            // Determine how many banks to create
-            let romsz = rom.borrow().len();
-            let nbanks = romsz / 0x4000;
-            // Create banks as `View`s of the ROM
-            let mut banks: Vec<SharedDevice> = Default::default();
-            for i in 0..nbanks {
-                let range = (0x4000 * i)..(0x4000 * (i + 1));
-                banks.push(View::new(rom.clone(), range).to_shared());
-            }
-            // Create the ROM bank object
-            let rom0 = banks.remove(0);
-            let bank = Bank::from(banks);
-            let rom = Rc::new(RefCell::new(bank));
-            // Use a bus to join ROM banks together
-            let mut bus = Bus::new();
-            bus.map(0x0000, rom0);
-            bus.map(0x4000, rom.clone());
-            let bus = Rc::new(RefCell::new(bus));
-
-            Rom {
-                bus,
-                rom,
-                ram: ram.0.clone(),
-            }
-        };
+        let rom = Rom { rom, ctl };
 
         Self {
             rom: Rc::new(RefCell::new(rom)),
@@ -89,60 +56,149 @@ impl Mbc for Mbc1 {
     fn ram(&self) -> SharedDevice {
         self.ram.clone()
     }
+
+    fn state(&self) -> MbcState {
+        let ctl = self.rom.borrow().ctl.clone();
+        let ctl = ctl.borrow();
+        MbcState::Mbc1 {
+            bank1: ctl.bank1,
+            bank2: ctl.bank2,
+            mode: ctl.mode,
+            ena: ctl.ena,
+        }
+    }
+
+    fn set_state(&mut self, state: &MbcState) {
+        if let &MbcState::Mbc1 {
+            bank1,
+            bank2,
+            mode,
+            ena,
+        } = state
+        {
+            let ctl = self.rom.borrow().ctl.clone();
+            let mut ctl = ctl.borrow_mut();
+            ctl.bank1 = bank1;
+            ctl.bank2 = bank2;
+            ctl.mode = mode;
+            ctl.ena = ena;
+        }
+    }
+}
+
+/// MBC1 control registers.
+#[derive(Debug, Default)]
+struct Control {
+    /// Primary (5-bit) bank number.
+    bank1: u8,
+    /// Secondary (2-bit) bank number.
+    bank2: u8,
+    /// Banking mode select.
+    mode: bool,
+    /// RAM enable.
+    ena: bool,
+    /// Multicart (MBC1M) wiring.
+    multicart: bool,
+}
+
+impl Control {
+    /// Width of the primary bank number as wired.
+    fn width(&self) -> u8 {
+        if self.multicart {
+            4
+        } else {
+            5
+        }
+    }
+
+    /// Gets the ROM bank mapped at `0x0000..=0x3fff`.
+    fn rom0(&self) -> usize {
+        if self.mode {
+            (self.bank2 << self.width()) as usize
+        } else {
+            0
+        }
+    }
+
+    /// Gets the ROM bank mapped at `0x4000..=0x7fff`.
+    fn rom1(&self) -> usize {
+        // NOTE: A primary bank number of zero is always translated to one,
+        //       even if the zeroed bits are unused by the wiring.
+        let bank1 = match self.bank1 {
+            0x00 => 0x01,
+            bank => bank,
+        };
+        let bank1 = bank1 & !(u8::MAX << self.width());
+        ((self.bank2 << self.width()) | bank1) as usize
+    }
+
+    /// Gets the RAM bank mapped at `0xa000..=0xbfff`.
+    fn ram(&self) -> usize {
+        if self.mode {
+            self.bank2 as usize
+        } else {
+            0
+        }
+    }
+}
+
+impl Block for Control {
+    fn reset(&mut self) {
+        // NOTE: The cartridge's wiring is preserved
+        self.bank1 = Default::default();
+        self.bank2 = Default::default();
+        self.mode = Default::default();
+        self.ena = Default::default();
+    }
 }
 
 /// MBC1 ROM.
 #[derive(Debug)]
 struct Rom {
-    bus: Rc<RefCell<Bus>>,
-    rom: Rc<RefCell<Bank>>,
-    ram: Rc<RefCell<Bank>>,
+    rom: SharedDevice,
+    ctl: Rc<RefCell<Control>>,
 }
 
 impl Block for Rom {
     fn reset(&mut self) {
-        // Reset bus
-        self.bus.borrow_mut().reset();
-        // Reset ROM
-        self.rom.borrow_mut().reset();
-        // Reset RAM
-        self.ram.borrow_mut().reset();
+        // Reset control registers
+        self.ctl.borrow_mut().reset();
     }
 }
 
 impl Device for Rom {
     fn contains(&self, index: usize) -> bool {
-        self.bus.borrow().contains(index)
+        index < self.len()
     }
 
     fn len(&self) -> usize {
-        self.bus.borrow().len()
+        0x8000
     }
 
     fn read(&self, index: usize) -> u8 {
-        self.bus.borrow().read(index)
+        // Determine the selected bank
+        let ctl = self.ctl.borrow();
+        let bank = match index {
+            0x0000..=0x3fff => ctl.rom0(),
+            _ => ctl.rom1(),
+        };
+        // Read from the bank
+        // NOTE: Unused upper bank bits are ignored by wrapping the address
+        let rom = self.rom.borrow();
+        rom.read((0x4000 * bank + (index & 0x3fff)) % rom.len())
     }
 
     fn write(&mut self, index: usize, value: u8) {
+        let mut ctl = self.ctl.borrow_mut();
         match index {
             // RAM Enable
-            0x0000..=0x1fff => {
-                // TODO: RAM Enable
-            }
+            0x0000..=0x1fff => ctl.ena = value & 0x0f == 0x0a,
             // ROM Bank Number
-            0x2000..=0x3fff => {
-                // FIXME: Mask depending on ROM size
-                self.rom.borrow_mut().set(match value & 0x1f {
-                    0x00 => 0x00,
-                    bank => bank - 1,
-                } as usize);
-            }
-            0x4000..=0x5fff => {
-                // TODO: RAM Bank Number - or - Upper Bits of ROM Bank Number
-            }
-            0x6000..=0x7fff => {
-                // TODO: Banking Mode Select
-            }
+            0x2000..=0x3fff => ctl.bank1 = value & 0x1f,
+            // RAM Bank Number - or - Upper Bits of ROM Bank Number
+            0x4000..=0x5fff => ctl.bank2 = value & 0x03,
+            // Banking Mode Select
+            0x6000..=0x7fff => ctl.mode = value & 0x01 != 0,
             _ => panic!(), // TODO: some error here
         }
     }
@@ -150,29 +206,161 @@ impl Device for Rom {
 
 /// MBC1 RAM.
 #[derive(Debug)]
-struct Ram(Rc<RefCell<Bank>>);
+struct Ram {
+    ram: SharedDevice,
+    ctl: Rc<RefCell<Control>>,
+}
+
+impl Ram {
+    /// Translates an index into the selected RAM bank.
+    fn addr(&self, index: usize) -> usize {
+        let bank = self.ctl.borrow().ram();
+        (0x2000 * bank + index) % self.ram.borrow().len()
+    }
+}
 
 impl Block for Ram {
     fn reset(&mut self) {
         // Reset RAM
-        self.0.borrow_mut().reset();
+        self.ram.borrow_mut().reset();
     }
 }
 
 impl Device for Ram {
     fn contains(&self, index: usize) -> bool {
-        self.0.borrow().contains(index)
+        index < self.len()
     }
 
     fn len(&self) -> usize {
-        self.0.borrow().len()
+        self.ram.borrow().len().min(0x2000)
     }
 
     fn read(&self, index: usize) -> u8 {
-        self.0.borrow().read(index)
+        // NOTE: Reads are fixed at `0xff` until RAM is enabled, rather than
+        //       the last value on the bus
+        if !self.ctl.borrow().ena {
+            return 0xff;
+        }
+        let addr = self.addr(index);
+        self.ram.borrow().read(addr)
     }
 
     fn write(&mut self, index: usize, value: u8) {
-        self.0.borrow_mut().write(index, value);
+        // NOTE: Writes are ignored until RAM is enabled
+        if !self.ctl.borrow().ena {
+            return;
+        }
+        let addr = self.addr(index);
+        self.ram.borrow_mut().write(addr, value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::iter;
+
+    use remus::dev::Null;
+    use remus::mem::Rom;
+
+    use super::*;
+
+    fn setup(multicart: bool) -> Mbc1 {
+        // Label each ROM bank with its bank number
+        let rom = (0..0x40)
+            .flat_map(|bank| iter::repeat(bank).take(0x4000))
+            .collect::<Vec<u8>>()
+            .into_boxed_slice();
+        let rom = Rom::<0x100000>::from(&*Box::<[_; 0x100000]>::try_from(rom).unwrap());
+        // Construct the MBC
+        Mbc1::with(
+            rom.to_shared(),
+            Null::<0>::new().to_shared(),
+            false,
+            multicart,
+        )
+    }
+
+    #[test]
+    fn mbc1_banking_works() {
+        let mbc = setup(false);
+        let rom = mbc.rom();
+
+        // Bank 0 is translated to bank 1
+        assert_eq!(rom.borrow().read(0x4000), 0x01);
+        // Select bank 0x21
+        rom.borrow_mut().write(0x2000, 0x01);
+        rom.borrow_mut().write(0x4000, 0x01);
+        assert_eq!(rom.borrow().read(0x0000), 0x00);
+        assert_eq!(rom.borrow().read(0x4000), 0x21);
+        // Enable advanced banking mode
+        rom.borrow_mut().write(0x6000, 0x01);
+        assert_eq!(rom.borrow().read(0x0000), 0x20);
+        assert_eq!(rom.borrow().read(0x7fff), 0x21);
+    }
+
+    #[test]
+    fn mbc1m_banking_works() {
+        let mbc = setup(true);
+        let rom = mbc.rom();
+
+        // Select game 1, bank 1
+        rom.borrow_mut().write(0x2000, 0x01);
+        rom.borrow_mut().write(0x4000, 0x01);
+        assert_eq!(rom.borrow().read(0x0000), 0x00);
+        assert_eq!(rom.borrow().read(0x4000), 0x11);
+        // Enable advanced banking mode to map the game's first bank
+        rom.borrow_mut().write(0x6000, 0x01);
+        assert_eq!(rom.borrow().read(0x0000), 0x10);
+        // Select game 3
+        rom.borrow_mut().write(0x4000, 0x03);
+        assert_eq!(rom.borrow().read(0x0000), 0x30);
+        assert_eq!(rom.borrow().read(0x4000), 0x31);
+        // Bit 4 of the primary bank number is unused
+        rom.borrow_mut().write(0x2000, 0x12);
+        assert_eq!(rom.borrow().read(0x4000), 0x32);
+        rom.borrow_mut().write(0x2000, 0x10);
+        assert_eq!(rom.borrow().read(0x4000), 0x30);
+    }
+
+    #[test]
+    fn mbc1_ram_enable_works() {
+        let mbc = Mbc1::with(
+            Null::<0x8000>::new().to_shared(),
+            Null::<0x2000>::with(0x55).to_shared(),
+            false,
+            false,
+        );
+        let (rom, ram) = (mbc.rom(), mbc.ram());
+
+        // RAM reads are `0xff` until enabled
+        assert_eq!(ram.borrow().read(0x0000), 0xff);
+        rom.borrow_mut().write(0x0000, 0x0a);
+        assert_eq!(ram.borrow().read(0x0000), 0x55);
+        // Any other value disables RAM
+        rom.borrow_mut().write(0x1fff, 0x0b);
+        assert_eq!(ram.borrow().read(0x0000), 0xff);
+    }
+
+    #[test]
+    fn mbc1_ram_write_gate_works() {
+        let eram = remus::mem::Ram::<0x2000>::new().to_shared();
+        let mbc = Mbc1::with(
+            Null::<0x8000>::new().to_shared(),
+            eram.clone(),
+            false,
+            false,
+        );
+        let (rom, ram) = (mbc.rom(), mbc.ram());
+
+        // RAM writes are ignored until enabled
+        ram.borrow_mut().write(0x0000, 0x55);
+        assert_eq!(eram.borrow().read(0x0000), 0x00);
+        rom.borrow_mut().write(0x0000, 0x0a);
+        ram.borrow_mut().write(0x0000, 0x55);
+        assert_eq!(ram.borrow().read(0x0000), 0x55);
+        // Data is retained once disabled
+        rom.borrow_mut().write(0x0000, 0x00);
+        ram.borrow_mut().write(0x0000, 0xaa);
+        assert_eq!(eram.borrow().read(0x0000), 0x55);
     }
 }
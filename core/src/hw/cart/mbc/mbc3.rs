@@ -0,0 +1,491 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use remus::{Block, Device, SharedDevice};
+
+use super::{Mbc, MbcState, RtcState};
+use crate::emu::clock::Clock;
+
+/// Number of days counted by the RTC before overflowing.
+const DAYS: u64 = 512;
+
+/// Number of seconds in a day.
+const DAY: u64 = 24 * 60 * 60;
+
+/// MBC3 cartridge type.
+#[derive(Debug)]
+pub struct Mbc3 {
+    rom: Rc<RefCell<Rom>>,
+    ram: Rc<RefCell<Ram>>,
+}
+
+impl Mbc3 {
+    /// Constructs a new `Mbc3` with the provided configuration.
+    ///
+    /// If present, the real-time clock (RTC) keeps time using the provided
+    /// `clock`.
+    pub fn with(
+        rom: SharedDevice,
+        ram: SharedDevice,
+        _battery: bool,
+        rtc: Option<Box<dyn Clock>>,
+    ) -> Self {
+        // Prepare control registers
+        let ctl = Rc::new(RefCell::new(Control {
+            bank1: Default::default(),
+            sel: Default::default(),
+            latch: Default::default(),
+            ena: Default::default(),
+            rtc: rtc.map(Rtc::new),
+        }));
+        // Prepare RAM
+        let ram = Ram {
+            ram,
+            ctl: ctl.clone(),
+        };
+        // Prepare ROM
+        let rom = Rom { rom, ctl };
+
+        Self {
+            rom: Rc::new(RefCell::new(rom)),
+            ram: Rc::new(RefCell::new(ram)),
+        }
+    }
+}
+
+impl Block for Mbc3 {
+    fn reset(&mut self) {
+        // Reset ROM
+        self.rom.borrow_mut().reset();
+        // Reset RAM
+        self.ram.borrow_mut().reset();
+    }
+}
+
+impl Mbc for Mbc3 {
+    fn rom(&self) -> SharedDevice {
+        self.rom.clone()
+    }
+
+    fn ram(&self) -> SharedDevice {
+        self.ram.clone()
+    }
+
+    fn state(&self) -> MbcState {
+        let ctl = self.rom.borrow().ctl.clone();
+        let mut ctl = ctl.borrow_mut();
+        MbcState::Mbc3 {
+            bank1: ctl.bank1,
+            sel: ctl.sel,
+            latch: ctl.latch,
+            ena: ctl.ena,
+            rtc: ctl.rtc.as_mut().map(|rtc| {
+                rtc.sync();
+                RtcState {
+                    secs: rtc.secs,
+                    halt: rtc.halt,
+                    carry: rtc.carry,
+                    latched: rtc.latched,
+                }
+            }),
+        }
+    }
+
+    fn set_state(&mut self, state: &MbcState) {
+        if let &MbcState::Mbc3 {
+            bank1,
+            sel,
+            latch,
+            ena,
+            rtc: state,
+        } = state
+        {
+            let ctl = self.rom.borrow().ctl.clone();
+            let mut ctl = ctl.borrow_mut();
+            ctl.bank1 = bank1;
+            ctl.sel = sel;
+            ctl.latch = latch;
+            ctl.ena = ena;
+            // NOTE: The RTC counts from its restored time, without accounting
+            //       for any time elapsed since it was saved.
+            if let (Some(rtc), Some(state)) = (&mut ctl.rtc, state) {
+                rtc.base = rtc.clock.now();
+                rtc.secs = state.secs;
+                rtc.halt = state.halt;
+                rtc.carry = state.carry;
+                rtc.latched = state.latched;
+            }
+        }
+    }
+}
+
+/// MBC3 control registers.
+#[derive(Debug)]
+struct Control {
+    /// ROM (7-bit) bank number.
+    bank1: u8,
+    /// RAM bank number - or - RTC register select.
+    sel: u8,
+    /// Previous value written to the latch register.
+    latch: u8,
+    /// RAM and timer enable.
+    ena: bool,
+    /// Real-time clock.
+    rtc: Option<Rtc>,
+}
+
+impl Control {
+    /// Gets the ROM bank mapped at `0x4000..=0x7fff`.
+    fn rom1(&self) -> usize {
+        // NOTE: A bank number of zero is always translated to one.
+        match self.bank1 {
+            0x00 => 0x01,
+            bank => bank as usize,
+        }
+    }
+}
+
+impl Block for Control {
+    fn reset(&mut self) {
+        // NOTE: The RTC is battery backed, so continues to keep time
+        self.bank1 = Default::default();
+        self.sel = Default::default();
+        self.latch = Default::default();
+        self.ena = Default::default();
+    }
+}
+
+/// MBC3 ROM.
+#[derive(Debug)]
+struct Rom {
+    rom: SharedDevice,
+    ctl: Rc<RefCell<Control>>,
+}
+
+impl Block for Rom {
+    fn reset(&mut self) {
+        // Reset control registers
+        self.ctl.borrow_mut().reset();
+    }
+}
+
+impl Device for Rom {
+    fn contains(&self, index: usize) -> bool {
+        index < self.len()
+    }
+
+    fn len(&self) -> usize {
+        0x8000
+    }
+
+    fn read(&self, index: usize) -> u8 {
+        // Determine the selected bank
+        let bank = match index {
+            0x0000..=0x3fff => 0,
+            _ => self.ctl.borrow().rom1(),
+        };
+        // Read from the bank
+        // NOTE: Unused upper bank bits are ignored by wrapping the address
+        let rom = self.rom.borrow();
+        rom.read((0x4000 * bank + (index & 0x3fff)) % rom.len())
+    }
+
+    fn write(&mut self, index: usize, value: u8) {
+        let mut ctl = self.ctl.borrow_mut();
+        match index {
+            // RAM and Timer Enable
+            0x0000..=0x1fff => ctl.ena = value & 0x0f == 0x0a,
+            // ROM Bank Number
+            0x2000..=0x3fff => ctl.bank1 = value & 0x7f,
+            // RAM Bank Number - or - RTC Register Select
+            0x4000..=0x5fff => ctl.sel = value,
+            // Latch Clock Data
+            0x6000..=0x7fff => {
+                // Latch on a write of 0x00 followed by 0x01
+                if let (0x00, 0x01) = (ctl.latch, value) {
+                    if let Some(rtc) = &mut ctl.rtc {
+                        rtc.latch();
+                    }
+                }
+                ctl.latch = value;
+            }
+            _ => panic!(), // TODO: some error here
+        }
+    }
+}
+
+/// MBC3 RAM.
+#[derive(Debug)]
+struct Ram {
+    ram: SharedDevice,
+    ctl: Rc<RefCell<Control>>,
+}
+
+impl Block for Ram {
+    fn reset(&mut self) {
+        // Reset RAM
+        self.ram.borrow_mut().reset();
+    }
+}
+
+impl Device for Ram {
+    fn contains(&self, index: usize) -> bool {
+        index < self.len()
+    }
+
+    fn len(&self) -> usize {
+        // NOTE: RTC registers are mapped even without any RAM
+        match self.ctl.borrow().rtc {
+            Some(_) => 0x2000,
+            None => self.ram.borrow().len().min(0x2000),
+        }
+    }
+
+    fn read(&self, index: usize) -> u8 {
+        let ctl = self.ctl.borrow();
+        let ram = self.ram.borrow();
+        match (ctl.sel, &ctl.rtc) {
+            // Disabled (reads as `0xff`)
+            _ if !ctl.ena => 0xff,
+            // RAM Bank 00-03
+            (bank @ 0x00..=0x03, _) if ram.len() > 0 => {
+                ram.read((0x2000 * bank as usize + index) % ram.len())
+            }
+            // RTC Register 08-0C
+            (reg @ 0x08..=0x0c, Some(rtc)) => rtc.read(reg - 0x08),
+            // Unmapped
+            _ => 0xff,
+        }
+    }
+
+    fn write(&mut self, index: usize, value: u8) {
+        let mut ctl = self.ctl.borrow_mut();
+        let mut ram = self.ram.borrow_mut();
+        // NOTE: Writes are ignored until RAM is enabled
+        if !ctl.ena {
+            return;
+        }
+        match (ctl.sel, &mut ctl.rtc) {
+            // RAM Bank 00-03
+            (bank @ 0x00..=0x03, _) if ram.len() > 0 => {
+                let addr = (0x2000 * bank as usize + index) % ram.len();
+                ram.write(addr, value);
+            }
+            // RTC Register 08-0C
+            (reg @ 0x08..=0x0c, Some(rtc)) => rtc.write(reg - 0x08, value),
+            // Unmapped
+            _ => (),
+        }
+    }
+}
+
+/// MBC3 real-time clock.
+///
+/// Rather than ticking every second, the RTC counts the seconds elapsed on
+/// its [`Clock`] whenever it is accessed.
+#[derive(Debug)]
+struct Rtc {
+    /// Time source.
+    clock: Box<dyn Clock>,
+    /// Time of the most recent sync.
+    base: Duration,
+    /// Seconds counted since day zero.
+    secs: u64,
+    /// Halt flag.
+    halt: bool,
+    /// Day counter carry flag.
+    carry: bool,
+    /// Latched registers.
+    latched: [u8; 5],
+}
+
+impl Rtc {
+    fn new(clock: Box<dyn Clock>) -> Self {
+        Self {
+            base: clock.now(),
+            clock,
+            secs: Default::default(),
+            halt: Default::default(),
+            carry: Default::default(),
+            latched: Default::default(),
+        }
+    }
+
+    /// Counts the seconds elapsed since the most recent sync.
+    fn sync(&mut self) {
+        // Count elapsed seconds (unless halted)
+        if !self.halt {
+            // NOTE: Fractional seconds are carried over to the next sync
+            let secs = self.clock.now().saturating_sub(self.base).as_secs();
+            self.base += Duration::from_secs(secs);
+            self.secs += secs;
+        }
+        // Overflow the day counter
+        if self.secs >= DAYS * DAY {
+            self.secs %= DAYS * DAY;
+            self.carry = true;
+        }
+    }
+
+    /// Gets the current values of the registers.
+    fn regs(&self) -> [u8; 5] {
+        let days = self.secs / DAY;
+        [
+            (self.secs % 60) as u8,
+            (self.secs / 60 % 60) as u8,
+            (self.secs / 3600 % 24) as u8,
+            days as u8,
+            ((self.carry as u8) << 7) | ((self.halt as u8) << 6) | (days >> 8) as u8,
+        ]
+    }
+
+    /// Latches the current values of the registers.
+    fn latch(&mut self) {
+        self.sync();
+        self.latched = self.regs();
+    }
+
+    /// Reads a latched register.
+    fn read(&self, reg: u8) -> u8 {
+        self.latched[reg as usize]
+    }
+
+    /// Writes a register.
+    fn write(&mut self, reg: u8, value: u8) {
+        // Update the registers
+        self.sync();
+        let mut regs = self.regs();
+        regs[reg as usize] = value;
+        // Recount the seconds
+        // NOTE: Out of range values are carried into the next register.
+        let [s, m, h, dl, dh] = regs;
+        let days = (((dh & 0x01) as u64) << 8) | dl as u64;
+        self.secs =
+            ((days * 24 + (h & 0x1f) as u64) * 60 + (m & 0x3f) as u64) * 60 + (s & 0x3f) as u64;
+        self.carry = dh & 0x80 != 0;
+        // Start counting from now when resumed
+        let halt = dh & 0x40 != 0;
+        if self.halt && !halt {
+            self.base = self.clock.now();
+        }
+        self.halt = halt;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use remus::dev::Null;
+    use remus::mem::Rom;
+
+    use super::*;
+    use crate::emu::clock::ManualClock;
+
+    fn setup(clock: &ManualClock) -> Mbc3 {
+        // Label each ROM bank with its bank number
+        let rom = (0..0x80)
+            .flat_map(|bank| std::iter::repeat(bank).take(0x4000))
+            .collect::<Vec<u8>>()
+            .into_boxed_slice();
+        let rom = Rom::<0x200000>::from(&*Box::<[_; 0x200000]>::try_from(rom).unwrap());
+        // Construct the MBC
+        Mbc3::with(
+            rom.to_shared(),
+            Null::<0>::new().to_shared(),
+            true,
+            Some(Box::new(clock.clone())),
+        )
+    }
+
+    /// Latches and reads the RTC registers.
+    fn latch(mbc: &Mbc3) -> [u8; 5] {
+        let (rom, ram) = (mbc.rom(), mbc.ram());
+        rom.borrow_mut().write(0x0000, 0x0a);
+        rom.borrow_mut().write(0x6000, 0x00);
+        rom.borrow_mut().write(0x6000, 0x01);
+        std::array::from_fn(|reg| {
+            rom.borrow_mut().write(0x4000, 0x08 + reg as u8);
+            ram.borrow().read(0x0000)
+        })
+    }
+
+    #[test]
+    fn mbc3_banking_works() {
+        let mbc = setup(&ManualClock::default());
+        let rom = mbc.rom();
+
+        // Bank 0 is translated to bank 1
+        assert_eq!(rom.borrow().read(0x4000), 0x01);
+        // Select bank 0x7f
+        rom.borrow_mut().write(0x2000, 0xff);
+        assert_eq!(rom.borrow().read(0x0000), 0x00);
+        assert_eq!(rom.borrow().read(0x7fff), 0x7f);
+    }
+
+    #[test]
+    fn mbc3_rtc_works() {
+        let clock = ManualClock::default();
+        let mbc = setup(&clock);
+
+        // Registers only update when latched
+        clock.advance(Duration::from_secs(60));
+        assert_eq!(latch(&mbc), [0, 1, 0, 0, 0x00]);
+        clock.advance(Duration::from_millis(59_500));
+        mbc.rom().borrow_mut().write(0x4000, 0x08);
+        assert_eq!(mbc.ram().borrow().read(0x0000), 0x00);
+        assert_eq!(latch(&mbc), [59, 1, 0, 0, 0x00]);
+        // Fractional seconds are not lost
+        clock.advance(Duration::from_millis(500));
+        assert_eq!(latch(&mbc), [0, 2, 0, 0, 0x00]);
+
+        // Halt the RTC
+        let (rom, ram) = (mbc.rom(), mbc.ram());
+        rom.borrow_mut().write(0x4000, 0x0c);
+        ram.borrow_mut().write(0x0000, 0x40);
+        clock.advance(Duration::from_secs(3600));
+        assert_eq!(latch(&mbc), [0, 2, 0, 0, 0x40]);
+        // Set the day counter, then resume
+        rom.borrow_mut().write(0x4000, 0x0b);
+        ram.borrow_mut().write(0x0000, 0xff);
+        rom.borrow_mut().write(0x4000, 0x0c);
+        ram.borrow_mut().write(0x0000, 0x01);
+        clock.advance(Duration::from_secs(DAY));
+        assert_eq!(latch(&mbc), [0, 2, 0, 0x00, 0x80]);
+    }
+
+    #[test]
+    fn mbc3_ram_write_gate_works() {
+        let clock = ManualClock::default();
+        let eram = remus::mem::Ram::<0x8000>::new().to_shared();
+        let mbc = Mbc3::with(
+            Null::<0x8000>::new().to_shared(),
+            eram.clone(),
+            true,
+            Some(Box::new(clock.clone())),
+        );
+        let (rom, ram) = (mbc.rom(), mbc.ram());
+
+        // RAM writes are ignored until enabled
+        rom.borrow_mut().write(0x4000, 0x01);
+        ram.borrow_mut().write(0x0000, 0x55);
+        assert_eq!(eram.borrow().read(0x2000), 0x00);
+        rom.borrow_mut().write(0x0000, 0x0a);
+        ram.borrow_mut().write(0x0000, 0x55);
+        assert_eq!(eram.borrow().read(0x2000), 0x55);
+
+        // As are RTC writes
+        rom.borrow_mut().write(0x0000, 0x00);
+        rom.borrow_mut().write(0x4000, 0x08);
+        ram.borrow_mut().write(0x0000, 0x2a);
+        assert_eq!(latch(&mbc), [0, 0, 0, 0, 0x00]);
+        rom.borrow_mut().write(0x4000, 0x08);
+        ram.borrow_mut().write(0x0000, 0x2a);
+        assert_eq!(latch(&mbc), [42, 0, 0, 0, 0x00]);
+
+        // Data is retained once disabled
+        rom.borrow_mut().write(0x0000, 0x00);
+        rom.borrow_mut().write(0x4000, 0x01);
+        ram.borrow_mut().write(0x0000, 0xaa);
+        assert_eq!(eram.borrow().read(0x2000), 0x55);
+    }
+}
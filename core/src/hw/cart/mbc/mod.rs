@@ -7,9 +7,11 @@ use std::fmt::Debug;
 use remus::{Block, SharedDevice};
 
 mod mbc1;
+mod mbc3;
 mod nombc;
 
 pub use self::mbc1::Mbc1;
+pub use self::mbc3::Mbc3;
 pub use self::nombc::NoMbc;
 
 /// Unified MBC interface.
@@ -19,4 +21,61 @@ pub(super) trait Mbc: Block + Debug {
 
     /// Gets a shared reference to the MBC's RAM.
     fn ram(&self) -> SharedDevice;
+
+    /// Gets the MBC's control state.
+    fn state(&self) -> MbcState {
+        MbcState::None
+    }
+
+    /// Sets the MBC's control state.
+    ///
+    /// NOTE: States for a different kind of MBC are ignored.
+    fn set_state(&mut self, _: &MbcState) {}
+}
+
+/// MBC control state.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum MbcState {
+    /// No MBC.
+    #[default]
+    None,
+    /// MBC1 registers.
+    Mbc1 {
+        /// Primary (5-bit) bank number.
+        bank1: u8,
+        /// Secondary (2-bit) bank number.
+        bank2: u8,
+        /// Banking mode select.
+        mode: bool,
+        /// RAM enable.
+        ena: bool,
+    },
+    /// MBC3 registers.
+    Mbc3 {
+        /// ROM (7-bit) bank number.
+        bank1: u8,
+        /// RAM bank number - or - RTC register select.
+        sel: u8,
+        /// Previous value written to the latch register.
+        latch: u8,
+        /// RAM and timer enable.
+        ena: bool,
+        /// Real-time clock, if present.
+        rtc: Option<RtcState>,
+    },
+}
+
+/// MBC3 real-time clock state.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct RtcState {
+    /// Seconds counted since day zero.
+    pub secs: u64,
+    /// Halt flag.
+    pub halt: bool,
+    /// Day counter carry flag.
+    pub carry: bool,
+    /// Latched registers.
+    pub latched: [u8; 5],
 }
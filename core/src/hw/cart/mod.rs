@@ -20,7 +20,8 @@ use remus::{Block, Device, Memory, SharedDevice};
 use thiserror::Error;
 
 use self::header::CartridgeType;
-use self::mbc::{Mbc, Mbc1, NoMbc};
+use self::mbc::{Mbc, Mbc1, Mbc3, MbcState, NoMbc};
+use crate::emu::clock::{Clock, SystemClock};
 
 mod header;
 
@@ -28,6 +29,9 @@ pub mod mbc;
 
 pub use self::header::{Error as HeaderError, Header};
 
+#[cfg(test)]
+pub(crate) use self::header::rom;
+
 /// Cartridge model.
 ///
 /// Parses a [`Header`] from the ROM, then initializes the memory bank
@@ -35,20 +39,36 @@ pub use self::header::{Error as HeaderError, Header};
 #[derive(Debug)]
 pub struct Cartridge {
     header: Header,
+    len: usize,
     mbc: Box<dyn Mbc>,
+    erom: SharedDevice,
+    eram: SharedDevice,
 }
 
 impl Cartridge {
     /// Constructs a new `Cartridge`.
     pub fn new(rom: &[u8]) -> Result<Self, Error> {
+        Self::with_clock(rom, Box::new(SystemClock))
+    }
+
+    /// Constructs a new `Cartridge` whose real-time clock (if any) keeps time
+    /// using the provided `clock`.
+    pub fn with_clock(rom: &[u8], clock: Box<dyn Clock>) -> Result<Self, Error> {
         // Parse cartridge header
         let header = Header::try_from(&*rom)?;
         debug!("Header:\n{header}");
 
+        // Reject unsupported cartridge types
+        let report = header.validate(rom.len());
+        if report.issues.contains(&Issue::Unsupported) {
+            return Err(Error::Unsupported(header.cart.to_string()));
+        }
+
         // Construct null device (for reuse where needed)
         let null = Null::<0>::new().to_shared();
 
         // Prepare external ROM
+        let len = rom.len();
         let rom = {
             // Calculate buffer stats
             let read = rom.len();
@@ -76,6 +96,11 @@ impl Cartridge {
         };
         trace!("ROM:\n{}", &&*rom as &dyn Memory);
 
+        // Detect multicart (MBC1M) wiring
+        // NOTE: Multicarts are 1 MiB, with each game's header (and thus its
+        //       logo) found every 256 KiB.
+        let multicart = header.romsz == 0x100000 && rom[0x40104..0x40134] == header::LOGO;
+
         // Construct external ROM
         let rom = {
             match header.romsz {
@@ -106,6 +131,8 @@ impl Cartridge {
             }
         };
 
+        let erom = rom.clone();
+
         // Construct external RAM
         let eram = match header.ramsz {
             0x0 => null.clone(),
@@ -117,19 +144,37 @@ impl Cartridge {
         };
 
         // Construct a cartridge
-        let mbc: Box<dyn Mbc> = match header.cart {
-            CartridgeType::NoMbc { ram, .. } => {
+        let (mbc, eram): (Box<dyn Mbc>, _) = match &header.cart {
+            &CartridgeType::NoMbc { ram, .. } => {
                 let eram = [null, eram][ram as usize].clone();
-                Box::new(NoMbc::with(rom, eram))
+                (Box::new(NoMbc::with(rom, eram.clone())), eram)
             }
-            CartridgeType::Mbc1 { ram, battery } => {
+            &CartridgeType::Mbc1 { ram, battery } => {
                 let eram = [null, eram][ram as usize].clone();
-                Box::new(Mbc1::with(rom, eram, battery))
+                (
+                    Box::new(Mbc1::with(rom, eram.clone(), battery, multicart)),
+                    eram,
+                )
             }
-            cart => unimplemented!("{cart:?}"),
+            &CartridgeType::Mbc3 {
+                timer,
+                ram,
+                battery,
+            } => {
+                let eram = [null, eram][ram as usize].clone();
+                let rtc = timer.then_some(clock);
+                (Box::new(Mbc3::with(rom, eram.clone(), battery, rtc)), eram)
+            }
+            _ => unreachable!(),
         };
 
-        Ok(Self { header, mbc })
+        Ok(Self {
+            header,
+            len,
+            mbc,
+            erom,
+            eram,
+        })
     }
 
     /// Gets a reference to the cartridge's header.
@@ -138,6 +183,28 @@ impl Cartridge {
         &self.header
     }
 
+    /// Gets the title of the cartridge's ROM.
+    ///
+    /// Convenience over [`Header::title`], keeping only printable ASCII, such
+    /// that the title is safe to use in window captions and file names.
+    #[must_use]
+    pub fn title(&self) -> String {
+        self.header
+            .title()
+            .chars()
+            .filter(|char| char.is_ascii_graphic() || *char == ' ')
+            .collect()
+    }
+
+    /// Checks the cartridge for compatibility issues.
+    ///
+    /// Frontends may use this to warn about problematic dumps before running.
+    /// See [`Header::validate`] to check ROMs which fail to load.
+    #[must_use]
+    pub fn validate(&self) -> ValidationReport {
+        self.header.validate(self.len)
+    }
+
     /// Gets a shared reference to the cartridge's ROM.
     pub fn rom(&self) -> SharedDevice {
         self.mbc.rom()
@@ -147,6 +214,54 @@ impl Cartridge {
     pub fn ram(&self) -> SharedDevice {
         self.mbc.ram()
     }
+
+    /// Gets the state of the cartridge's MBC.
+    #[must_use]
+    pub fn mbc_state(&self) -> MbcState {
+        self.mbc.state()
+    }
+
+    /// Sets the state of the cartridge's MBC.
+    ///
+    /// NOTE: States for a different kind of MBC are ignored.
+    pub fn set_mbc_state(&mut self, state: &MbcState) {
+        self.mbc.set_state(state);
+    }
+
+    /// Dumps the contents of the cartridge's ROM.
+    ///
+    /// All banks are included, irrespective of which is currently selected.
+    #[must_use]
+    pub fn dump_rom(&self) -> Vec<u8> {
+        let erom = self.erom.borrow();
+        (0..erom.len()).map(|index| erom.read(index)).collect()
+    }
+
+    /// Gets the size of the cartridge's external RAM (across all banks).
+    #[must_use]
+    pub fn ram_size(&self) -> usize {
+        self.eram.borrow().len()
+    }
+
+    /// Saves the contents of the cartridge's external RAM.
+    ///
+    /// All banks are included, irrespective of which is currently selected.
+    #[must_use]
+    pub fn save_ram(&self) -> Vec<u8> {
+        let eram = self.eram.borrow();
+        (0..eram.len()).map(|index| eram.read(index)).collect()
+    }
+
+    /// Loads the contents of the cartridge's external RAM.
+    ///
+    /// Any bytes beyond the size of the RAM are ignored.
+    pub fn load_ram(&mut self, data: &[u8]) {
+        let mut eram = self.eram.borrow_mut();
+        let len = eram.len();
+        for (index, &byte) in data.iter().take(len).enumerate() {
+            eram.write(index, byte);
+        }
+    }
 }
 
 impl Block for Cartridge {
@@ -184,19 +299,139 @@ impl Default for Cartridge {
             0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
             0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0xe6, 0x00, 0x6b,
         ];
+        let erom = Rom::<0x8000>::new().to_shared();
+        let eram = Ram::<0x2000>::new().to_shared();
         Self {
             header: Header::try_from(&rom[..]).unwrap(),
-            mbc: Box::new(NoMbc::with(
-                Rom::<0x8000>::new().to_shared(),
-                Ram::<0x2000>::new().to_shared(),
-            )),
+            len: 0x8000,
+            mbc: Box::new(NoMbc::with(erom.clone(), eram.clone())),
+            erom,
+            eram,
         }
     }
 }
 
+/// Compatibility report, produced by [`Header::validate`].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ValidationReport {
+    /// Issues found with the cartridge.
+    pub issues: Vec<Issue>,
+}
+
+impl ValidationReport {
+    /// Checks if no issues were found.
+    #[must_use]
+    pub fn is_ok(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/// A type specifying general categories of cartridge compatibility issue.
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
+pub enum Issue {
+    #[error("mismatched ROM size: declared {declared}, found {found}")]
+    RomSize { declared: usize, found: usize },
+    #[error("unsupported cartridge type")]
+    Unsupported,
+    #[error("inconsistent RAM size: {0}")]
+    RamSize(usize),
+    #[error("logo does not match")]
+    Logo,
+}
+
 /// A type specifying general categories of [`Cartridge`] error.
 #[derive(Debug, Error)]
 pub enum Error {
     #[error("could not parse header")]
     Header(#[from] header::Error),
+    #[error("unsupported cartridge type: {0}")]
+    Unsupported(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_works() {
+        // Prepare a 64 KiB ROM, truncated to 32 KiB
+        let mut rom = header::rom(0x10000, 0x01, b"");
+        rom.truncate(0x8000);
+        let cart = Cartridge::new(&rom).unwrap();
+        assert_eq!(
+            cart.validate().issues,
+            [Issue::RomSize {
+                declared: 0x10000,
+                found: 0x8000,
+            }]
+        );
+
+        // Correct the declared size
+        let rom = header::rom(0x8000, 0x01, b"");
+        let cart = Cartridge::new(&rom).unwrap();
+        assert!(cart.validate().is_ok());
+    }
+
+    #[test]
+    fn unsupported_works() {
+        // Prepare a 64 KiB MBC5 ROM
+        let rom = header::rom(0x10000, 0x19, b"");
+
+        // Unsupported cartridges are reported...
+        let header = Header::try_from(&*rom).unwrap();
+        assert_eq!(header.validate(rom.len()).issues, [Issue::Unsupported]);
+        // ... and refused
+        assert!(matches!(Cartridge::new(&rom), Err(Error::Unsupported(_))));
+    }
+
+    #[test]
+    fn title_works() {
+        let rom = header::rom(0x8000, 0x00, b"TETRIS");
+        let cart = Cartridge::new(&rom).unwrap();
+        assert_eq!(cart.title(), "TETRIS");
+    }
+
+    #[test]
+    fn mbc_state_works() {
+        // Prepare a 64 KiB MBC1 ROM, marking the start of each bank
+        let mut rom = header::rom(0x10000, 0x01, b"");
+        (1..4).for_each(|bank| rom[0x4000 * bank] = bank as u8);
+        let mut cart = Cartridge::new(&rom).unwrap();
+
+        // Select ROM bank 3
+        cart.rom().borrow_mut().write(0x2000, 0x03);
+        let state = cart.mbc_state();
+        assert!(matches!(state, MbcState::Mbc1 { bank1: 3, .. }));
+
+        // Restore the selected bank
+        cart.reset();
+        assert_eq!(cart.rom().borrow().read(0x4000), 0x01);
+        cart.set_mbc_state(&state);
+        assert_eq!(cart.rom().borrow().read(0x4000), 0x03);
+        // States for a different MBC are ignored
+        cart.set_mbc_state(&MbcState::None);
+        assert_eq!(cart.mbc_state(), state);
+    }
+
+    #[test]
+    fn multicart_detect_works() {
+        for multicart in [false, true] {
+            // Prepare a 1 MiB MBC1 ROM, marking the start of each bank
+            let mut rom = header::rom(0x100000, 0x01, b"");
+            (0..0x40).for_each(|bank| rom[0x4000 * bank] = bank as u8);
+            // Include a second game's logo for multicarts
+            if multicart {
+                rom[0x40104..0x40134].copy_from_slice(&header::LOGO);
+            }
+            let cart = Cartridge::new(&rom).unwrap();
+
+            // Select the second game
+            let rom = cart.rom();
+            let mut rom = rom.borrow_mut();
+            rom.write(0x6000, 0x01);
+            rom.write(0x4000, 0x01);
+            // Multicarts shift the secondary bank number by 4 (rather than 5)
+            assert_eq!(rom.read(0x0000), [0x20, 0x10][multicart as usize]);
+        }
+    }
 }
@@ -13,16 +13,28 @@ use crate::hw::pic::Pic;
 
 mod sm83;
 
-pub use self::sm83::Cpu as Sm83;
+pub use self::sm83::{
+    disasm, Access, BusAccess, Cpu as Sm83, CpuControl, CpuState, CpuStats, HookAction, Ime,
+    Status as CpuStatus,
+};
 
 /// Unified processor interface.
 pub trait Processor: Block + Machine {
     /// Sets the processor's memory bus.
     fn set_bus(&mut self, bus: Rc<RefCell<Bus>>);
 
+    /// Sets the processor's side-effect free view of memory.
+    ///
+    /// This is used for reads which must not be observable, such as by
+    /// debugging hooks. Defaults to the memory bus.
+    fn set_mem(&mut self, mem: Rc<RefCell<Bus>>);
+
     /// Sets the processor's interrupt controller.
     fn set_pic(&mut self, pic: Rc<RefCell<Pic>>);
 
     /// Enable (or wake) the processor.
     fn wake(&mut self);
+
+    /// Resume the processor after STOP, due to a joypad press.
+    fn press(&mut self);
 }
@@ -3,7 +3,7 @@
 use std::ops::{BitAnd, BitOr, BitXor};
 
 use enumflag::Enumflag;
-use remus::Device;
+use log::error;
 
 use super::{helpers, Cpu, Flag, Ime, Instruction, Status};
 
@@ -520,9 +520,9 @@ pub mod dec {
         // Check opcode
         match inst.opcode {
             0x35 => {
-                let op1 = cpu.readbyte();
-                inst.stack.push(op1);
-                inst.exec = done;
+                // Proceed
+                // NOTE: (HL) is read on the following cycle.
+                inst.exec = read;
                 Some(inst)
             }
             0x05 | 0x0d | 0x15 | 0x1d | 0x25 | 0x2d | 0x3d => {
@@ -543,6 +543,16 @@ pub mod dec {
         }
     }
 
+    pub fn read(mut inst: Instruction, cpu: &mut Cpu) -> Option<Instruction> {
+        // Read (HL)
+        let op1 = cpu.readbyte();
+        inst.stack.push(op1);
+
+        // Proceed
+        inst.exec = done;
+        Some(inst)
+    }
+
     pub fn done(mut inst: Instruction, cpu: &mut Cpu) -> Option<Instruction> {
         // Execute DEC
         let op1 = inst.stack.pop().unwrap();
@@ -559,9 +569,8 @@ pub mod dec {
             0x35 => {
                 // Write (HL)
                 cpu.writebyte(res);
-                // Proceed
-                inst.exec = delay;
-                Some(inst)
+                // Finish
+                None
             }
             0x05 | 0x0d | 0x15 | 0x1d | 0x25 | 0x2d | 0x3d => {
                 // Write X
@@ -581,14 +590,6 @@ pub mod dec {
             _ => panic!("Illegal instruction."),
         }
     }
-
-    pub fn delay(_: Instruction, _: &mut Cpu) -> Option<Instruction> {
-        // Delay by 1 cycle
-        // NOTE: This represents the fact that it takes 2 cycles to write a u16.
-
-        // Finish
-        None
-    }
 }
 
 /// Decrement wide (16-bit).
@@ -620,6 +621,7 @@ pub mod decw {
                 .unwrap(),
         );
         let res = op1.wrapping_sub(1);
+        cpu.idu(op1);
         match inst.opcode {
             0x0b => {
                 let bc = cpu.regs.bc;
@@ -671,7 +673,10 @@ pub mod ei {
         }
 
         // Execute EI
-        cpu.ime = Ime::WillEnable;
+        // NOTE: A repeated EI must not delay interrupts any further.
+        if !cpu.ime.enabled() {
+            cpu.ime = Ime::WillEnable;
+        }
 
         // Finish
         None
@@ -689,6 +694,8 @@ pub mod halt {
         }
 
         // Perform HALT bug
+        // NOTE: Otherwise, the CPU halts until an interrupt is pending. Only
+        //       if IME is set will the interrupt then be serviced.
         if !cpu.ime.enabled() && cpu.pic.borrow().int().is_some() {
             cpu.halt_bug = true;
         } else {
@@ -709,9 +716,9 @@ pub mod inc {
         // Check opcode
         match inst.opcode {
             0x34 => {
-                let op1 = cpu.readbyte();
-                inst.stack.push(op1);
-                inst.exec = done;
+                // Proceed
+                // NOTE: (HL) is read on the following cycle.
+                inst.exec = read;
                 Some(inst)
             }
             0x04 | 0x0c | 0x14 | 0x1c | 0x24 | 0x2c | 0x3c => {
@@ -732,6 +739,16 @@ pub mod inc {
         }
     }
 
+    pub fn read(mut inst: Instruction, cpu: &mut Cpu) -> Option<Instruction> {
+        // Read (HL)
+        let op1 = cpu.readbyte();
+        inst.stack.push(op1);
+
+        // Proceed
+        inst.exec = done;
+        Some(inst)
+    }
+
     pub fn done(mut inst: Instruction, cpu: &mut Cpu) -> Option<Instruction> {
         // Execute INC
         let op1 = inst.stack.pop().unwrap();
@@ -748,9 +765,8 @@ pub mod inc {
             0x34 => {
                 // Write (HL)
                 cpu.writebyte(res);
-                // Proceed
-                inst.exec = delay;
-                Some(inst)
+                // Finish
+                None
             }
             0x04 | 0x0c | 0x14 | 0x1c | 0x24 | 0x2c | 0x3c => {
                 // Write X
@@ -770,14 +786,6 @@ pub mod inc {
             _ => panic!("Illegal instruction."),
         }
     }
-
-    pub fn delay(_: Instruction, _: &mut Cpu) -> Option<Instruction> {
-        // Delay by 1 cycle
-        // NOTE: This represents the fact that it takes 2 cycles to write a u16.
-
-        // Finish
-        None
-    }
 }
 
 /// Increment wide (16-bit).
@@ -809,6 +817,7 @@ pub mod incw {
                 .unwrap(),
         );
         let res = op1.wrapping_add(1);
+        cpu.idu(op1);
         match inst.opcode {
             0x03 => {
                 let bc = cpu.regs.bc;
@@ -1032,7 +1041,7 @@ pub mod ld {
                 .get(&cpu.regs);
                 inst.stack.extend(addr.to_le_bytes());
                 let op2 = *cpu.regs.a;
-                cpu.bus.borrow_mut().write(addr as usize, op2);
+                cpu.write(addr, op2);
                 // Proceed
                 inst.exec = delay;
                 Some(inst)
@@ -1047,7 +1056,7 @@ pub mod ld {
                 }
                 .get(&cpu.regs);
                 inst.stack.extend(addr.to_le_bytes());
-                let op2 = cpu.bus.borrow().read(addr as usize);
+                let op2 = cpu.read(addr);
                 inst.stack.push(op2);
                 // Proceed
                 inst.exec = execute;
@@ -1083,7 +1092,7 @@ pub mod ld {
                 let addr = cpu.regs.hl.get(&cpu.regs);
                 inst.stack.extend(addr.to_le_bytes());
                 let op2 = helpers::get_op8(cpu, inst.opcode & 0x07);
-                cpu.bus.borrow_mut().write(addr as usize, op2);
+                cpu.write(addr, op2);
                 // Proceed
                 inst.exec = delay;
                 Some(inst)
@@ -1174,6 +1183,7 @@ pub mod ld {
                 .try_into()
                 .unwrap(),
         );
+        cpu.idu(addr);
         let hl = cpu.regs.hl;
         match inst.opcode {
             0x22 | 0x2a => hl.set(&mut cpu.regs, addr.wrapping_add(1)),
@@ -1206,11 +1216,11 @@ pub mod ld {
             0xea => {
                 // Execute LD (a16), A
                 let op2 = *cpu.regs.a;
-                cpu.bus.borrow_mut().write(addr as usize, op2);
+                cpu.write(addr, op2);
             }
             0xfa => {
                 // Execute LD A, (a16)
-                let op2 = cpu.bus.borrow_mut().read(addr as usize);
+                let op2 = cpu.read(addr);
                 *cpu.regs.a = op2;
             }
             _ => panic!("Illegal instruction."),
@@ -1313,10 +1323,10 @@ pub mod ldw {
                 .try_into()
                 .unwrap(),
         );
-        let sp = &cpu.regs.sp;
-        cpu.bus.borrow_mut().write(addr as usize, sp.read(0));
+        let sp = cpu.regs.sp.to_le_bytes();
+        cpu.write(addr, sp[0]);
         let addr = addr.wrapping_add(1);
-        cpu.bus.borrow_mut().write(addr as usize, sp.read(1));
+        cpu.write(addr, sp[1]);
 
         // Proceed
         inst.exec = delay_0x08_3;
@@ -1404,11 +1414,11 @@ pub mod ldh {
             0xe0 | 0xe2 => {
                 // Execute LD (a8|C), A
                 let op2 = *cpu.regs.a;
-                cpu.bus.borrow_mut().write(addr as usize, op2);
+                cpu.write(addr, op2);
             }
             0xf0 | 0xf2 => {
                 // Execute LD A, (a8|C)
-                *cpu.regs.a = cpu.bus.borrow().read(addr as usize);
+                *cpu.regs.a = cpu.read(addr);
             }
             _ => panic!("Illegal instruction."),
         }
@@ -1495,35 +1505,33 @@ pub mod or {
 pub mod pop {
     use super::*;
 
-    pub fn start(mut inst: Instruction, cpu: &mut Cpu) -> Option<Instruction> {
+    pub fn start(mut inst: Instruction, _: &mut Cpu) -> Option<Instruction> {
         // Check opcode
         match inst.opcode {
             0xc1 | 0xd1 | 0xe1 | 0xf1 => (),
             _ => panic!("Illegal instruction."),
         }
 
-        // Pop u16
-        let mut word = cpu.popword();
-        if inst.opcode == 0xf1 {
-            word &= 0xfff0; // lower 4 bits of F cannot be changed
-        }
-        inst.stack.extend(word.to_le_bytes());
-
         // Proceed
-        inst.exec = delay;
+        inst.exec = low;
         Some(inst)
     }
 
-    pub fn delay(mut inst: Instruction, _: &mut Cpu) -> Option<Instruction> {
-        // Delay by 1 cycle
-        // NOTE: This represents the fact that it takes 2 cycles to pop a u16.
+    pub fn low(mut inst: Instruction, cpu: &mut Cpu) -> Option<Instruction> {
+        // Pop low byte
+        let byte = cpu.popbyte();
+        inst.stack.push(byte);
 
         // Proceed
-        inst.exec = done;
+        inst.exec = high;
         Some(inst)
     }
 
-    pub fn done(mut inst: Instruction, cpu: &mut Cpu) -> Option<Instruction> {
+    pub fn high(mut inst: Instruction, cpu: &mut Cpu) -> Option<Instruction> {
+        // Pop high byte
+        let byte = cpu.popbyte();
+        inst.stack.push(byte);
+
         // Perform pop
         let word = u16::from_le_bytes(
             inst.stack
@@ -1558,6 +1566,7 @@ pub mod prefix {
 
         // Fetch prefix instruction
         let opcode = cpu.fetchbyte();
+        cpu.stats.opcodes[0x100 + opcode as usize] += 1;
         let inst = Instruction::prefix(opcode);
         Some(inst)
     }
@@ -1580,38 +1589,33 @@ pub mod push {
         inst.stack.extend(word.to_le_bytes());
 
         // Proceed
-        inst.exec = push;
+        inst.exec = delay;
         Some(inst)
     }
 
-    pub fn push(mut inst: Instruction, cpu: &mut Cpu) -> Option<Instruction> {
-        // Perform push
-        let word = u16::from_le_bytes(
-            inst.stack
-                .drain(0..=1)
-                .collect::<Vec<_>>()
-                .try_into()
-                .unwrap(),
-        );
-        cpu.pushword(word);
+    pub fn delay(mut inst: Instruction, _: &mut Cpu) -> Option<Instruction> {
+        // Delay by 1 cycle
+        // NOTE: This represents the fact that it takes 1 cycle to decrement SP.
 
         // Proceed
-        inst.exec = delay;
+        inst.exec = high;
         Some(inst)
     }
 
-    pub fn delay(mut inst: Instruction, _: &mut Cpu) -> Option<Instruction> {
-        // Delay by 1 cycle
-        // NOTE: This represents the fact that it takes 2 cycles to push a u16.
+    pub fn high(mut inst: Instruction, cpu: &mut Cpu) -> Option<Instruction> {
+        // Push high byte
+        let byte = inst.stack.pop().unwrap();
+        cpu.pushbyte(byte);
 
         // Proceed
-        inst.exec = done;
+        inst.exec = low;
         Some(inst)
     }
 
-    pub fn done(_: Instruction, _: &mut Cpu) -> Option<Instruction> {
-        // Delay by 1 cycle
-        // NOTE: This represents the delay needed to fetch the next instruction.
+    pub fn low(mut inst: Instruction, cpu: &mut Cpu) -> Option<Instruction> {
+        // Push low byte
+        let byte = inst.stack.pop().unwrap();
+        cpu.pushbyte(byte);
 
         // Finish
         None
@@ -1796,7 +1800,8 @@ pub mod reti {
 
     pub fn done(_: Instruction, cpu: &mut Cpu) -> Option<Instruction> {
         // Enable interrupts
-        cpu.ime = Ime::WillEnable;
+        // NOTE: Unlike EI, interrupts are enabled immediately.
+        cpu.ime = Ime::Enabled;
 
         // Finish
         None
@@ -2257,7 +2262,7 @@ pub mod sbc {
     }
 
     pub fn done(mut inst: Instruction, cpu: &mut Cpu) -> Option<Instruction> {
-        // Execute SUB
+        // Execute SBC
         let acc = *cpu.regs.a;
         let op2 = inst.stack.pop().unwrap();
         let cin = Flag::C.get(&*cpu.regs.f) as u8;
@@ -2549,15 +2554,37 @@ pub mod srl {
 pub mod stop {
     use super::*;
 
-    #[allow(unreachable_code)]
-    pub fn start(inst: Instruction, _: &mut Cpu) -> Option<Instruction> {
+    pub fn start(inst: Instruction, cpu: &mut Cpu) -> Option<Instruction> {
         // Check opcode
         if inst.opcode != 0x10 {
             panic!("Illegal instruction.");
         }
 
+        // Check for held buttons, pending interrupts
+        let held = cpu.peek(0xff00) & 0x0f != 0x0f;
+        let int = cpu.pic.borrow().int().is_some();
+
+        // Skip the following byte
+        // NOTE: STOP is only a 1-byte instruction when an interrupt is
+        //       pending.
+        if !int {
+            cpu.fetchbyte();
+        }
+
         // Execute STOP
-        todo!("implement this mess of an instruction");
+        // NOTE: With a button already held, STOP mode would exit immediately,
+        //       so it is never entered. Instead, the CPU halts until an
+        //       interrupt is pending (unless one already is).
+        if held {
+            if !int {
+                cpu.status = Status::Halted;
+            }
+        } else {
+            // Reset DIV
+            cpu.write(0xff04, 0x00);
+            // Stop until a button is pressed
+            cpu.status = Status::Stopped;
+        }
 
         // Finish
         None
@@ -2677,8 +2704,20 @@ pub mod swap {
 pub mod unused {
     use super::*;
 
-    pub fn start(_: Instruction, _: &mut Cpu) -> Option<Instruction> {
-        panic!("Illegal instruction.");
+    pub fn start(inst: Instruction, cpu: &mut Cpu) -> Option<Instruction> {
+        // Report illegal instruction
+        let pc = cpu.regs.pc.wrapping_sub(1);
+        match cpu.trap.get() {
+            Some(trap) => trap(pc, inst.opcode),
+            None => error!("{pc:#06x}: Illegal instruction: {:#04x}", inst.opcode),
+        }
+
+        // Lock up the CPU
+        // NOTE: On hardware, the CPU hangs until it is reset.
+        cpu.status = Status::Locked(inst.opcode);
+
+        // Finish
+        None
     }
 }
 
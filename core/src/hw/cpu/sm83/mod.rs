@@ -3,6 +3,7 @@
 //! Model for the CPU core present on the Sharp LR35902 SoC.
 
 use std::cell::RefCell;
+use std::collections::VecDeque;
 use std::fmt::{Debug, Display};
 use std::rc::Rc;
 
@@ -14,15 +15,29 @@ use remus::{Block, Device, Machine};
 
 use self::inst::Instruction;
 use super::Processor;
-use crate::hw::pic::Pic;
+use crate::emu::hook::Hook;
+use crate::hw::pic::{Interrupt, Pic};
 
 mod inst;
 
+pub use self::inst::disasm;
+
+/// Number of checkpoints retained in the undo history.
+const HISTORY: usize = 64;
+
+/// Number of memory writes tracked by a checkpoint.
+const WRITES: usize = 1024;
+
+/// Number of bus accesses retained in the access log.
+const ACCESSES: usize = 1024;
+
 /// SM83 central processing unit.
 #[derive(Debug, Default)]
 pub struct Cpu {
     /// Memory address bus.
     bus: Rc<RefCell<Bus>>,
+    /// Side-effect free view of memory.
+    mem: Rc<RefCell<Bus>>,
     /// Programmable interrupt controller.
     pic: Rc<RefCell<Pic>>,
     /// Internal register set.
@@ -34,59 +49,364 @@ pub struct Cpu {
     /// Interrupt master enable.
     ime: Ime,
     halt_bug: bool,
+    /// Instruction-level undo history.
+    hist: History,
+    /// Pre-execution hook.
+    hook: Hook<dyn FnMut(u16, u8) -> HookAction>,
+    /// Whether the hook has already run for the next instruction.
+    hooked: bool,
+    /// Illegal instruction trap.
+    trap: Hook<dyn FnMut(u16, u8)>,
+    /// Interrupt service hook.
+    irq: Hook<dyn FnMut(Interrupt, u16)>,
+    /// Instruction trace hook.
+    trace: Hook<dyn FnMut(CpuState, u8)>,
+    /// Increment/decrement unit hook.
+    idu: Hook<dyn FnMut(u16)>,
+    /// Elapsed T-cycles.
+    cycles: usize,
+    /// Execution statistics.
+    stats: CpuStats,
+    /// Bus access log.
+    log: AccessLog,
 }
 
 impl Cpu {
+    /// Gets the values of the CPU's registers.
+    #[must_use]
+    pub fn regs(&self) -> CpuState {
+        self.regs.save()
+    }
+
+    /// Sets the values of the CPU's registers.
+    ///
+    /// As with hardware, the lower 4 bits of F are always cleared.
+    pub fn set_regs(&mut self, regs: CpuState) {
+        self.regs.load(regs);
+    }
+
+    /// Gets the CPU's control state.
+    #[must_use]
+    pub fn control(&self) -> CpuControl {
+        CpuControl {
+            ime: self.ime,
+            status: self.status,
+            halt_bug: self.halt_bug,
+        }
+    }
+
+    /// Sets the CPU's control state.
+    ///
+    /// NOTE: Execution resumes from an instruction boundary, as the state of a
+    ///       partially executed instruction is not preserved.
+    pub fn set_control(&mut self, ctl: CpuControl) {
+        self.ime = ctl.ime;
+        self.status = ctl.status;
+        self.halt_bug = ctl.halt_bug;
+        self.state = State::Done;
+    }
+
+    /// Sets a hook to be called before each instruction is executed.
+    ///
+    /// The hook is called once per instruction with the address and opcode of
+    /// the instruction about to be executed. If it returns
+    /// [`HookAction::Break`], the CPU will be disabled before the instruction
+    /// executes, until it is resumed by [`Cpu::resume`].
+    pub fn set_pre_exec_hook(&mut self, hook: Box<dyn FnMut(u16, u8) -> HookAction>) {
+        self.hook = hook.into();
+    }
+
+    /// Sets a trap to be called when an illegal instruction is executed.
+    ///
+    /// The trap is called with the address and opcode of the illegal
+    /// instruction, replacing the default behaviour of logging an error. In
+    /// either case, the CPU locks up until reset.
+    pub fn set_illegal_trap(&mut self, trap: Box<dyn FnMut(u16, u8)>) {
+        self.trap = trap.into();
+    }
+
+    /// Sets a hook to be called whenever an interrupt is serviced.
+    ///
+    /// The hook is called with the interrupt and the return address to be
+    /// pushed, after the interrupt is acknowledged but before its handler
+    /// executes.
+    pub fn on_interrupt(&mut self, hook: Box<dyn FnMut(Interrupt, u16)>) {
+        self.irq = hook.into();
+    }
+
+    /// Sets a hook to be called as each instruction is fetched.
+    ///
+    /// The hook is called with the values of the CPU's registers and the
+    /// opcode of the instruction about to be executed.
+    pub fn set_trace_hook(&mut self, hook: Box<dyn FnMut(CpuState, u8)>) {
+        self.trace = hook.into();
+    }
+
+    /// Sets a hook to be called with each address output by the
+    /// increment/decrement unit (IDU).
+    ///
+    /// NOTE: The IDU drives the address bus while operating, such that it may
+    ///       be observed by hardware despite no access taking place.
+    pub(crate) fn set_idu_hook(&mut self, hook: Box<dyn FnMut(u16)>) {
+        self.idu = hook.into();
+    }
+
+    /// Clears the hook set by [`Cpu::set_trace_hook`].
+    pub fn clear_trace_hook(&mut self) {
+        self.trace = Hook::default();
+    }
+
+    /// Gets execution statistics since the last reset.
+    ///
+    /// Prefixed instructions are counted under both the `0xcb` prefix and the
+    /// prefixed opcode.
+    #[must_use]
+    pub fn stats(&self) -> CpuStats {
+        CpuStats {
+            cycles: self.cycles as u64,
+            ..self.stats.clone()
+        }
+    }
+
+    /// Predicts the cost in clock cycles of the instruction at PC.
+    ///
+    /// Returns the cost when a condition is not met, followed by the cost when
+    /// it is. These are identical for unconditional instructions. Pending
+    /// interrupts are not considered.
+    #[must_use]
+    pub fn next_cycles(&self) -> (u8, u8) {
+        let pc = *self.regs.pc;
+        let inst = match self.peek(pc) {
+            0xcb => Instruction::prefix(self.peek(pc.wrapping_add(1))),
+            opcode => Instruction::new(opcode),
+        };
+        (inst.cycles(), inst.cycles_taken())
+    }
+
+    /// Gets the opcode of the illegal instruction that locked up the CPU.
+    #[must_use]
+    pub fn locked(&self) -> Option<u8> {
+        match self.status {
+            Status::Locked(opcode) => Some(opcode),
+            _ => None,
+        }
+    }
+
+    /// Resumes execution after a break.
+    pub fn resume(&mut self) {
+        if let Status::Break = self.status {
+            self.status = Status::Enabled;
+        }
+    }
+
+    /// Checks if an instruction was completed by the CPU's last cycle.
+    pub(crate) fn done(&self) -> bool {
+        matches!(self.state, State::Done)
+    }
+
+    /// Checks if execution has been suspended, by either a break or a lock up.
+    pub(crate) fn suspended(&self) -> bool {
+        matches!(self.status, Status::Break | Status::Locked(_))
+    }
+
+    /// Gets the number of cycles run since reset.
+    pub(crate) fn cycles(&self) -> usize {
+        self.cycles
+    }
+
+    /// Enables or disables logging of bus accesses.
+    ///
+    /// Enabling the log clears any previously logged accesses.
+    pub fn set_access_log(&mut self, enable: bool) {
+        self.log = AccessLog {
+            enabled: enable,
+            ..Default::default()
+        };
+    }
+
+    /// Gets the most recently logged bus accesses, oldest first.
+    ///
+    /// Only the most recent 1024 accesses are retained.
+    #[must_use]
+    pub fn access_log(&self) -> &[BusAccess] {
+        let list = &self.log.list;
+        &list[list.len().saturating_sub(ACCESSES)..]
+    }
+
+    /// Records a checkpoint of the current CPU state.
+    ///
+    /// Any memory written by the CPU after this point is tracked as part of
+    /// the checkpoint, allowing the write to be reverted by
+    /// [`Cpu::step_back`]. Only the most recent 64 checkpoints are retained.
+    ///
+    /// If more than 1024 writes follow a checkpoint, or if any of them could
+    /// have side effects, the history is discarded, as it can no longer be
+    /// reverted. Writes with side effects are those to the cartridge (whose
+    /// MBC registers are mapped over ROM, and whose RAM may be an MBC3's RTC)
+    /// and to the I/O registers.
+    ///
+    /// NOTE: Checkpoints should be recorded between instructions, as the state
+    ///       of a partially executed instruction is not preserved.
+    pub fn checkpoint(&mut self) {
+        // Drop the oldest checkpoint once full
+        if self.hist.list.len() == HISTORY {
+            self.hist.list.pop_front();
+        }
+        // Record the current state
+        self.hist.list.push_back(Checkpoint {
+            regs: self.regs.save(),
+            status: self.status,
+            ime: self.ime,
+            halt_bug: self.halt_bug,
+            writes: Vec::new(),
+        });
+        self.hist.open = true;
+    }
+
+    /// Reverts the CPU to its most recent checkpoint.
+    ///
+    /// Returns `false` if there was no checkpoint to revert to.
+    pub fn step_back(&mut self) -> bool {
+        // Retrieve the most recent checkpoint
+        let chk = match self.hist.list.pop_back() {
+            Some(chk) => chk,
+            None => return false,
+        };
+        // Track further writes against the preceding checkpoint
+        self.hist.open = !self.hist.list.is_empty();
+        // Undo memory writes (in reverse order)
+        //
+        // NOTE: Writes bypass the memory bus, as the bytes being restored were
+        //       themselves peeked. Only plain memory is restored, as writes
+        //       with side effects discard the history.
+        for (addr, byte) in chk.writes.into_iter().rev() {
+            self.mem.borrow_mut().write(addr as usize, byte);
+        }
+        // Restore internal state
+        self.regs.load(chk.regs);
+        self.status = chk.status;
+        self.ime = chk.ime;
+        self.halt_bug = chk.halt_bug;
+        self.state = State::Done;
+        true
+    }
+
+    /// Read the byte at an address without side effects.
+    ///
+    /// NOTE: Peeking bypasses the memory bus, such that it is unobservable by
+    ///       the rest of the system (e.g. bus arbitration).
+    fn peek(&self, addr: u16) -> u8 {
+        self.mem.borrow().read(addr as usize)
+    }
+
+    /// Log a bus access.
+    fn access(&mut self, kind: Access, addr: u16, value: u8) {
+        if self.log.enabled {
+            let cycle = self.cycles;
+            self.log.push(BusAccess {
+                kind,
+                addr,
+                value,
+                cycle,
+            });
+        }
+    }
+
+    /// Read the byte at an address.
+    fn read(&mut self, addr: u16) -> u8 {
+        let byte = self.bus.borrow().read(addr as usize);
+        self.access(Access::Read, addr, byte);
+        byte
+    }
+
+    /// Write to the byte at an address.
+    fn write(&mut self, addr: u16, byte: u8) {
+        self.access(Access::Write, addr, byte);
+        // Record the overwritten byte for undo
+        if self.hist.open && matches!(addr, 0x0000..=0x7fff | 0xa000..=0xbfff | 0xff00..=0xff7f) {
+            // Discard the history on writes with side effects
+            debug!("discarding undo history: write to {addr:#06x}");
+            self.hist = History::default();
+        } else if self.hist.open {
+            let prev = self.peek(addr);
+            if let Some(chk) = self.hist.list.back_mut() {
+                chk.writes.push((addr, prev));
+            }
+            // Discard the history once too many writes are tracked
+            if self.hist.list.back().map_or(0, |chk| chk.writes.len()) > WRITES {
+                debug!("discarding undo history");
+                self.hist = History::default();
+            }
+        }
+        self.bus.borrow_mut().write(addr as usize, byte);
+    }
+
+    /// Increment or decrement an address with the IDU.
+    fn idu(&mut self, addr: u16) {
+        if let Some(idu) = self.idu.get() {
+            idu(addr);
+        }
+    }
+
     /// Fetch the next byte after PC.
     fn fetchbyte(&mut self) -> u8 {
-        let pc = &mut *self.regs.pc;
-        let byte = self.bus.borrow().read(*pc as usize);
-        *pc = pc.wrapping_add(1);
+        let pc = *self.regs.pc;
+        let byte = self.bus.borrow().read(pc as usize);
+        self.access(Access::Fetch, pc, byte);
+        *self.regs.pc = pc.wrapping_add(1);
         byte
     }
 
     /// Read the byte at HL.
     fn readbyte(&mut self) -> u8 {
         let hl = self.regs.hl.get(&self.regs);
-        self.bus.borrow().read(hl as usize)
+        self.read(hl)
     }
 
     /// Write to the byte at HL
     fn writebyte(&mut self, byte: u8) {
         let hl = self.regs.hl.get(&self.regs);
-        self.bus.borrow_mut().write(hl as usize, byte);
+        self.write(hl, byte);
     }
 
     /// Fetch the next word after PC.
     fn fetchword(&mut self) -> u16 {
-        let pc = &mut *self.regs.pc;
         let mut word = [0; 2];
-        word[0] = self.bus.borrow().read(*pc as usize);
-        *pc = pc.wrapping_add(1);
-        word[1] = self.bus.borrow().read(*pc as usize);
-        *pc = pc.wrapping_add(1);
+        word[0] = self.fetchbyte();
+        word[1] = self.fetchbyte();
         u16::from_le_bytes(word)
     }
 
+    /// Pop the byte at SP.
+    fn popbyte(&mut self) -> u8 {
+        let sp = *self.regs.sp;
+        let byte = self.read(sp);
+        self.idu(sp);
+        *self.regs.sp = sp.wrapping_add(1);
+        byte
+    }
+
     /// Pop the word at SP.
     fn popword(&mut self) -> u16 {
-        let sp = &mut *self.regs.sp;
         let mut word = [0; 2];
-        word[0] = self.bus.borrow().read(*sp as usize);
-        *sp = sp.wrapping_add(1);
-        word[1] = self.bus.borrow().read(*sp as usize);
-        *sp = sp.wrapping_add(1);
+        word[0] = self.popbyte();
+        word[1] = self.popbyte();
         u16::from_le_bytes(word)
     }
 
+    /// Push to the byte at SP.
+    fn pushbyte(&mut self, byte: u8) {
+        self.idu(*self.regs.sp);
+        let sp = self.regs.sp.wrapping_sub(1);
+        self.write(sp, byte);
+        *self.regs.sp = sp;
+    }
+
     /// Push to the word at SP.
     fn pushword(&mut self, word: u16) {
-        let sp = &mut *self.regs.sp;
         let word = word.to_le_bytes();
-        *sp = sp.wrapping_sub(1);
-        self.bus.borrow_mut().write(*sp as usize, word[1]);
-        *sp = sp.wrapping_sub(1);
-        self.bus.borrow_mut().write(*sp as usize, word[0]);
+        self.pushbyte(word[1]);
+        self.pushbyte(word[0]);
     }
 }
 
@@ -100,20 +420,38 @@ impl Block for Cpu {
         self.status = Default::default();
         self.state = Default::default();
         self.ime = Default::default();
+        self.hist = Default::default();
+        self.hooked = Default::default();
+        self.cycles = Default::default();
+        self.stats = Default::default();
+        self.log.list.clear();
     }
 }
 
 impl Processor for Cpu {
     fn set_bus(&mut self, bus: Rc<RefCell<Bus>>) {
+        self.mem = bus.clone();
         self.bus = bus;
     }
 
+    fn set_mem(&mut self, mem: Rc<RefCell<Bus>>) {
+        self.mem = mem;
+    }
+
     fn set_pic(&mut self, pic: Rc<RefCell<Pic>>) {
         self.pic = pic;
     }
 
     fn wake(&mut self) {
-        self.status = Status::Enabled;
+        if let Status::Halted = self.status {
+            self.status = Status::Enabled;
+        }
+    }
+
+    fn press(&mut self) {
+        if let Status::Stopped = self.status {
+            self.status = Status::Enabled;
+        }
     }
 }
 
@@ -124,6 +462,11 @@ impl Machine for Cpu {
 
     fn cycle(&mut self) {
         self.state = std::mem::take(&mut self.state).exec(self);
+        // NOTE: A break occurs before the instruction is fetched, taking no
+        //       time.
+        if !matches!(self.status, Status::Break) {
+            self.cycles = self.cycles.wrapping_add(4);
+        }
     }
 }
 
@@ -159,6 +502,35 @@ struct Registers {
     pc: Register<u16>,
 }
 
+impl Registers {
+    /// Saves the values of all registers.
+    fn save(&self) -> CpuState {
+        CpuState {
+            af: self.af.get(self),
+            bc: self.bc.get(self),
+            de: self.de.get(self),
+            hl: self.hl.get(self),
+            sp: *self.sp,
+            pc: *self.pc,
+        }
+    }
+
+    /// Loads previously saved register values.
+    fn load(&mut self, state: CpuState) {
+        let wide = [
+            (self.af, state.af),
+            (self.bc, state.bc),
+            (self.de, state.de),
+            (self.hl, state.hl),
+        ];
+        for (reg, word) in wide {
+            reg.set(self, word);
+        }
+        *self.sp = state.sp;
+        *self.pc = state.pc;
+    }
+}
+
 impl Block for Registers {
     fn reset(&mut self) {
         // NOTE: the values of internal registers other than PC are undefined
@@ -180,7 +552,8 @@ impl Default for Registers {
                 },
                 set: |regs: &mut Registers, af: u16| {
                     *regs.a = ((af & 0xff00) >> 8) as u8;
-                    *regs.f = (af & 0x00ff) as u8;
+                    // NOTE: Lower 4 bits of F are unused, and cannot be set
+                    *regs.f = (af & 0x00f0) as u8;
                 },
             },
             b: Default::default(),
@@ -246,6 +619,51 @@ impl Display for Registers {
     }
 }
 
+/// CPU register values.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct CpuState {
+    pub af: u16,
+    pub bc: u16,
+    pub de: u16,
+    pub hl: u16,
+    pub sp: u16,
+    pub pc: u16,
+}
+
+/// CPU control state, beyond its registers.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct CpuControl {
+    /// Interrupt master enable.
+    pub ime: Ime,
+    /// Run status.
+    pub status: Status,
+    /// Whether the HALT bug will trigger on the next fetch.
+    pub halt_bug: bool,
+}
+
+/// CPU execution statistics.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CpuStats {
+    /// Instructions executed.
+    pub instructions: u64,
+    /// Elapsed T-cycles.
+    pub cycles: u64,
+    /// Executions of each opcode, followed by each `0xcb`-prefixed opcode.
+    pub opcodes: [u64; 0x200],
+}
+
+impl Default for CpuStats {
+    fn default() -> Self {
+        Self {
+            instructions: 0,
+            cycles: 0,
+            opcodes: [0; 0x200],
+        }
+    }
+}
+
 /// 16-bit wide linked register.
 #[derive(Copy, Clone)]
 struct WideRegister {
@@ -286,13 +704,30 @@ impl From<Flag> for u8 {
     }
 }
 
+/// Action to take after a CPU hook is called.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum HookAction {
+    /// Continue execution.
+    Continue,
+    /// Break before executing the instruction.
+    Break,
+}
+
 /// CPU run status.
-#[derive(Debug, Default)]
-enum Status {
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum Status {
+    /// Executing instructions.
     #[default]
     Enabled,
+    /// Halted until an interrupt is pending.
     Halted,
-    _Stopped,
+    /// Stopped until a joypad press.
+    Stopped,
+    /// Suspended by a pre-execution hook.
+    Break,
+    /// Locked up by an illegal opcode.
+    Locked(u8),
 }
 
 /// CPU execution state.
@@ -312,6 +747,9 @@ impl State {
             trace!("Registers:\n{}", cpu.regs);
 
             // Check for pending interrupts
+            // NOTE: When woken from HALT with interrupts disabled, execution
+            //       resumes without servicing the interrupt, leaving it
+            //       pending in IF.
             let int = match cpu.ime {
                 Ime::Enabled => cpu.pic.borrow().int(),
                 _ => None,
@@ -321,6 +759,11 @@ impl State {
             if let Some(int) = int {
                 // Acknowledge the interrupt
                 cpu.pic.borrow_mut().ack(int);
+                // Call the pre-execution hook again after servicing it
+                cpu.hooked = false;
+                if let Some(irq) = cpu.irq.get() {
+                    irq(int, *cpu.regs.pc);
+                }
                 // Skip State::Fetch
                 let inst = Instruction::int(int);
                 debug!("0xXXXX: {inst}");
@@ -335,6 +778,30 @@ impl State {
 
         // If we're State::Fetch, proceed to State::Execute(_) this cycle
         if let State::Fetch = self {
+            // Call the pre-execution hook
+            // NOTE: The hook is only called once per instruction, even if
+            //       execution was interrupted by a break.
+            if !std::mem::take(&mut cpu.hooked) {
+                let pc = *cpu.regs.pc;
+                let opcode = cpu.peek(pc);
+                if let Some(HookAction::Break) = cpu.hook.get().map(|hook| hook(pc, opcode)) {
+                    // Break before executing the instruction
+                    // NOTE: Upon resuming, interrupts are checked once more,
+                    //       as the instruction was never fetched.
+                    debug!("{pc:#06x}: Break");
+                    cpu.status = Status::Break;
+                    cpu.hooked = true;
+                    return State::Done;
+                }
+            }
+
+            // Call the trace hook
+            if let Some(trace) = cpu.trace.get() {
+                // NOTE: Peek through the field, as the hook is borrowed.
+                let opcode = cpu.mem.borrow().read(*cpu.regs.pc as usize);
+                trace(cpu.regs.save(), opcode);
+            }
+
             // Read the next instruction
             let pc = *cpu.regs.pc;
             let opcode = cpu.fetchbyte();
@@ -342,6 +809,10 @@ impl State {
             // Decode the instruction
             let inst = Instruction::new(opcode);
 
+            // Update statistics
+            cpu.stats.instructions += 1;
+            cpu.stats.opcodes[opcode as usize] += 1;
+
             // Check for HALT bug
             if cpu.halt_bug {
                 // Service the bug by rolling back the PC
@@ -350,19 +821,23 @@ impl State {
             }
 
             // Log the instruction
-            // NOTE: Ensure that prefix instructions are logged correctly
+            // NOTE: Ensure that prefix instructions are logged correctly, and
+            //       that relative jumps are logged with their target.
             debug!(
                 "{pc:#06x}: {}",
                 match opcode {
                     0xcb => {
-                        let opcode = cpu.bus.borrow().read(*cpu.regs.pc as usize);
+                        let opcode = cpu.peek(*cpu.regs.pc);
                         format!("{}", Instruction::prefix(opcode))
                     }
-                    _ => format!("{inst}"),
+                    _ => match inst.target(pc, cpu.peek(*cpu.regs.pc)) {
+                        Some(target) => inst.to_string().replace("r8", &format!("${target:04x}")),
+                        None => format!("{inst}"),
+                    },
                 }
             );
 
-            // Enable interrupts (after EI, RETI)
+            // Enable interrupts (after EI)
             if let Ime::WillEnable = cpu.ime {
                 cpu.ime = Ime::Enabled;
             }
@@ -387,11 +862,15 @@ impl State {
 }
 
 /// CPU interrupt master enable.
-#[derive(Debug, Default)]
-enum Ime {
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum Ime {
+    /// Interrupts are disabled.
     #[default]
     Disabled,
+    /// Interrupts are enabled.
     Enabled,
+    /// Interrupts will be enabled after the next instruction (following EI).
     WillEnable,
 }
 
@@ -400,3 +879,890 @@ impl Ime {
         matches!(self, Self::Enabled)
     }
 }
+
+/// Kind of bus access.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Access {
+    /// Instruction or operand fetch.
+    Fetch,
+    /// Memory read.
+    Read,
+    /// Memory write.
+    Write,
+}
+
+/// Logged bus access.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct BusAccess {
+    /// Kind of access.
+    pub kind: Access,
+    /// Accessed address.
+    pub addr: u16,
+    /// Value read or written.
+    pub value: u8,
+    /// CPU T-cycle of the access.
+    pub cycle: usize,
+}
+
+/// CPU bus access log.
+#[derive(Debug, Default)]
+struct AccessLog {
+    /// Whether accesses are logged.
+    enabled: bool,
+    /// Logged accesses, oldest first.
+    list: Vec<BusAccess>,
+}
+
+impl AccessLog {
+    fn push(&mut self, access: BusAccess) {
+        // Drop the oldest accesses once full
+        // NOTE: Up to twice the retained accesses are buffered so that the
+        //       log remains contiguous, while dropping in amortized O(1).
+        if self.list.len() == 2 * ACCESSES {
+            self.list.drain(..ACCESSES);
+        }
+        self.list.push(access);
+    }
+}
+
+/// CPU undo history.
+#[derive(Debug, Default)]
+struct History {
+    /// Recorded checkpoints, oldest first.
+    list: VecDeque<Checkpoint>,
+    /// Whether writes are recorded into the latest checkpoint.
+    open: bool,
+}
+
+/// Minimal record of the CPU state prior to executing an instruction.
+#[derive(Debug)]
+struct Checkpoint {
+    regs: CpuState,
+    status: Status,
+    ime: Ime,
+    halt_bug: bool,
+    /// Overwritten memory as `(address, previous)` pairs.
+    writes: Vec<(u16, u8)>,
+}
+
+#[cfg(test)]
+mod tests {
+    use remus::mem::Ram;
+
+    use super::*;
+
+    fn setup(prog: &[u8]) -> Cpu {
+        // Load the program into memory
+        let mut ram = Ram::<0x10000>::new();
+        prog.iter()
+            .enumerate()
+            .for_each(|(addr, &byte)| ram.write(addr, byte));
+        let mut bus = Bus::default();
+        bus.map(0x0000, ram.to_shared());
+        // Create a CPU connected to the memory
+        let mut cpu = Cpu::default();
+        cpu.set_bus(Rc::new(RefCell::new(bus)));
+        cpu
+    }
+
+    /// Runs a program loaded at $0100 until it halts (or `max_cycles` have
+    /// elapsed), returning the final register values.
+    ///
+    /// Execution starts from the register values left by the DMG boot ROM.
+    fn run_program(prog: &[u8], max_cycles: u64) -> CpuState {
+        let mut mem = vec![0; 0x0100];
+        mem.extend(prog);
+        let mut cpu = setup(&mem);
+        cpu.set_regs(CpuState {
+            af: 0x01b0,
+            bc: 0x0013,
+            de: 0x00d8,
+            hl: 0x014d,
+            sp: 0xfffe,
+            pc: 0x0100,
+        });
+        let mut cycles = 0;
+        while cpu.enabled() && cycles < max_cycles {
+            cpu.cycle();
+            cycles += 4;
+        }
+        cpu.regs()
+    }
+
+    /// Executes a single instruction, returning the cycles it took.
+    fn step(cpu: &mut Cpu) -> usize {
+        let mut cycles = 0;
+        loop {
+            cpu.cycle();
+            cycles += 4;
+            if let State::Done = cpu.state {
+                break cycles;
+            }
+        }
+    }
+
+    #[test]
+    fn step_back_works() {
+        let mut cpu = setup(&[
+            0x3e, 0x42, // LD A, 0x42
+            0x21, 0x00, 0xc1, // LD HL, 0xc100
+            0x77, // LD (HL), A
+        ]);
+        step(&mut cpu);
+        step(&mut cpu);
+
+        // Execute a write
+        cpu.checkpoint();
+        step(&mut cpu);
+        assert_eq!(*cpu.regs.pc, 0x0006);
+        assert_eq!(cpu.read(0xc100), 0x42);
+
+        // Undo the write
+        assert!(cpu.step_back());
+        assert_eq!(*cpu.regs.pc, 0x0005);
+        assert_eq!(cpu.read(0xc100), 0x00);
+        assert_eq!(*cpu.regs.a, 0x42);
+        // History is now exhausted
+        assert!(!cpu.step_back());
+    }
+
+    #[test]
+    fn step_back_twice_works() {
+        let mut cpu = setup(&[0x22; 0x100]); // LD (HL+), A
+        let regs = cpu.regs();
+        cpu.set_regs(CpuState {
+            af: 0x4200,
+            hl: 0x8000,
+            ..regs
+        });
+
+        // Write twice, undoing the second write
+        cpu.checkpoint();
+        step(&mut cpu);
+        cpu.checkpoint();
+        step(&mut cpu);
+        assert!(cpu.step_back());
+        assert_eq!(cpu.read(0x8001), 0x00);
+        // Writes are tracked against the first checkpoint
+        step(&mut cpu);
+        assert_eq!(cpu.read(0x8001), 0x42);
+        assert!(cpu.step_back());
+        assert_eq!([cpu.read(0x8000), cpu.read(0x8001)], [0x00, 0x00]);
+        assert_eq!(cpu.regs().hl, 0x8000);
+        assert_eq!(cpu.regs().pc, 0x0000);
+    }
+
+    #[test]
+    fn step_back_history_works() {
+        let mut cpu = setup(&[0x3c; 0x100]); // INC A
+        (0..100).for_each(|_| {
+            cpu.checkpoint();
+            step(&mut cpu);
+        });
+        assert_eq!(*cpu.regs.a, 100);
+
+        // Only the last 64 instructions can be undone
+        while cpu.step_back() {}
+        assert_eq!(*cpu.regs.a, 36);
+        assert_eq!(*cpu.regs.pc, 36);
+    }
+
+    #[test]
+    fn step_back_writes_works() {
+        let mut cpu = setup(&[
+            0x22, // LD (HL+), A
+            0x18, 0xfd, // JR -3
+        ]);
+        let regs = cpu.regs();
+        cpu.set_regs(CpuState { hl: 0x8000, ..regs });
+
+        // Writes are tracked up to a limit
+        cpu.checkpoint();
+        while cpu.hist.open {
+            assert!(cpu.hist.list.back().unwrap().writes.len() <= WRITES);
+            step(&mut cpu);
+            step(&mut cpu);
+        }
+        // After which the history is discarded
+        assert_eq!(cpu.regs().hl, 0x8000 + WRITES as u16 + 1);
+        assert!(!cpu.step_back());
+    }
+
+    #[test]
+    fn pre_exec_hook_works() {
+        let mut cpu = setup(&[0x00; 0x100]); // NOP
+
+        // Install a hook to break on the 10th instruction
+        let count = Rc::new(RefCell::new(0));
+        cpu.set_pre_exec_hook(Box::new({
+            let count = count.clone();
+            move |_, _| {
+                *count.borrow_mut() += 1;
+                match *count.borrow() {
+                    10 => HookAction::Break,
+                    _ => HookAction::Continue,
+                }
+            }
+        }));
+
+        // Run until the break
+        while cpu.enabled() {
+            cpu.cycle();
+        }
+        assert_eq!(*count.borrow(), 10);
+        assert_eq!(*cpu.regs.pc, 0x0009);
+        // Resume execution without calling the hook again
+        cpu.resume();
+        step(&mut cpu);
+        assert_eq!(*count.borrow(), 10);
+        assert_eq!(*cpu.regs.pc, 0x000a);
+        step(&mut cpu);
+        assert_eq!(*count.borrow(), 11);
+    }
+
+    #[test]
+    fn pre_exec_hook_interrupt_works() {
+        let mut cpu = setup(&[0x00; 0x100]); // NOP
+        cpu.ime = Ime::Enabled;
+        cpu.pic.borrow_mut().enable.borrow_mut().write(0, 0x01);
+
+        // Install a hook to break at $0010
+        cpu.set_pre_exec_hook(Box::new(|pc, _| match pc {
+            0x0010 => HookAction::Break,
+            _ => HookAction::Continue,
+        }));
+
+        // Run until the break
+        while cpu.enabled() {
+            cpu.cycle();
+        }
+        assert_eq!(*cpu.regs.pc, 0x0010);
+        // Breaking takes no time
+        let cycles = cpu.cycles;
+        assert_eq!(cycles, 0x10 * 4);
+
+        // Resume with an interrupt pending
+        cpu.pic.borrow_mut().req(Interrupt::VBlank);
+        cpu.resume();
+        step(&mut cpu);
+        // The interrupt is serviced before the instruction at the break
+        assert_eq!(*cpu.regs.pc, 0x0040);
+        assert_eq!(cpu.cycles, cycles + 20);
+        assert_eq!(cpu.peek(*cpu.regs.sp), 0x10);
+    }
+
+    #[test]
+    fn illegal_trap_works() {
+        let prog = [
+            0x00, // NOP
+            0xd3, // UNUSED
+        ];
+
+        // Illegal instructions lock up the CPU by default
+        let mut cpu = setup(&prog);
+        step(&mut cpu);
+        assert_eq!(cpu.locked(), None);
+        step(&mut cpu);
+        assert!(!cpu.enabled());
+        assert_eq!(cpu.locked(), Some(0xd3));
+
+        // Install a trap to report illegal instructions
+        let mut cpu = setup(&prog);
+        let trapped = Rc::new(RefCell::new(None));
+        cpu.set_illegal_trap(Box::new({
+            let trapped = trapped.clone();
+            move |pc, opcode| *trapped.borrow_mut() = Some((pc, opcode))
+        }));
+        step(&mut cpu);
+        step(&mut cpu);
+        assert_eq!(*trapped.borrow(), Some((0x0001, 0xd3)));
+        assert_eq!(cpu.locked(), Some(0xd3));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_works() {
+        let regs = CpuState {
+            af: 0x01b0,
+            bc: 0x0013,
+            de: 0x00d8,
+            hl: 0x014d,
+            sp: 0xfffe,
+            pc: 0x0100,
+        };
+        let data = bincode::serialize(&regs).unwrap();
+        assert_eq!(data.len(), 12);
+        assert_eq!(bincode::deserialize::<CpuState>(&data).unwrap(), regs);
+        let ctl = CpuControl {
+            ime: Ime::WillEnable,
+            status: Status::Locked(0xd3),
+            halt_bug: true,
+        };
+        let data = bincode::serialize(&ctl).unwrap();
+        assert_eq!(bincode::deserialize::<CpuControl>(&data).unwrap(), ctl);
+    }
+
+    #[test]
+    fn stats_works() {
+        let mut cpu = setup(&[
+            0x06, 0x0a, // LD B, 10
+            0x05, // DEC B
+            0x20, 0xfd, // JR NZ, -3
+        ]);
+        (0..21).for_each(|_| {
+            step(&mut cpu);
+        });
+        assert_eq!(*cpu.regs.pc, 0x0005);
+
+        let stats = cpu.stats();
+        assert_eq!(stats.instructions, 21);
+        assert_eq!(stats.cycles, 8 + 10 * 4 + 9 * 12 + 8);
+        assert_eq!(stats.opcodes[0x06], 1);
+        assert_eq!(stats.opcodes[0x05], 10);
+        assert_eq!(stats.opcodes[0x20], 10);
+        assert_eq!(stats.opcodes.iter().sum::<u64>(), 21);
+
+        // Statistics are cleared on reset
+        cpu.reset();
+        assert_eq!(cpu.stats(), CpuStats::default());
+    }
+
+    #[test]
+    fn run_program_works() {
+        let regs = run_program(
+            &[
+                0x3e, 0x42, // LD A, 0x42
+                0x76, // HALT
+            ],
+            1000,
+        );
+        assert_eq!(regs.af >> 8, 0x42);
+        assert_eq!(regs.pc, 0x0103);
+        // Programs which don't halt are stopped at the cycle cap
+        let regs = run_program(&[0x18, 0xfe], 1200); // JR -2
+        assert_eq!(regs.pc, 0x0100);
+    }
+
+    #[test]
+    fn push_pop_timing_works() {
+        let mut cpu = setup(&[
+            0xc5, // PUSH BC
+            0xc1, // POP BC
+        ]);
+        cpu.set_regs(CpuState {
+            bc: 0x1234,
+            sp: 0xfffe,
+            ..Default::default()
+        });
+        cpu.set_access_log(true);
+        assert_eq!(step(&mut cpu), 16);
+        assert_eq!(step(&mut cpu), 12);
+        assert_eq!(cpu.regs().bc, 0x1234);
+        assert_eq!(*cpu.regs.sp, 0xfffe);
+
+        // Check the stack is accessed on the correct cycles
+        let log = cpu.access_log();
+        assert_eq!(
+            log.iter()
+                .map(|access| (access.kind, access.addr, access.value, access.cycle))
+                .collect::<Vec<_>>(),
+            [
+                (Access::Fetch, 0x0000, 0xc5, 0),
+                (Access::Write, 0xfffd, 0x12, 8),
+                (Access::Write, 0xfffc, 0x34, 12),
+                (Access::Fetch, 0x0001, 0xc1, 16),
+                (Access::Read, 0xfffc, 0x34, 20),
+                (Access::Read, 0xfffd, 0x12, 24),
+            ]
+        );
+    }
+
+    #[test]
+    fn access_log_works() {
+        let mut cpu = setup(&[
+            0x3e, 0x42, // LD A, 0x42
+            0xea, 0x00, 0x01, // LD (0x0100), A
+            0xfa, 0x00, 0x01, // LD A, (0x0100)
+        ]);
+        cpu.set_access_log(true);
+        (0..3).for_each(|_| {
+            step(&mut cpu);
+        });
+
+        // Check the logged accesses
+        let log = cpu.access_log();
+        assert_eq!(
+            log.iter()
+                .map(|access| (access.kind, access.addr, access.value))
+                .collect::<Vec<_>>(),
+            [
+                (Access::Fetch, 0x0000, 0x3e),
+                (Access::Fetch, 0x0001, 0x42),
+                (Access::Fetch, 0x0002, 0xea),
+                (Access::Fetch, 0x0003, 0x00),
+                (Access::Fetch, 0x0004, 0x01),
+                (Access::Write, 0x0100, 0x42),
+                (Access::Fetch, 0x0005, 0xfa),
+                (Access::Fetch, 0x0006, 0x00),
+                (Access::Fetch, 0x0007, 0x01),
+                (Access::Read, 0x0100, 0x42),
+            ]
+        );
+        assert!(log.windows(2).all(|pair| pair[0].cycle <= pair[1].cycle));
+        assert_eq!(log[9].cycle, 32);
+    }
+
+    #[test]
+    fn halt_ime_disabled_works() {
+        let mut cpu = setup(&[
+            0x76, // HALT
+            0x3c, // INC A
+        ]);
+        **cpu.pic.borrow().enable.borrow_mut() = Interrupt::Timer as u8;
+
+        // Halt until an interrupt is pending
+        step(&mut cpu);
+        assert!(!cpu.enabled());
+        cpu.pic.borrow_mut().req(Interrupt::Timer);
+        cpu.wake();
+        assert!(cpu.enabled());
+
+        // Resume after HALT without servicing the interrupt
+        step(&mut cpu);
+        assert_eq!(*cpu.regs.pc, 0x0002);
+        assert_eq!(*cpu.regs.a, 0x01);
+        assert!(cpu.pic.borrow().int().is_some());
+    }
+
+    #[test]
+    fn halt_ime_enabled_works() {
+        let mut cpu = setup(&[
+            0xfb, // EI
+            0x76, // HALT
+            0x3c, // INC A
+        ]);
+        **cpu.pic.borrow().enable.borrow_mut() = Interrupt::Timer as u8;
+
+        // Halt until an interrupt is pending
+        step(&mut cpu);
+        step(&mut cpu);
+        assert!(!cpu.enabled());
+        cpu.pic.borrow_mut().req(Interrupt::Timer);
+        cpu.wake();
+
+        // Service the interrupt
+        assert_eq!(step(&mut cpu), 20);
+        assert_eq!(*cpu.regs.pc, 0x0050);
+        assert_eq!(*cpu.regs.a, 0x00);
+        assert!(cpu.pic.borrow().int().is_none());
+        // Return to the instruction after HALT
+        assert_eq!(cpu.popword(), 0x0002);
+    }
+
+    #[test]
+    fn pop_af_works() {
+        let mut prog = [0; 0x12];
+        prog[..4].copy_from_slice(&[
+            0x31, 0x10, 0x00, // LD SP, 0x0010
+            0xf1, // POP AF
+        ]);
+        prog[0x10..].copy_from_slice(&[0xff, 0xff]);
+        let mut cpu = setup(&prog);
+        step(&mut cpu);
+        step(&mut cpu);
+
+        // Lower 4 bits of F are masked
+        assert_eq!(*cpu.regs.a, 0xff);
+        assert_eq!(*cpu.regs.f, 0xf0);
+        assert_eq!(cpu.regs().af, 0xfff0);
+        // Including when set directly
+        cpu.set_regs(CpuState {
+            af: 0x120f,
+            ..cpu.regs()
+        });
+        assert_eq!(cpu.regs().af, 0x1200);
+    }
+
+    #[test]
+    fn jr_target_works() {
+        // Resolve relative to the following instruction
+        assert_eq!(Instruction::new(0x18).target(0x0150, 0xfe), Some(0x0150));
+        assert_eq!(Instruction::new(0x20).target(0x0142, 0x03), Some(0x0147));
+        assert_eq!(Instruction::new(0x38).target(0xfffe, 0x7f), Some(0x007f));
+        // Only relative jumps have a target
+        assert_eq!(Instruction::new(0xc3).target(0x0150, 0xfe), None);
+    }
+
+    #[test]
+    fn disasm_works() {
+        let text = |bytes: &[u8]| disasm(0x0150, bytes);
+        // Operands are substituted
+        assert_eq!(text(&[0x3e, 0x42]), Some(("LD A, $42".into(), 2)));
+        assert_eq!(text(&[0xea, 0x00, 0xc0]), Some(("LD ($c000), A".into(), 3)));
+        assert_eq!(text(&[0xe0, 0x40]), Some(("LDH ($ff40), A".into(), 2)));
+        assert_eq!(text(&[0x18, 0xfe]), Some(("JR $0150".into(), 2)));
+        assert_eq!(text(&[0xf8, 0xfe]), Some(("LD HL, SP - 2".into(), 2)));
+        // Prefixed instructions are followed
+        assert_eq!(text(&[0xcb, 0x7c]), Some(("BIT 7, H".into(), 2)));
+        // Undefined or truncated instructions are data
+        assert_eq!(text(&[0xd3, 0x00]), Some(("DB $d3".into(), 1)));
+        assert_eq!(text(&[0xc3, 0x00]), Some(("DB $c3".into(), 1)));
+        assert_eq!(text(&[]), None);
+    }
+
+    #[test]
+    fn cycles_works() {
+        // Costs are reported from the opcode table
+        assert_eq!(Instruction::new(0x7e).cycles(), 8);
+        assert_eq!(Instruction::new(0x00).cycles(), 4);
+        assert_eq!(Instruction::new(0xcd).cycles(), 24);
+        assert_eq!(Instruction::prefix(0x46).cycles(), 12);
+        assert_eq!(Instruction::prefix(0x86).cycles(), 16);
+        assert_eq!(Instruction::prefix(0x00).cycles(), 8);
+        // Interrupt service routines have a fixed cost
+        assert_eq!(Instruction::int(Interrupt::VBlank).cycles(), 20);
+        // Conditional costs depend on the branch
+        let jr = Instruction::new(0x20);
+        assert_eq!((jr.cycles(), jr.cycles_taken()), (8, 12));
+        let call = Instruction::new(0xc4);
+        assert_eq!((call.cycles(), call.cycles_taken()), (12, 24));
+        // Costs can be predicted for the instruction at PC
+        let cpu = setup(&[0xcb, 0x46]);
+        assert_eq!(cpu.next_cycles(), (12, 12));
+    }
+
+    #[test]
+    fn jr_cc_timing_works() {
+        // JR NZ, -2 (not taken)
+        let mut cpu = setup(&[0x20, 0xfe]);
+        *cpu.regs.f = Flag::Z as u8;
+        assert_eq!(step(&mut cpu), 8);
+        assert_eq!(*cpu.regs.pc, 0x0002);
+
+        // JR NZ, -2 (taken)
+        let mut cpu = setup(&[0x20, 0xfe]);
+        *cpu.regs.f = 0;
+        assert_eq!(step(&mut cpu), 12);
+        assert_eq!(*cpu.regs.pc, 0x0000);
+    }
+
+    #[test]
+    fn cc_timing_works() {
+        #[rustfmt::skip]
+        let progs: [&[u8]; 3] = [
+            &[0xc2, 0x00, 0x00], // JP NZ, 0x0000
+            &[0xc4, 0x00, 0x00], // CALL NZ, 0x0000
+            &[0xc0],             // RET NZ
+        ];
+        for prog in progs {
+            let inst = Instruction::new(prog[0]);
+            // Not taken
+            let mut cpu = setup(prog);
+            *cpu.regs.f = Flag::Z as u8;
+            assert_eq!(step(&mut cpu), inst.cycles() as usize);
+            assert_eq!(*cpu.regs.pc, prog.len() as u16);
+            // Taken
+            let mut cpu = setup(prog);
+            *cpu.regs.f = 0;
+            assert_eq!(step(&mut cpu), inst.cycles_taken() as usize);
+            assert!(inst.cycles() < inst.cycles_taken());
+        }
+    }
+
+    #[test]
+    fn inc_dec_hl_works() {
+        // INC (HL) (half-carry from bit 3, C preserved)
+        let mut cpu = setup(&[0x34, 0x0f]);
+        cpu.set_regs(CpuState {
+            af: 0x0010,
+            hl: 0x0001,
+            ..Default::default()
+        });
+        cpu.set_access_log(true);
+        assert_eq!(step(&mut cpu), 12);
+        assert_eq!(cpu.peek(0x0001), 0x10);
+        assert_eq!(*cpu.regs.f, Flag::H as u8 | Flag::C as u8);
+        // Memory is read, then written, on consecutive cycles
+        let log = cpu.access_log();
+        assert_eq!(
+            log.iter()
+                .map(|access| (access.kind, access.value, access.cycle - log[0].cycle))
+                .collect::<Vec<_>>(),
+            [
+                (Access::Fetch, 0x34, 0),
+                (Access::Read, 0x0f, 4),
+                (Access::Write, 0x10, 8),
+            ]
+        );
+
+        // DEC (HL) (half-borrow from bit 4, C preserved)
+        let mut cpu = setup(&[0x35, 0x10]);
+        cpu.set_regs(CpuState {
+            af: 0x0010,
+            hl: 0x0001,
+            ..Default::default()
+        });
+        assert_eq!(step(&mut cpu), 12);
+        assert_eq!(cpu.peek(0x0001), 0x0f);
+        assert_eq!(*cpu.regs.f, Flag::N as u8 | Flag::H as u8 | Flag::C as u8);
+    }
+
+    #[test]
+    fn add_hl_rr_flags_work() {
+        // ADD HL, BC (half-carry from bit 11)
+        let mut cpu = setup(&[0x09]);
+        cpu.set_regs(CpuState {
+            bc: 0x0001,
+            hl: 0x0fff,
+            ..Default::default()
+        });
+        assert_eq!(step(&mut cpu), 8);
+        assert_eq!(cpu.regs.hl.get(&cpu.regs), 0x1000);
+        assert_eq!(*cpu.regs.f, Flag::H as u8);
+
+        // ADD HL, BC (carry from bit 15, Z preserved)
+        let mut cpu = setup(&[0x09]);
+        cpu.set_regs(CpuState {
+            af: 0x00c0,
+            bc: 0x0001,
+            hl: 0xffff,
+            ..Default::default()
+        });
+        assert_eq!(step(&mut cpu), 8);
+        assert_eq!(cpu.regs.hl.get(&cpu.regs), 0x0000);
+        assert_eq!(*cpu.regs.f, Flag::Z as u8 | Flag::H as u8 | Flag::C as u8);
+    }
+
+    #[test]
+    fn add_sp_r8_flags_work() {
+        // ADD SP, -1 (flags from the low byte, Z and N cleared)
+        let mut cpu = setup(&[0xe8, 0xff]);
+        cpu.set_regs(CpuState {
+            af: 0x00f0,
+            sp: 0x0001,
+            ..Default::default()
+        });
+        assert_eq!(step(&mut cpu), 16);
+        assert_eq!(*cpu.regs.sp, 0x0000);
+        assert_eq!(*cpu.regs.f, Flag::H as u8 | Flag::C as u8);
+
+        // ADD SP, -1 (no carry out of the low byte, despite 16-bit borrow)
+        let mut cpu = setup(&[0xe8, 0xff]);
+        cpu.set_regs(CpuState {
+            sp: 0x0000,
+            ..Default::default()
+        });
+        assert_eq!(step(&mut cpu), 16);
+        assert_eq!(*cpu.regs.sp, 0xffff);
+        assert_eq!(*cpu.regs.f, 0x00);
+    }
+
+    #[test]
+    fn ime_timing_works() {
+        // EI enables interrupts after the following instruction
+        let mut cpu = setup(&[
+            0xfb, // EI
+            0x00, // NOP
+            0x00, // NOP
+        ]);
+        cpu.set_regs(CpuState {
+            sp: 0xfffe,
+            ..Default::default()
+        });
+        **cpu.pic.borrow().enable.borrow_mut() = Interrupt::Timer as u8;
+        cpu.pic.borrow_mut().req(Interrupt::Timer);
+        step(&mut cpu);
+        assert_eq!(*cpu.regs.pc, 0x0001);
+        step(&mut cpu);
+        assert_eq!(*cpu.regs.pc, 0x0002);
+        assert_eq!(step(&mut cpu), 20);
+        assert_eq!(*cpu.regs.pc, 0x0050);
+        assert_eq!(cpu.popword(), 0x0002);
+
+        // RETI enables interrupts immediately
+        let mut cpu = setup(&[
+            0xd9, // RETI
+            0x00, // NOP
+        ]);
+        cpu.set_regs(CpuState {
+            sp: 0xfffe,
+            ..Default::default()
+        });
+        cpu.pushword(0x0001);
+        **cpu.pic.borrow().enable.borrow_mut() = Interrupt::Timer as u8;
+        cpu.pic.borrow_mut().req(Interrupt::Timer);
+        assert_eq!(step(&mut cpu), 16);
+        assert_eq!(*cpu.regs.pc, 0x0001);
+        assert_eq!(step(&mut cpu), 20);
+        assert_eq!(*cpu.regs.pc, 0x0050);
+        assert_eq!(cpu.popword(), 0x0001);
+    }
+
+    #[test]
+    fn ei_delay_works() {
+        let mut cpu = setup(&[
+            0xfb, // EI
+            0x01, 0x34, 0x12, // LD BC, 0x1234
+            0x00, // NOP
+        ]);
+        cpu.set_regs(CpuState {
+            sp: 0xfffe,
+            ..Default::default()
+        });
+        **cpu.pic.borrow().enable.borrow_mut() = Interrupt::Timer as u8;
+        cpu.pic.borrow_mut().req(Interrupt::Timer);
+
+        // The instruction following EI runs to completion uninterrupted
+        assert_eq!(step(&mut cpu), 4);
+        assert_eq!(step(&mut cpu), 12);
+        assert_eq!((*cpu.regs.pc, cpu.regs().bc), (0x0004, 0x1234));
+        // Only then is the pending interrupt serviced
+        assert_eq!(step(&mut cpu), 20);
+        assert_eq!(*cpu.regs.pc, 0x0050);
+        assert_eq!(cpu.popword(), 0x0004);
+    }
+
+    #[test]
+    fn ei_sequence_works() {
+        let mut cpu = setup(&[
+            0xfb, // EI
+            0xfb, // EI
+            0x00, // NOP
+        ]);
+        cpu.set_regs(CpuState {
+            sp: 0xfffe,
+            ..Default::default()
+        });
+        **cpu.pic.borrow().enable.borrow_mut() = Interrupt::Timer as u8;
+        cpu.pic.borrow_mut().req(Interrupt::Timer);
+
+        // The first EI takes effect after the second
+        assert_eq!(step(&mut cpu), 4);
+        assert_eq!(step(&mut cpu), 4);
+        assert_eq!(step(&mut cpu), 20);
+        assert_eq!(*cpu.regs.pc, 0x0050);
+        assert_eq!(cpu.popword(), 0x0002);
+    }
+
+    #[test]
+    fn reti_chain_works() {
+        let mut prog = [0x00; 0x60];
+        prog[0x00] = 0xfb; // EI
+        prog[0x50] = 0xd9; // RETI
+        let mut cpu = setup(&prog);
+        cpu.set_regs(CpuState {
+            sp: 0xfffe,
+            ..Default::default()
+        });
+        **cpu.pic.borrow().enable.borrow_mut() = Interrupt::Timer as u8 | Interrupt::Serial as u8;
+
+        // Service a Timer interrupt
+        cpu.pic.borrow_mut().req(Interrupt::Timer);
+        for _ in 0..3 {
+            step(&mut cpu);
+        }
+        assert_eq!(*cpu.regs.pc, 0x0050);
+        // Return while another interrupt is pending
+        cpu.pic.borrow_mut().req(Interrupt::Serial);
+        assert_eq!(step(&mut cpu), 16);
+        assert_eq!(*cpu.regs.pc, 0x0002);
+        // The pending interrupt is serviced before the next instruction
+        assert_eq!(step(&mut cpu), 20);
+        assert_eq!(*cpu.regs.pc, 0x0058);
+        assert_eq!(cpu.popword(), 0x0002);
+    }
+
+    #[test]
+    fn rst_works() {
+        for (opcode, vector) in (0xc7..=0xff).step_by(8).zip((0x00..=0x38).step_by(8)) {
+            let mut prog = [0x00; 0x1234];
+            prog[0x1232] = 0xfb; // EI
+            prog[0x1233] = opcode; // RST
+            let mut cpu = setup(&prog);
+            cpu.set_regs(CpuState {
+                sp: 0xfffe,
+                pc: 0x1232,
+                ..Default::default()
+            });
+            **cpu.pic.borrow().enable.borrow_mut() = Interrupt::Timer as u8;
+
+            // Restart to the vector
+            step(&mut cpu);
+            cpu.pic.borrow_mut().req(Interrupt::Timer);
+            assert_eq!(step(&mut cpu), 16);
+            assert_eq!(*cpu.regs.pc, vector);
+            assert_eq!(*cpu.regs.sp, 0xfffc);
+            assert_eq!(cpu.bus.borrow().read(0xfffd), 0x12);
+            assert_eq!(cpu.bus.borrow().read(0xfffc), 0x34);
+            // The pending interrupt is serviced at the vector
+            assert_eq!(step(&mut cpu), 20);
+            assert_eq!(*cpu.regs.pc, 0x0050);
+            assert_eq!(cpu.popword(), vector);
+            assert_eq!(cpu.popword(), 0x1234);
+        }
+    }
+
+    #[test]
+    fn on_interrupt_works() {
+        let mut cpu = setup(&[
+            0xfb, // EI
+            0x00, // NOP
+            0x00, // NOP
+        ]);
+        cpu.set_regs(CpuState {
+            sp: 0xfffe,
+            ..Default::default()
+        });
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        cpu.on_interrupt(Box::new({
+            let seen = seen.clone();
+            move |int, ret| seen.borrow_mut().push((int, ret))
+        }));
+
+        // Service a VBlank interrupt
+        **cpu.pic.borrow().enable.borrow_mut() = Interrupt::VBlank as u8;
+        cpu.pic.borrow_mut().req(Interrupt::VBlank);
+        step(&mut cpu);
+        step(&mut cpu);
+        assert!(seen.borrow().is_empty());
+        step(&mut cpu);
+        assert_eq!(*cpu.regs.pc, 0x0040);
+        assert_eq!(*seen.borrow(), [(Interrupt::VBlank, 0x0002)]);
+    }
+
+    #[test]
+    fn adc_sbc_carry_works() {
+        const Z: u8 = Flag::Z as u8;
+        const N: u8 = Flag::N as u8;
+        const H: u8 = Flag::H as u8;
+        const C: u8 = Flag::C as u8;
+
+        // Each case is (opcode, A, n, carry-in) => (A, F)
+        #[rustfmt::skip]
+        let cases = [
+            // ADC A, d8
+            ((0xce, 0x0f, 0x00, true ), (0x10, H)),
+            ((0xce, 0x0f, 0x00, false), (0x0f, 0)),
+            ((0xce, 0x0e, 0x01, true ), (0x10, H)),
+            ((0xce, 0xff, 0x00, true ), (0x00, Z | H | C)),
+            ((0xce, 0xf0, 0x0f, true ), (0x00, Z | H | C)),
+            ((0xce, 0x80, 0x7f, false), (0xff, 0)),
+            // SBC A, d8
+            ((0xde, 0x10, 0x00, true ), (0x0f, N | H)),
+            ((0xde, 0x10, 0x00, false), (0x10, N)),
+            ((0xde, 0x00, 0x00, true ), (0xff, N | H | C)),
+            ((0xde, 0x00, 0xff, true ), (0x00, Z | N | H | C)),
+            ((0xde, 0x1f, 0x0f, true ), (0x0f, N | H)),
+            ((0xde, 0x20, 0x0f, false), (0x11, N | H)),
+        ];
+        for ((opcode, a, n, cin), (res, flags)) in cases {
+            let mut cpu = setup(&[opcode, n]);
+            cpu.set_regs(CpuState {
+                af: u16::from_be_bytes([a, [0, C][cin as usize]]),
+                ..Default::default()
+            });
+            assert_eq!(step(&mut cpu), 8);
+            assert_eq!((*cpu.regs.a, *cpu.regs.f), (res, flags));
+        }
+    }
+}
@@ -4,13 +4,14 @@ use std::cell::RefCell;
 use std::rc::Rc;
 
 use log::{info, trace};
-use remus::{reg, Block, Device};
+use remus::{Block, Device};
 
 use super::pic::{Interrupt, Pic};
 
 /// Joypad button encoding.
 #[rustfmt::skip]
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum Button {
     A      = 0b00100001,
     B      = 0b00100010,
@@ -22,6 +23,20 @@ pub enum Button {
     Down   = 0b00011000,
 }
 
+impl Button {
+    /// Gets the button's bit within the pressed buttons.
+    fn mask(self) -> u8 {
+        let btn = self as u8;
+        match btn & 0x30 {
+            0x20 => btn & 0x0f,
+            _ => (btn & 0x0f) << 4,
+        }
+    }
+}
+
+/// Buttons pressed at once.
+pub type JoypadState = Vec<Button>;
+
 /// Joypad model.
 #[rustfmt::skip]
 #[derive(Debug, Default)]
@@ -41,30 +56,27 @@ impl Joypad {
     /// Handle pressed button inputs.
     #[allow(unused)]
     pub fn input(&mut self, keys: Vec<Button>) {
-        // Retrieve controller state (inverted)
-        let prev = !*self.con.borrow().0;
-        let is_empty = keys.is_empty();
-
-        // Calculate updated state
-        let next = keys
-            // Use `.iter().cloned()` to allow use of `btns` later for logging.
-            .iter()
-            .cloned()
-            // Filter buttons as requested in the controller register
-            .filter(|&btn| (prev & btn as u8) & 0x30 != 0)
-            // Fold matching pressed buttons' corresponding bits into a byte
-            .fold(prev & 0xf0, |acc, btn| acc | ((btn as u8) & 0x0f));
+        let mut con = self.con.borrow_mut();
+        let prev = con.keys();
+
+        // Update pressed buttons
+        con.pressed = keys.iter().fold(0, |acc, &btn| acc | btn.mask());
+        let next = con.keys();
+        drop(con);
 
         // Schedule interrupt on updated value
-        if (prev & 0x0f) != (next & 0x0f) {
+        if prev != next {
             self.pic.borrow_mut().req(Interrupt::Joypad);
-            info!("Input {next:#010b}: {keys:?}"); // log updates with `info`
-        } else if !is_empty {
-            trace!("Input {next:#010b}: {keys:?}"); // log others with `trace`
+            info!("Input {next:#06b}: {keys:?}"); // log updates with `info`
+        } else if !keys.is_empty() {
+            trace!("Input {next:#06b}: {keys:?}"); // log others with `trace`
         }
+    }
 
-        // Update controller state (inverted)
-        *self.con.borrow_mut().0 = !next;
+    /// Checks if any of the selected buttons are pressed.
+    #[must_use]
+    pub fn pressed(&self) -> bool {
+        self.con.borrow().keys() != 0x0f
     }
 }
 
@@ -76,8 +88,33 @@ impl Block for Joypad {
 }
 
 /// Player input register.
+///
+/// Only the button group select bits (`0x30`) are writable. The lower nibble
+/// reports the pressed buttons (active low) within the selected groups, and
+/// the unused upper bits always read as set.
 #[derive(Debug)]
-pub struct Register(reg::Register<u8>);
+pub struct Register {
+    /// Button group select (active low).
+    sel: u8,
+    /// Pressed buttons, as `0bDDDDAAAA` (directions, actions).
+    pressed: u8,
+}
+
+impl Register {
+    /// Gets the lower nibble, reflecting the selected pressed buttons.
+    fn keys(&self) -> u8 {
+        let mut keys = 0;
+        // Action buttons
+        if self.sel & 0x20 == 0 {
+            keys |= self.pressed & 0x0f;
+        }
+        // Direction buttons
+        if self.sel & 0x10 == 0 {
+            keys |= self.pressed >> 4;
+        }
+        !keys & 0x0f
+    }
+}
 
 impl Block for Register {
     fn reset(&mut self) {
@@ -87,28 +124,82 @@ impl Block for Register {
 
 impl Default for Register {
     fn default() -> Self {
-        Self(reg::Register::from(0xff))
+        Self {
+            sel: 0x30,
+            pressed: 0x00,
+        }
     }
 }
 
 impl Device for Register {
     fn contains(&self, index: usize) -> bool {
-        self.0.contains(index)
+        (0..self.len()).contains(&index)
     }
 
     fn len(&self) -> usize {
-        self.0.len()
+        1
     }
 
-    fn read(&self, index: usize) -> u8 {
-        self.0.read(index)
+    fn read(&self, _: usize) -> u8 {
+        // NOTE: Unused bits 0xc0 always read as set
+        0xc0 | self.sel | self.keys()
     }
 
-    fn write(&mut self, index: usize, mut value: u8) {
+    fn write(&mut self, _: usize, value: u8) {
         // NOTE: Only bits 0x30 are writable
-        const MASK: u8 = 0x30;
-        let read = self.read(index);
-        value = (read & !MASK) | (value & MASK);
-        self.0.write(index, value)
+        self.sel = value & 0x30;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup() -> Joypad {
+        let mut joypad = Joypad::default();
+        joypad.reset();
+        joypad
+    }
+
+    #[test]
+    fn register_select_works() {
+        let mut joypad = setup();
+        joypad.input(vec![Button::A, Button::Start, Button::Down]);
+
+        // Select neither group
+        joypad.con.borrow_mut().write(0, 0x30);
+        assert_eq!(joypad.con.borrow().read(0), 0xff);
+        // Select action buttons
+        joypad.con.borrow_mut().write(0, 0x10);
+        assert_eq!(joypad.con.borrow().read(0), 0xd6);
+        // Select direction buttons
+        joypad.con.borrow_mut().write(0, 0x20);
+        assert_eq!(joypad.con.borrow().read(0), 0xe7);
+        // Select both groups
+        joypad.con.borrow_mut().write(0, 0x00);
+        assert_eq!(joypad.con.borrow().read(0), 0xc6);
+    }
+
+    #[test]
+    fn register_read_works() {
+        let mut joypad = setup();
+        joypad.input(vec![Button::A, Button::Up]);
+
+        for value in 0x00..=0xff {
+            joypad.con.borrow_mut().write(0, value);
+            let read = joypad.con.borrow().read(0);
+            // Unused bits always read as set
+            assert_eq!(read & 0xc0, 0xc0);
+            // Select bits read back as written
+            assert_eq!(read & 0x30, value & 0x30);
+            // Unselected groups read as released
+            let keys = match value & 0x30 {
+                0x00 => 0x0a,
+                0x10 => 0x0e,
+                0x20 => 0x0b,
+                _ => 0x0f,
+            };
+            assert_eq!(read & 0x0f, keys);
+        }
     }
 }
@@ -8,4 +8,5 @@ pub(crate) mod cpu;
 pub(crate) mod joypad;
 pub(crate) mod pic;
 pub(crate) mod ppu;
+pub(crate) mod serial;
 pub(crate) mod timer;
@@ -44,7 +44,7 @@ impl Block for Pic {
 
 /// Interrupt type.
 #[rustfmt::skip]
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum Interrupt {
     // ┌─────┬──────────┬─────────┐
     // │ BIT │  SOURCE  │ HANDLER │
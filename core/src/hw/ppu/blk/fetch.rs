@@ -158,15 +158,16 @@ impl Stage {
             }
             Stage::ReadData0 { addr } => {
                 // Fetch the first byte of the tile
-                let data0 = ppu.vram.borrow().read(addr as usize);
+                // NOTE: Addresses wrap within the 8 KiB of VRAM.
+                let data0 = ppu.vram.borrow().read((addr & 0x1fff) as usize);
 
                 // Progress to next stage
-                let addr = addr + 1;
+                let addr = addr.wrapping_add(1);
                 Stage::ReadData1 { addr, data0 }
             }
             Stage::ReadData1 { addr, data0 } => {
-                // Fetch the seocnd byte of the tile
-                let data1 = ppu.vram.borrow().read(addr as usize);
+                // Fetch the second byte of the tile
+                let data1 = ppu.vram.borrow().read((addr & 0x1fff) as usize);
 
                 // Decode pixels from data
                 let row = TileRow::from([data0, data1]);
@@ -192,3 +193,38 @@ impl Stage {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use remus::Block;
+
+    use super::*;
+    use crate::hw::ppu::Color;
+
+    #[test]
+    fn data_wrap_works() {
+        let mut ppu = Ppu::default();
+        ppu.reset();
+        // Mark the last and first bytes of VRAM
+        ppu.vram.borrow_mut().write(0x1fff, 0xff);
+        ppu.vram.borrow_mut().write(0x0000, 0x0f);
+
+        // Fetch a tile row straddling the top of VRAM
+        let mut fetch = Fetch::default();
+        let mut fifo = Fifo::default();
+        let mut stage = Stage::ReadData0 { addr: 0xffff };
+        for _ in 0..2 {
+            stage = stage.exec(&mut fetch, &mut fifo, &mut ppu, Location::Background);
+        }
+        // Second byte is read from the wrapped address
+        let Stage::Push(row) = stage else {
+            panic!("expected a fetched row");
+        };
+        #[rustfmt::skip]
+        let cols = [
+            Color::C1, Color::C1, Color::C1, Color::C1,
+            Color::C3, Color::C3, Color::C3, Color::C3,
+        ];
+        assert!(row.iter().map(|pixel| pixel.col()).eq(cols));
+    }
+}
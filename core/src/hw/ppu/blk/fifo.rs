@@ -13,7 +13,7 @@ impl Fifo {
 
     pub fn try_append(&mut self, row: TileRow) -> Result<(), TileRow> {
         if self.0.is_empty() {
-            self.0.append(&mut row.0.into());
+            self.0.extend(row.0);
             Ok(())
         } else {
             Err(row)
@@ -38,34 +38,51 @@ impl Deref for TileRow {
 
 impl From<[u8; 2]> for TileRow {
     fn from(bytes: [u8; 2]) -> Self {
-        // Iterate through each bit of both bytes
-        let pixels = (0..u8::BITS)
-            // Combine upper and lower bytes into colors for each bit
-            .map(|bit| {
-                // Extract color bits
-                let mask = 0b1 << bit;
-                let bit0 = bytes[0] & mask != 0;
-                let bit1 = bytes[1] & mask != 0;
-                // Combine into color value
-                ((bit1 as u8) << 1) | (bit0 as u8)
-            })
-            // Reverse, since bit 7 represents the leftmost pixel, and bit 0 the
-            // rightmost.
-            .rev()
-            // Convert into pixels
-            .map(|col| Pixel {
+        // Build each pixel in place (avoiding any allocation)
+        let pixels = std::array::from_fn(|idx| {
+            // NOTE: Bit 7 represents the leftmost pixel, and bit 0 the
+            //       rightmost.
+            let mask = 0x80 >> idx;
+            // Extract color bits
+            let bit0 = bytes[0] & mask != 0;
+            let bit1 = bytes[1] & mask != 0;
+            // Combine into color value
+            let col = ((bit1 as u8) << 1) | (bit0 as u8);
+            // Convert into pixel
+            Pixel {
                 // FIXME: Properly handle `pal`, `bgp`
                 col: col.try_into().unwrap(), // succeeds since values are 2-bit
                 pal: Palette::BgWin,
                 bgp: false,
-            })
-            // Collect into an array of [Pixel; 8]
-            .collect::<Vec<Pixel>>()
-            .try_into()
-            .unwrap(); // this will succeed on any sane platform where there are
-                       // 8 bits in a byte. We won't support whatever archaic
-                       // (or futuristic) platform does otherwise. Sorry :P
+            }
+        });
 
         Self(pixels)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hw::ppu::Color;
+
+    #[test]
+    fn tile_row_works() {
+        // Decode a row with every color, from leftmost to rightmost
+        let row = TileRow::from([0b1010_0101, 0b1100_0011]);
+        #[rustfmt::skip]
+        let cols = [
+            Color::C3, Color::C2, Color::C1, Color::C0,
+            Color::C0, Color::C1, Color::C2, Color::C3,
+        ];
+        assert!(row.iter().map(Pixel::col).eq(cols));
+
+        // Pixels are appended in order to an empty FIFO only
+        let mut fifo = Fifo::default();
+        assert!(fifo.try_append(row).is_ok());
+        assert!(fifo.try_append(TileRow::from([0x00; 2])).is_err());
+        assert!(std::iter::from_fn(|| fifo.pop())
+            .map(|pixel| pixel.col)
+            .eq(cols));
+    }
+}
@@ -0,0 +1,129 @@
+use remus::{Block, Device};
+
+/// Color palette memory.
+///
+/// # Usage
+///
+/// Backs the CGB's 8 palettes of 4 colors each, where every color is stored
+/// as a little-endian RGB555 value. Memory is accessed indirectly through a
+/// pair of registers:
+/// - Specification (`0x0`): selects the byte address, with bit 7 enabling
+///   auto-increment.
+/// - Data (`0x1`): reads or writes the selected byte.
+///
+/// When auto-increment is enabled, the address is advanced after each write
+/// to the data register, wrapping around at the end of memory.
+#[derive(Debug)]
+pub struct Cram {
+    idx: u8,
+    inc: bool,
+    mem: [u8; 0x40],
+}
+
+impl Cram {
+    /// Gets a color as an RGB555 value.
+    #[must_use]
+    pub fn color(&self, pal: usize, col: usize) -> u16 {
+        let addr = 8 * pal + 2 * col;
+        u16::from_le_bytes([self.mem[addr], self.mem[addr + 1]])
+    }
+
+    /// Dumps the specification register, followed by the palette memory.
+    #[must_use]
+    pub fn dump(&self) -> Vec<u8> {
+        std::iter::once(self.read(0)).chain(self.mem).collect()
+    }
+
+    /// Loads the specification register, followed by the palette memory.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `data` is not exactly 65 bytes.
+    pub fn load(&mut self, data: &[u8]) {
+        self.write(0, data[0]);
+        self.mem.copy_from_slice(&data[1..]);
+    }
+}
+
+impl Block for Cram {
+    fn reset(&mut self) {
+        std::mem::take(self);
+    }
+}
+
+impl Default for Cram {
+    fn default() -> Self {
+        Self {
+            idx: Default::default(),
+            inc: Default::default(),
+            mem: [0; 0x40],
+        }
+    }
+}
+
+impl Device for Cram {
+    fn contains(&self, index: usize) -> bool {
+        (0..self.len()).contains(&index)
+    }
+
+    fn len(&self) -> usize {
+        2
+    }
+
+    fn read(&self, index: usize) -> u8 {
+        match index {
+            // NOTE: Bit 6 is unused, and always reads as set
+            0 => ((self.inc as u8) << 7) | 0x40 | self.idx,
+            _ => self.mem[self.idx as usize],
+        }
+    }
+
+    fn write(&mut self, index: usize, value: u8) {
+        match index {
+            0 => {
+                self.idx = value & 0x3f;
+                self.inc = value & 0x80 != 0;
+            }
+            _ => {
+                self.mem[self.idx as usize] = value;
+                if self.inc {
+                    self.idx = (self.idx + 1) & 0x3f;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn auto_increment_works() {
+        let mut cram = Cram::default();
+
+        // Enable auto-increment from the first byte
+        cram.write(0, 0x80);
+        assert_eq!(cram.read(0), 0xc0);
+        // Fill every palette through the data register
+        (0..0x40).for_each(|byte| cram.write(1, byte));
+        assert_eq!(cram.mem, std::array::from_fn(|idx| idx as u8));
+        // Index wraps around after the final byte
+        assert_eq!(cram.read(0), 0xc0);
+        assert_eq!(cram.color(0, 0), 0x0100);
+        assert_eq!(cram.color(7, 3), 0x3f3e);
+    }
+
+    #[test]
+    fn no_increment_works() {
+        let mut cram = Cram::default();
+
+        // Select the final color without auto-increment
+        cram.write(0, 0x3e);
+        cram.write(1, 0xff);
+        cram.write(1, 0x7f);
+        assert_eq!(cram.read(0), 0x7e);
+        assert_eq!(cram.read(1), 0x7f);
+        assert_eq!(cram.color(7, 3), 0x007f);
+    }
+}
@@ -11,6 +11,7 @@ use remus::{Block, Device, Machine};
 pub struct Dma {
     page: u8,
     idx: Option<u8>,
+    data: u8,
     bus: Rc<RefCell<Bus>>,
     oam: Rc<RefCell<Ram<0x00a0>>>,
 }
@@ -23,6 +24,12 @@ impl Dma {
     pub fn set_oam(&mut self, oam: Rc<RefCell<Ram<0x00a0>>>) {
         self.oam = oam;
     }
+
+    /// Gets the byte on the bus while a transfer is in progress.
+    #[must_use]
+    pub fn transfer(&self) -> Option<u8> {
+        self.enabled().then_some(self.data)
+    }
 }
 
 impl Block for Dma {
@@ -37,7 +44,7 @@ impl Device for Dma {
     }
 
     fn len(&self) -> usize {
-        std::mem::size_of::<Self>()
+        1
     }
 
     fn read(&self, _: usize) -> u8 {
@@ -65,6 +72,7 @@ impl Machine for Dma {
         trace!("Transferring OAM({idx:#04x}) <- *{addr:#06x} = {data:#04x}");
         // Write this byte
         self.oam.borrow_mut().write(*idx as usize, data);
+        self.data = data;
         // Increment the address
         self.idx = match *idx + 1 {
             160 => None,
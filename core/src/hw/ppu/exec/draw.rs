@@ -1,8 +1,11 @@
 use std::fmt::Display;
 
+use remus::Device;
+
 use super::blk::Pipeline;
+use super::pixel::{Color, Pixel};
 use super::sprite::Sprite;
-use super::{Mode, Ppu, Scan, SCREEN};
+use super::{Layer, Lcdc, Mode, Ppu, Scan, SCREEN};
 
 #[derive(Debug, Default)]
 pub struct Draw {
@@ -13,8 +16,10 @@ pub struct Draw {
 impl Draw {
     pub fn setup(&mut self, ppu: &mut Ppu) {
         // Set up the pipeline
+        // NOTE: Coarse scrolling is handled by the fetcher, so only the fine
+        //       scroll (within the first tile) is discarded.
         let scx = **ppu.ctl.borrow().scx.borrow();
-        self.pixels.set_discard(scx);
+        self.pixels.set_discard(scx % 8);
     }
 
     pub fn exec(mut self, ppu: &mut Ppu) -> Mode {
@@ -24,15 +29,43 @@ impl Draw {
         // If we have a pixel to draw, draw it
         let xpos = self.pixels.xpos() as usize;
         if let Some(pixel) = self.pixels.shift(ppu) {
-            // Calculate pixel index on screen
-            let ypos = **ppu.ctl.borrow().ly.borrow() as usize;
-            let idx = (ypos * SCREEN.width) + xpos;
+            // Retrieve the current scanline
+            let ypos = **ppu.ctl.borrow().ly.borrow();
+
+            // Mask out the background or window if disabled or hidden
+            //
+            // NOTE: This happens before mixing in sprites, such that sprites
+            //       with background priority are drawn over a disabled
+            //       background.
+            let layer = if self.pixels.was_at_win() {
+                Layer::Window
+            } else {
+                Layer::Background
+            };
+            let lcdc = **ppu.ctl.borrow().lcdc.borrow();
+            let pixel = if Lcdc::BgWinEnable.get(&lcdc) && ppu.layer_visible(layer) {
+                pixel
+            } else {
+                Pixel {
+                    col: Color::C0,
+                    ..pixel
+                }
+            };
+
+            // Mix in any sprite covering this pixel
+            let sprite = self
+                .sprite(ppu, xpos as u8)
+                .filter(|_| ppu.layer_visible(Layer::Objects));
+            let pixel = match sprite {
+                Some(sprite) => Pixel::blend(pixel, sprite),
+                None => pixel,
+            };
 
             // Determine this pixel's color (according to its palette)
             let color = ppu.color(pixel);
 
-            // Write the pixel into the framebuffer
-            ppu.lcd[idx] = color;
+            // Output the pixel
+            ppu.output(xpos as u8, ypos, color);
         }
 
         // Retrieve updated x-position
@@ -49,9 +82,65 @@ impl Draw {
             if self.pixels.was_at_win() {
                 ppu.winln += 1;
             }
+            // Notify of the completed scanline
+            let ly = **ppu.ctl.borrow().ly.borrow();
+            if let Some(hook) = ppu.hblank.get() {
+                hook(ly);
+            }
+            if let Some(hook) = ppu.scanline.get() {
+                let start = ly as usize * SCREEN.width;
+                let line = ppu.lcd[start..start + SCREEN.width].try_into().unwrap();
+                hook(ly, line);
+            }
             Mode::HBlank(self.into())
         }
     }
+
+    /// Finds the highest priority opaque sprite pixel at the provided
+    /// x-position on the current scanline.
+    fn sprite(&self, ppu: &Ppu, xpos: u8) -> Option<Pixel> {
+        // Most scanlines have no sprites, so skip any register access
+        if self.objs.is_empty() {
+            return None;
+        }
+        let regs = ppu.ctl.borrow();
+        let lcdc = **regs.lcdc.borrow();
+        // NOTE: Sprites may be disabled mid-scanline, after they were scanned.
+        if !Lcdc::ObjEnable.get(&lcdc) {
+            return None;
+        }
+        let ht = [8, 16][Lcdc::ObjSize.get(&lcdc) as usize];
+        let ly = **regs.ly.borrow();
+        let vram = ppu.vram.borrow();
+
+        // NOTE: As sprites are ordered by priority, the first opaque pixel
+        //       found is the one drawn.
+        self.objs.iter().find_map(|obj| {
+            // Determine the sprite's column at this position
+            let col = (xpos + 8).checked_sub(obj.xpos).filter(|&col| col < 8)?;
+            let col = if obj.xflip { 7 - col } else { col };
+            // Determine the sprite's row on this scanline
+            // NOTE: Wrapping guards against the sprite size changing after
+            //       the sprite was scanned.
+            let row = (ly + 16 - obj.ypos) % ht;
+            let row = if obj.yflip { ht - 1 - row } else { row };
+            // Read the sprite's tile data
+            // NOTE: In 8x16 mode, the tile index's lowest bit is ignored.
+            let tidx = if ht == 16 { obj.idx & 0xfe } else { obj.idx };
+            let addr = 16 * tidx as usize + 2 * row as usize;
+            let bytes = [vram.read(addr), vram.read(addr + 1)];
+            // Extract the pixel's color
+            let bit = 7 - col;
+            let col = (((bytes[1] >> bit) & 0b1) << 1) | ((bytes[0] >> bit) & 0b1);
+            let col = Color::try_from(col).unwrap();
+            // Skip transparent pixels
+            (col != Color::C0).then_some(Pixel {
+                col,
+                pal: obj.palette,
+                bgp: obj.priority,
+            })
+        })
+    }
 }
 
 impl Display for Draw {
@@ -66,7 +155,12 @@ impl Display for Draw {
 }
 
 impl From<Scan> for Draw {
-    fn from(Scan { objs, .. }: Scan) -> Self {
+    fn from(Scan { mut objs, .. }: Scan) -> Self {
+        // Order sprites by priority
+        // NOTE: On the DMG, the sprite with the smaller x-position is drawn
+        //       on top. Ties are broken by OAM order, which is preserved by
+        //       the stable sort.
+        objs.sort_by_key(|obj| obj.xpos);
         Self {
             objs,
             ..Default::default()
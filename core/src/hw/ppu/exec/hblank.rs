@@ -20,15 +20,16 @@ impl HBlank {
             *ly += 1;
             ppu.dot = 0;
 
-            // Schedule VBlank interrupt
-            ppu.pic.borrow_mut().req(Interrupt::VBlank);
-
             // Either begin next scanline, or enter VBlank
             if *ly < SCREEN.height as u8 {
                 Mode::Scan(self.into())
             } else {
+                // Schedule VBlank interrupt
+                ppu.pic.borrow_mut().req(Interrupt::VBlank);
                 // Reset internal window line counter
                 ppu.winln = 0;
+                // Count the completed frame
+                ppu.frames += 1;
                 Mode::VBlank(Default::default())
             }
         }
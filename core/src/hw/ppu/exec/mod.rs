@@ -6,7 +6,7 @@ use self::draw::Draw;
 use self::hblank::HBlank;
 use self::scan::Scan;
 use self::vblank::VBlank;
-use super::{blk, sprite, Interrupt, Lcdc, Ppu, SCREEN};
+use super::{blk, pixel, sprite, Interrupt, Layer, Lcdc, Ppu, SCREEN};
 
 mod draw;
 mod hblank;
@@ -30,24 +30,27 @@ impl Mode {
             let stat = &mut **regs.stat.borrow_mut();
             let ly = **regs.ly.borrow();
             let lyc = **regs.lyc.borrow();
+            let prev = *stat & 0x04 != 0;
             *stat ^= (*stat & 0x03) ^ u8::from(&self);
             *stat ^= (*stat & 0x04) ^ ((ly == lyc) as u8) << 2;
 
             // Trigger interrupts
+            let mut int = 0;
+            // LYC=LY
+            // NOTE: The comparison is re-evaluated every dot, so a write to
+            //       LYC will trigger an interrupt immediately.
+            int |= ((ly == lyc && !prev) as u8) << 6;
             if ppu.dot == 0 {
-                let mut int = 0;
-                // LYC=LY
-                int |= ((lyc == ly) as u8) << 6;
                 // Mode 2
                 int |= (matches!(self, Mode::Scan(_)) as u8) << 5;
                 // Mode 1
                 int |= (matches!(self, Mode::VBlank(_)) as u8) << 4;
                 // Mode 0
                 int |= (matches!(self, Mode::HBlank(_)) as u8) << 3;
-                // Check for interrupts
-                if int & (*stat & 0x78) != 0 {
-                    ppu.pic.borrow_mut().req(Interrupt::LcdStat);
-                }
+            }
+            // Check for interrupts
+            if int & (*stat & 0x78) != 0 {
+                ppu.pic.borrow_mut().req(Interrupt::LcdStat);
             }
         }
 
@@ -20,37 +20,30 @@ impl Scan {
         let regs = ppu.ctl.borrow();
         let lcdc = **regs.lcdc.borrow();
         let size = Lcdc::ObjSize.get(&lcdc);
-        let ht = [8, 16][size as usize];
+        let ht: u16 = [8, 16][size as usize];
         let ly = **regs.ly.borrow();
 
         // Scan should only run when the following conditions are met:
         // - Sprites are enabled
-        // - It is currently an "on" dot (as Scan runs at 2 MiHz)
+        // - It is currently an "on" dot (as each OAM entry takes 2 dots)
         // - Fewer than 10 sprites have been found per scanline
         if Lcdc::ObjEnable.get(&lcdc) && ppu.dot % 2 == 0 && self.objs.len() < 10 {
-            // Checking an OAM entry takes 4 dots, due to the read. This means
-            // we need another clock divider to disable during the "off" dot.
-            if ppu.dot % 4 == 0 {
-                // Scan the current OAM entry
-                let mut obj = [0; 4];
-                obj.iter_mut().for_each(|byte| {
-                    *byte = ppu.oam.borrow().read(self.idx);
-                    self.idx += 1;
-                });
-                // Parse entry into Sprite
-                let obj = Sprite::from(obj);
-                // Add sprite to be rendered if it's on the current scanline
-                if obj.xpos != 0 && (obj.ypos..=obj.ypos + ht).contains(&(ly + 16)) {
-                    self.objs.push(obj);
-                }
+            // Scan the current OAM entry
+            let oam = ppu.oam.borrow();
+            let obj: [u8; 4] = std::array::from_fn(|byte| oam.read(4 * self.idx + byte));
+            // Parse entry into Sprite
+            let obj = Sprite::from(obj);
+            // Add sprite to be rendered if it's on the current scanline
+            // NOTE: Sprites are found irrespective of their x-position, such
+            //       that offscreen sprites still count towards the limit.
+            let top = obj.ypos as u16;
+            if (top..top + ht).contains(&(ly as u16 + 16)) {
+                self.objs.push(obj);
             }
-        } else {
-            // Regardless, move to next OAM entry
-            // NOTE: We're incrementing by 2 here, since the PPU has a 16-bit
-            //       wide bus to the OAM, allowing it to access one word (2
-            //       bytes) per dot.
-            // TODO: citation needed
-            self.idx += 2;
+        }
+        // Move to the next OAM entry every 2 dots
+        if ppu.dot % 2 == 1 {
+            self.idx += 1;
         }
         drop(regs);
 
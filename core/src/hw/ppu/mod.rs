@@ -1,36 +1,51 @@
 //! Picture processing unit.
 
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::rc::Rc;
 
+use remus::bus::adapt::Bank;
 use remus::bus::Bus;
 use remus::mem::Ram;
 use remus::reg::Register;
 use remus::{Block, Device, Machine};
 
 use self::dma::Dma;
-use self::exec::Mode;
-use self::pixel::{Color, Palette, Pixel};
+use self::pixel::Pixel;
 use super::pic::{Interrupt, Pic};
+use crate::dev::Masked;
 use crate::dmg::SCREEN;
+use crate::emu::hook::Hook;
 
 mod blk;
+mod cram;
 mod dma;
 mod exec;
 mod pixel;
 mod screen;
 mod sprite;
 
-pub use self::screen::Screen;
+pub use self::cram::Cram;
+pub use self::pixel::{Color, Palette};
+pub use self::screen::{FrameFormat, Renderer, Screen, Shades};
+pub use self::sprite::Sprite as SpriteEntry;
 
 /// PPU model.
 #[rustfmt::skip]
 #[derive(Debug, Default)]
 pub struct Ppu {
     lcd: Screen,
+    fmt: FrameFormat,
+    rgba: Vec<u8>,
+    shades: Shades,
+    render: Hook<dyn Renderer>,
+    hblank: Hook<dyn FnMut(u8)>,
+    scanline: Hook<dyn FnMut(u8, &[Color; SCREEN.width])>,
     dot: usize,
+    frames: usize,
     winln: u8,
-    mode: Mode,
+    mode: exec::Mode,
+    oam_bug: bool,
+    hidden: [bool; 3],
     bus: Rc<RefCell<Bus>>,
     pic: Rc<RefCell<Pic>>,
     // ┌────────┬──────────────────┬─────┬───────┐
@@ -39,10 +54,19 @@ pub struct Ppu {
     // │ 8 Ki B │            Video │ RAM │ VRAM  │
     // │  160 B │ Object Attribute │ RAM │ OAM   │
     // │   12 B │      LCD Control │ Reg │       │
+    // │ 8 Ki B │   Video (Bank 1) │ RAM │ VRAM1 │
+    // │    1 B │        VRAM Bank │ Reg │ VBK   │
+    // │   64 B │       BG Palette │ RAM │ BCP   │
+    // │   64 B │      OBJ Palette │ RAM │ OCP   │
     // └────────┴──────────────────┴─────┴───────┘
     pub vram: Rc<RefCell<Ram<0x2000>>>,
     pub oam:  Rc<RefCell<Ram<0x00a0>>>,
     pub ctl: Rc<RefCell<Registers>>,
+    // NOTE: VRAM banking and color palettes are only mapped on the CGB
+    pub vram1: Rc<RefCell<Ram<0x2000>>>,
+    pub vbk:  Rc<RefCell<Vbk>>,
+    pub bcp:  Rc<RefCell<Cram>>,
+    pub ocp:  Rc<RefCell<Cram>>,
 }
 
 impl Ppu {
@@ -56,12 +80,275 @@ impl Ppu {
         self.pic = pic;
     }
 
+    /// Set a custom renderer to receive pixels as they are drawn.
+    ///
+    /// Pixels continue to be written into the internal framebuffer.
+    pub fn set_renderer(&mut self, render: Box<dyn Renderer>) {
+        self.render = render.into();
+    }
+
+    /// Sets a callback to be invoked at the start of each HBlank.
+    ///
+    /// The callback receives the scanline (`LY`) just drawn, allowing
+    /// mid-frame (raster) effects to be observed.
+    pub fn on_hblank(&mut self, hook: Box<dyn FnMut(u8)>) {
+        self.hblank = hook.into();
+    }
+
+    /// Enables or disables emulation of the DMG's OAM corruption bug.
+    ///
+    /// When enabled, CPU accesses to `0xfe00..=0xfeff` during the OAM scan
+    /// corrupt the row of OAM currently being read by the PPU. This includes
+    /// addresses incremented or decremented by the CPU (e.g. `INC HL`, or
+    /// `PUSH` with SP in that range). This is off by default.
+    pub fn set_oam_bug(&mut self, on: bool) {
+        self.oam_bug = on;
+    }
+
+    /// Shows or hides a layer of the rendered output.
+    ///
+    /// Hidden layers are drawn as transparent, without affecting the PPU's
+    /// timing. All layers are visible by default.
+    pub fn set_layer_visible(&mut self, layer: Layer, on: bool) {
+        self.hidden[layer as usize] = !on;
+    }
+
+    /// Checks if a layer of the rendered output is visible.
+    #[must_use]
+    pub fn layer_visible(&self, layer: Layer) -> bool {
+        !self.hidden[layer as usize]
+    }
+
+    /// Sets a callback to be invoked at the end of each visible scanline.
+    ///
+    /// The callback receives the scanline (`LY`) just drawn, along with its
+    /// pixels.
+    pub fn on_scanline(&mut self, hook: Box<dyn FnMut(u8, &[Color; SCREEN.width])>) {
+        self.scanline = hook.into();
+    }
+
+    /// Sets the output color of each of the LCD's shades.
+    pub(crate) fn set_shades(&mut self, shades: Shades) {
+        self.shades = shades;
+    }
+
+    /// Sets the format of the output framebuffer.
+    ///
+    /// When [`FrameFormat::Rgba8888`] is selected, pixels are additionally
+    /// converted to RGBA as they are drawn.
+    pub fn set_frame_format(&mut self, fmt: FrameFormat) {
+        self.fmt = fmt;
+        self.rgba = match fmt {
+            FrameFormat::Indexed => Vec::new(),
+            FrameFormat::Rgba8888 => self
+                .lcd
+                .iter()
+                .flat_map(|&color| self.shades.rgba(color))
+                .collect(),
+        };
+    }
+
+    /// Gets the PPU's current mode.
+    #[must_use]
+    pub fn mode(&self) -> Mode {
+        match self.mode {
+            exec::Mode::Scan(_) => Mode::Scan,
+            exec::Mode::Draw(_) => Mode::Draw,
+            exec::Mode::HBlank(_) => Mode::HBlank,
+            exec::Mode::VBlank(_) => Mode::VBlank,
+        }
+    }
+
+    /// Gets the PPU's internal state.
+    #[must_use]
+    pub fn state(&self) -> PpuState {
+        PpuState {
+            mode: self.mode(),
+            dot: self.dot as u16,
+            winln: self.winln,
+        }
+    }
+
+    /// Sets the PPU's internal state.
+    ///
+    /// NOTE: As the pixel pipeline's state is not preserved, the OAM scan and
+    ///       drawing are replayed from the start of the current scanline (at
+    ///       the current `LY`), without producing any output or interrupts.
+    pub fn set_state(&mut self, state: PpuState) {
+        let dot = state.dot as usize;
+        match state.mode {
+            Mode::HBlank => self.mode = exec::Mode::HBlank(Default::default()),
+            Mode::VBlank => self.mode = exec::Mode::VBlank(Default::default()),
+            Mode::Scan | Mode::Draw => {
+                // Mute output while replaying
+                let render = std::mem::take(&mut self.render);
+                let hblank = std::mem::take(&mut self.hblank);
+                let scanline = std::mem::take(&mut self.scanline);
+                let active = **self.pic.borrow().active.borrow();
+                // Replay the scanline up to the dot
+                self.dot = 0;
+                self.mode = exec::Mode::default();
+                while self.dot < dot && self.mode() != Mode::HBlank {
+                    self.mode = std::mem::take(&mut self.mode).exec(self);
+                }
+                // Restore output
+                self.render = render;
+                self.hblank = hblank;
+                self.scanline = scanline;
+                **self.pic.borrow().active.borrow_mut() = active;
+            }
+        }
+        self.dot = dot;
+        self.winln = state.winln;
+    }
+
+    /// Overrides the mode and LYC=LY bits reported by `STAT`.
+    ///
+    /// These are otherwise maintained by the PPU, which updates them again on
+    /// its next dot.
+    pub fn set_stat(&mut self, mode: Mode, lyc: bool) {
+        let regs = self.ctl.borrow();
+        let stat = &mut **regs.stat.borrow_mut();
+        *stat = (*stat & !0x07) | (lyc as u8) << 2 | mode as u8;
+    }
+
+    /// Gets the current scanline (`LY`).
+    #[must_use]
+    pub fn line(&self) -> u8 {
+        **self.ctl.borrow().ly.borrow()
+    }
+
+    /// Moves the PPU to the start of the provided scanline.
+    ///
+    /// Useful for tests, which would otherwise need to cycle from the top of
+    /// the frame.
+    #[cfg(any(test, debug_assertions))]
+    pub fn set_ly(&mut self, ly: u8) {
+        **self.ctl.borrow().ly.borrow_mut() = ly;
+        self.dot = 0;
+        self.mode = if (ly as usize) < SCREEN.height {
+            exec::Mode::Scan(Default::default())
+        } else {
+            exec::Mode::VBlank(Default::default())
+        };
+    }
+
+    /// Gets the number of frames completed since reset.
+    #[must_use]
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+
+    /// Gets the current dot within the scanline.
+    #[must_use]
+    pub fn dot(&self) -> u16 {
+        self.dot as u16
+    }
+
+    /// Cycles the OAM DMA.
+    ///
+    /// Unlike the rest of the PPU, the DMA continues while the LCD is off.
+    pub fn cycle_dma(&mut self) {
+        let ctl = self.ctl.borrow();
+        let mut dma = ctl.dma.borrow_mut();
+        if dma.enabled() {
+            dma.cycle();
+        }
+    }
+
     /// Get a reference to the ppu's screen.
     #[must_use]
     pub fn screen(&self) -> &Screen {
         &self.lcd
     }
 
+    /// Converts the screen's pixels to output colors.
+    #[must_use]
+    pub fn frame_rgba(&self) -> Vec<u32> {
+        self.lcd
+            .iter()
+            .map(|&color| self.shades.get(color))
+            .collect()
+    }
+
+    /// Gets the screen's pixels as RGBA bytes.
+    ///
+    /// Returns `None` unless the [`FrameFormat::Rgba8888`] format is selected.
+    #[must_use]
+    pub fn frame_rgba8888(&self) -> Option<&[u8]> {
+        match self.fmt {
+            FrameFormat::Indexed => None,
+            FrameFormat::Rgba8888 => Some(&self.rgba),
+        }
+    }
+
+    /// Computes a stable hash of the screen's current contents.
+    ///
+    /// Uses the 64-bit [FNV-1a] hash over each pixel's color value, such that
+    /// the result is independent of the host platform.
+    ///
+    /// [FNV-1a]: https://en.wikipedia.org/wiki/Fowler%E2%80%93Noll%E2%80%93Vo_hash_function
+    #[must_use]
+    pub fn frame_hash(&self) -> u64 {
+        const BASIS: u64 = 0xcbf29ce484222325;
+        const PRIME: u64 = 0x00000100000001b3;
+        self.lcd.iter().fold(BASIS, |hash, &color| {
+            (hash ^ color as u64).wrapping_mul(PRIME)
+        })
+    }
+
+    /// Decodes each of the 40 sprites in OAM.
+    #[must_use]
+    pub fn sprites(&self) -> [SpriteEntry; 40] {
+        let oam = self.oam.borrow();
+        std::array::from_fn(|idx| {
+            SpriteEntry::from(std::array::from_fn(|byte| oam.read(4 * idx + byte)))
+        })
+    }
+
+    /// Renders the full 256x256 background map, regardless of scrolling.
+    #[must_use]
+    pub fn render_background(&self) -> [Color; 0x10000] {
+        let lcdc = **self.ctl.borrow().lcdc.borrow();
+        self.render_map(Lcdc::BgMap.get(&lcdc))
+    }
+
+    /// Renders the full 256x256 window map, regardless of its position.
+    #[must_use]
+    pub fn render_window(&self) -> [Color; 0x10000] {
+        let lcdc = **self.ctl.borrow().lcdc.borrow();
+        self.render_map(Lcdc::WinMap.get(&lcdc))
+    }
+
+    /// Renders a full tile map using the current tile data and palette.
+    fn render_map(&self, map: bool) -> [Color; 0x10000] {
+        // Extract rendering info
+        let regs = self.ctl.borrow();
+        let lcdc = **regs.lcdc.borrow();
+        let bgp = **regs.bgp.borrow();
+        let vram = self.vram.borrow();
+        let base = [0x1800, 0x1c00][map as usize];
+
+        std::array::from_fn(|idx| {
+            let (x, y) = (idx % 256, idx / 256);
+            // Look up the tile number
+            let tnum = vram.read(base + (32 * (y / 8)) + (x / 8));
+            // Calculate the tile data address
+            let yoff = 2 * (y % 8);
+            let addr = if Lcdc::BgWinData.get(&lcdc) {
+                (16 * tnum as usize) + yoff
+            } else {
+                (0x1000 + (16 * tnum as i8 as isize)) as usize + yoff
+            };
+            // Extract the pixel's color
+            let mask = 0x80 >> (x % 8);
+            let bit0 = vram.read(addr) & mask != 0;
+            let bit1 = vram.read(addr + 1) & mask != 0;
+            let col = ((bit1 as u8) << 1) | (bit0 as u8);
+            Color::try_from(col).unwrap().recolor(bgp)
+        })
+    }
+
     /// Check if the screen is ready to be redrawn.
     #[must_use]
     pub fn ready(&self) -> bool {
@@ -76,10 +363,62 @@ impl Ppu {
         enabled && topline && firstdot
     }
 
+    /// Output a pixel to the framebuffer (and any custom renderer).
+    fn output(&mut self, x: u8, y: u8, color: Color) {
+        // Write the pixel into the framebuffer
+        self.lcd.put_pixel(x, y, color);
+        // Convert the pixel to RGBA
+        if let FrameFormat::Rgba8888 = self.fmt {
+            let idx = 4 * ((y as usize * SCREEN.width) + x as usize);
+            self.rgba[idx..idx + 4].copy_from_slice(&self.shades.rgba(color));
+        }
+        // Forward the pixel to any custom renderer
+        if let Some(render) = self.render.get() {
+            render.put_pixel(x, y, color);
+        }
+    }
+
+    /// Corrupt OAM as a result of a CPU access during the OAM scan.
+    ///
+    /// OAM is accessed as 20 rows of four 16-bit words. The row currently
+    /// being scanned is corrupted with a pattern derived from itself and the
+    /// preceding row, with the first row never affected.
+    fn corrupt_oam(&mut self, access: OamAccess) {
+        if !matches!(self.mode, exec::Mode::Scan(_)) {
+            return;
+        }
+        // Determine the row being scanned
+        let row = self.dot / 4;
+        if row == 0 {
+            return;
+        }
+        // Read the current and preceding rows
+        let mut oam = self.oam.borrow_mut();
+        let word = |idx: usize| u16::from_le_bytes([oam.read(2 * idx), oam.read(2 * idx + 1)]);
+        let (a, b, c) = (word(4 * row), word(4 * (row - 1)), word(4 * (row - 1) + 2));
+        // Corrupt the first word of the row
+        let first = match access {
+            OamAccess::Read => b | (a & c),
+            OamAccess::Write => ((a ^ c) & (b ^ c)) ^ c,
+        };
+        let [lo, hi] = first.to_le_bytes();
+        oam.write(8 * row, lo);
+        oam.write(8 * row + 1, hi);
+        // Copy the rest of the preceding row
+        for idx in 2..8 {
+            let byte = oam.read(8 * (row - 1) + idx);
+            oam.write(8 * row + idx, byte);
+        }
+    }
+
     /// Color a pixel according to the ppu's palette configuration.
     fn color(&self, pixel: Pixel) -> Color {
         let regs = self.ctl.borrow();
+        let lcdc = **regs.lcdc.borrow();
         let pal = **match pixel.pal() {
+            // NOTE: On the DMG, a disabled background is blank (white),
+            //       irrespective of the palette.
+            Palette::BgWin if !Lcdc::BgWinEnable.get(&lcdc) => return Color::C0,
             Palette::BgWin => regs.bgp.borrow(),
             Palette::Obj0 => regs.obp0.borrow(),
             Palette::Obj1 => regs.obp1.borrow(),
@@ -92,13 +431,27 @@ impl Block for Ppu {
     fn reset(&mut self) {
         // Reset LCD
         self.lcd = Default::default();
+        self.set_frame_format(self.fmt);
 
         // Reset mode
         self.mode = Default::default();
+        self.frames = Default::default();
 
         // Reset memory
         self.vram.borrow_mut().reset();
         self.oam.borrow_mut().reset();
+        self.vram1.borrow_mut().reset();
+        self.bcp.borrow_mut().reset();
+        self.ocp.borrow_mut().reset();
+
+        // Reset VRAM bank
+        let mut vbk = self.vbk.borrow_mut();
+        vbk.reset();
+        let mut bank = vbk.bank.borrow_mut();
+        bank.reset();
+        bank.add(self.vram.clone());
+        bank.add(self.vram1.clone());
+        bank.set(0);
 
         // Reset registers
         self.ctl.borrow_mut().reset();
@@ -115,22 +468,68 @@ impl Machine for Ppu {
     }
 
     fn cycle(&mut self) {
-        self.mode = std::mem::take(&mut self.mode).exec(self);
-
-        // Cycle the DMA every machine cycle
-        let ctl = self.ctl.borrow();
-        let mut dma = ctl.dma.borrow_mut();
-        if dma.enabled() && self.dot % 4 == 0 {
-            dma.cycle();
+        // Handle any OAM access from the CPU
+        let access = self.ctl.borrow().oam.take();
+        if let Some(access) = access.filter(|_| self.oam_bug) {
+            self.corrupt_oam(access);
         }
+
+        self.mode = std::mem::take(&mut self.mode).exec(self);
     }
 }
 
+/// Rendered layers.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Layer {
+    Background,
+    Window,
+    Objects,
+}
+
+impl Layer {
+    /// Each of the rendered layers.
+    pub const ALL: [Self; 3] = [Self::Background, Self::Window, Self::Objects];
+}
+
+/// CPU accesses to OAM.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum OamAccess {
+    Read,
+    Write,
+}
+
+/// PPU modes.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum Mode {
+    /// Mode 0: Horizontal blank.
+    HBlank = 0b00,
+    /// Mode 1: Vertical blank.
+    VBlank = 0b01,
+    /// Mode 2: OAM scan.
+    Scan = 0b10,
+    /// Mode 3: Drawing pixels.
+    Draw = 0b11,
+}
+
+/// PPU internal state.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct PpuState {
+    /// Current mode.
+    pub mode: Mode,
+    /// Current dot within the scanline.
+    pub dot: u16,
+    /// Internal window line counter.
+    pub winln: u8,
+}
+
 /// Control registers.
 #[rustfmt::skip]
 #[derive(Debug, Default)]
 pub struct Registers {
     bus: Bus,
+    oam: Cell<Option<OamAccess>>,
     // ┌──────┬────────────────────┬─────┬───────┐
     // │ SIZE │        NAME        │ DEV │ ALIAS │
     // ├──────┼────────────────────┼─────┼───────┤
@@ -161,16 +560,31 @@ pub struct Registers {
     wx:   Rc<RefCell<Register<u8>>>,
 }
 
+impl Registers {
+    /// Gets the byte on the bus while an OAM DMA is in progress.
+    #[must_use]
+    pub fn dma_transfer(&self) -> Option<u8> {
+        self.dma.borrow().transfer()
+    }
+
+    /// Records a CPU access to OAM, for use by the OAM corruption bug.
+    pub fn oam_access(&self, access: OamAccess) {
+        self.oam.set(Some(access));
+    }
+}
+
 impl Block for Registers {
     #[rustfmt::skip]
     fn reset(&mut self) {
         // Reset self
         std::mem::take(self);
+        // NOTE: STAT bits 0-2 are maintained by the PPU, and bit 7 is unused
+        let stat = Masked::with(self.stat.clone(), 0x80, 0x78).to_shared();
         // Reset bus                           // ┌──────┬──────────────────┬─────┐
         self.bus.reset();                      // │ SIZE │      NAME        │ DEV │
                                                // ├──────┼──────────────────┼─────┤
         self.bus.map(0x00, self.lcdc.clone()); // │  1 B │      LCD Control │ Reg │
-        self.bus.map(0x01, self.stat.clone()); // │  1 B │       LCD Status │ Reg │
+        self.bus.map(0x01, stat);              // │  1 B │       LCD Status │ Reg │
         self.bus.map(0x02, self.scy.clone());  // │  1 B │         Scroll Y │ Reg │
         self.bus.map(0x03, self.scx.clone());  // │  1 B │         Scroll X │ Reg │
         self.bus.map(0x04, self.ly.clone());   // │  1 B │ LCD Y Coordinate │ Reg │
@@ -203,6 +617,50 @@ impl Device for Registers {
     }
 }
 
+/// VRAM bank select register.
+///
+/// Selects which bank of VRAM is mapped for the CPU, with all but the lowest
+/// bit reading as set.
+#[derive(Debug, Default)]
+pub struct Vbk {
+    reg: Register<u8>,
+    bank: Rc<RefCell<Bank>>,
+}
+
+impl Vbk {
+    /// Gets the VRAM bank selected by this register.
+    #[must_use]
+    pub fn vram(&self) -> Rc<RefCell<Bank>> {
+        self.bank.clone()
+    }
+}
+
+impl Block for Vbk {
+    fn reset(&mut self) {
+        // NOTE: Banks are added (and selected) by the PPU on reset.
+        self.reg.reset();
+    }
+}
+
+impl Device for Vbk {
+    fn contains(&self, index: usize) -> bool {
+        self.reg.contains(index)
+    }
+
+    fn len(&self) -> usize {
+        self.reg.len()
+    }
+
+    fn read(&self, _: usize) -> u8 {
+        0xfe | *self.reg
+    }
+
+    fn write(&mut self, _: usize, value: u8) {
+        *self.reg = value & 0x01;
+        self.bank.borrow_mut().set(*self.reg as usize);
+    }
+}
+
 #[rustfmt::skip]
 #[derive(Copy, Clone, Debug)]
 enum Lcdc {
@@ -221,3 +679,469 @@ impl Lcdc {
         *lcdc & self as u8 != 0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup() -> Ppu {
+        let mut ppu = Ppu::default();
+        ppu.reset();
+        // Enable the LCD
+        ppu.ctl.borrow_mut().write(0x00, 0x80);
+        ppu
+    }
+
+    /// Draws the first scanline with sprites enabled and identity palettes.
+    ///
+    /// Each of `tiles` is filled with a solid color, and `objs` are placed in
+    /// OAM. The PPU may be further configured by `config` before drawing.
+    fn draw_line(
+        tiles: &[(usize, u8)],
+        objs: &[[u8; 4]],
+        config: impl FnOnce(&mut Ppu),
+    ) -> Vec<Color> {
+        let mut ppu = setup();
+        // Enable sprites with identity palettes
+        ppu.ctl.borrow_mut().write(0x00, 0x93);
+        ppu.ctl.borrow_mut().write(0x07, 0xe4);
+        ppu.ctl.borrow_mut().write(0x08, 0xe4);
+        // Fill tiles with their colors
+        let mut vram = ppu.vram.borrow_mut();
+        for &(tile, color) in tiles {
+            let planes = [0xff * (color & 1), 0xff * (color >> 1)];
+            (16 * tile..16 * (tile + 1)).for_each(|addr| vram.write(addr, planes[addr % 2]));
+        }
+        drop(vram);
+        // Place sprites
+        let mut oam = ppu.oam.borrow_mut();
+        for (idx, byte) in objs.iter().flatten().enumerate() {
+            oam.write(idx, *byte);
+        }
+        drop(oam);
+        config(&mut ppu);
+        // Draw the first scanline
+        (0..456).for_each(|_| ppu.cycle());
+        ppu.lcd[..SCREEN.width].to_vec()
+    }
+
+    /// Expected first scanline of a sprite at x-position 16 over a solid
+    /// background.
+    fn sprite_line(bg: Color, obj: Option<Color>) -> Vec<Color> {
+        (0..SCREEN.width)
+            .map(|x| match (x, obj) {
+                (8..=15, Some(obj)) => obj,
+                _ => bg,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn frame_hash_works() {
+        let mut ppu = setup();
+        let other = setup();
+
+        // Identical frames hash equal
+        assert_eq!(ppu.frame_hash(), other.frame_hash());
+        // Changing a single pixel changes the hash
+        ppu.lcd[SCREEN.depth() - 1] = Color::C3;
+        assert_ne!(ppu.frame_hash(), other.frame_hash());
+    }
+
+    #[test]
+    fn stat_write_masked() {
+        let mut ppu = setup();
+        // Enter Draw (mode 3) on a line where LY=LYC
+        (0..80 + 1).for_each(|_| ppu.cycle());
+        assert_eq!(ppu.ctl.borrow().read(0x01), 0x87);
+
+        // Writes leave the mode and coincidence bits intact
+        ppu.ctl.borrow_mut().write(0x01, 0x00);
+        assert_eq!(ppu.ctl.borrow().read(0x01), 0x87);
+        ppu.ctl.borrow_mut().write(0x01, 0xff);
+        assert_eq!(ppu.ctl.borrow().read(0x01), 0xff);
+        assert_eq!(**ppu.ctl.borrow().stat.borrow(), 0x7f);
+    }
+
+    #[test]
+    fn renderer_works() {
+        struct Record(Rc<RefCell<Vec<(u8, u8)>>>);
+
+        impl Renderer for Record {
+            fn put_pixel(&mut self, x: u8, y: u8, _: Color) {
+                self.0.borrow_mut().push((x, y));
+            }
+        }
+
+        let mut ppu = setup();
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        ppu.set_renderer(Box::new(Record(seen.clone())));
+
+        // Draw a complete frame
+        (0..154 * 456).for_each(|_| ppu.cycle());
+        // Pixels are received once each, in row-major order
+        let order: Vec<_> = (0..SCREEN.height as u8)
+            .flat_map(|y| (0..SCREEN.width as u8).map(move |x| (x, y)))
+            .collect();
+        assert_eq!(*seen.borrow(), order);
+    }
+
+    #[test]
+    fn on_hblank_works() {
+        let mut ppu = setup();
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let log = seen.clone();
+        ppu.on_hblank(Box::new(move |ly| log.borrow_mut().push(ly)));
+
+        // Draw a complete frame
+        (0..154 * 456).for_each(|_| ppu.cycle());
+        // Fires once per visible scanline
+        let lines: Vec<_> = (0..SCREEN.height as u8).collect();
+        assert_eq!(*seen.borrow(), lines);
+    }
+
+    #[test]
+    fn oam_bug_works() {
+        #[rustfmt::skip]
+        let rows = [
+            [0x12, 0x34, 0x56, 0x78, 0x00, 0x00, 0xde, 0xf0],
+            [0xff, 0x00, 0xff, 0x00, 0xff, 0x00, 0xff, 0x00],
+        ];
+        let run = |bug: bool, access: OamAccess| {
+            let mut ppu = setup();
+            ppu.set_oam_bug(bug);
+            let mut oam = ppu.oam.borrow_mut();
+            for (idx, byte) in rows.iter().flatten().enumerate() {
+                oam.write(8 + idx, *byte);
+            }
+            drop(oam);
+            // Access OAM while the PPU scans the third row
+            (0..8).for_each(|_| ppu.cycle());
+            ppu.ctl.borrow().oam_access(access);
+            ppu.cycle();
+            let oam = ppu.oam.borrow();
+            (0..0x18).map(|idx| oam.read(idx)).collect::<Vec<_>>()
+        };
+
+        // Writes corrupt the first word to `a & b` (where `c` is clear)
+        let oam = run(true, OamAccess::Write);
+        assert_eq!(oam[0x00..0x10], [[0; 8], rows[0]].concat());
+        assert_eq!(
+            oam[0x10..0x18],
+            [0x12, 0x00, 0x56, 0x78, 0x00, 0x00, 0xde, 0xf0]
+        );
+        // Reads corrupt the first word to `b` (where `c` is clear)
+        let oam = run(true, OamAccess::Read);
+        assert_eq!(oam[0x10..0x18], rows[0]);
+        // OAM is untouched when disabled
+        let oam = run(false, OamAccess::Write);
+        assert_eq!(oam[0x10..0x18], rows[1]);
+    }
+
+    #[test]
+    fn sprites_works() {
+        let ppu = setup();
+        let mut oam = ppu.oam.borrow_mut();
+        #[rustfmt::skip]
+        let objs = [
+            [16, 8, 0x01, 0x00],
+            [ 0, 0, 0x00, 0x00],
+            [80, 40, 0x2a, 0xf0],
+        ];
+        for (idx, obj) in objs.iter().flatten().enumerate() {
+            oam.write(idx, *obj);
+        }
+        drop(oam);
+
+        let sprites = ppu.sprites();
+        assert_eq!(
+            sprites[0],
+            SpriteEntry {
+                ypos: 16,
+                xpos: 8,
+                idx: 0x01,
+                priority: false,
+                yflip: false,
+                xflip: false,
+                palette: Palette::Obj0,
+            }
+        );
+        assert_eq!(
+            sprites[2],
+            SpriteEntry {
+                ypos: 80,
+                xpos: 40,
+                idx: 0x2a,
+                priority: true,
+                yflip: true,
+                xflip: true,
+                palette: Palette::Obj1,
+            }
+        );
+    }
+
+    #[test]
+    fn frame_format_works() {
+        let mut ppu = setup();
+        assert!(ppu.frame_rgba8888().is_none());
+
+        // Draw a blank frame as RGBA
+        ppu.set_shades(Shades([0x123456, 0xaaaaaa, 0x555555, 0x000000]));
+        ppu.set_frame_format(FrameFormat::Rgba8888);
+        (0..154 * 456).for_each(|_| ppu.cycle());
+        let rgba = ppu.frame_rgba8888().unwrap();
+        assert_eq!(rgba.len(), SCREEN.depth() * 4);
+        assert!(rgba
+            .chunks(4)
+            .all(|pixel| pixel == [0x12, 0x34, 0x56, 0xff]));
+    }
+
+    #[test]
+    fn render_background_works() {
+        let ppu = setup();
+        // Use tile data at 0x8800 with an identity palette
+        ppu.ctl.borrow_mut().write(0x00, 0x80);
+        ppu.ctl.borrow_mut().write(0x07, 0xe4);
+        // Fill tile -1 with color 3, marking its top-left pixel with color 1
+        let mut vram = ppu.vram.borrow_mut();
+        (0x0ff0..0x1000).for_each(|addr| vram.write(addr, 0xff));
+        vram.write(0x0ff1, 0x7f);
+        // Place tile -1 at map coordinate (30, 20)
+        vram.write(0x1800 + (32 * 20) + 30, 0xff);
+        drop(vram);
+        // Scrolling has no effect
+        ppu.ctl.borrow_mut().write(0x02, 0x42);
+        ppu.ctl.borrow_mut().write(0x03, 0x24);
+
+        let bg = ppu.render_background();
+        assert!(bg.iter().enumerate().all(|(idx, &color)| {
+            let (x, y) = (idx % 256, idx / 256);
+            color
+                == match (x, y) {
+                    (240, 160) => Color::C1,
+                    (240..=247, 160..=167) => Color::C3,
+                    _ => Color::C0,
+                }
+        }));
+    }
+
+    #[test]
+    fn mode_sequence_works() {
+        let mut ppu = setup();
+
+        // Record the modes visited on each scanline
+        let mut seen = vec![Vec::new(); 154];
+        for _ in 0..154 * 456 {
+            let modes: &mut Vec<_> = &mut seen[ppu.line() as usize];
+            if modes.last() != Some(&ppu.mode()) {
+                modes.push(ppu.mode());
+            }
+            ppu.cycle();
+        }
+        // Visible scanlines progress through modes 2 -> 3 -> 0
+        for modes in &seen[..144] {
+            assert_eq!(modes, &[Mode::Scan, Mode::Draw, Mode::HBlank]);
+        }
+        // VBlank scanlines remain in mode 1
+        for modes in &seen[144..] {
+            assert_eq!(modes, &[Mode::VBlank]);
+        }
+        // Frame wraps around to the first scanline
+        assert_eq!((ppu.line(), ppu.dot()), (0, 0));
+    }
+
+    #[test]
+    fn set_ly_works() {
+        let mut ppu = setup();
+        ppu.set_ly(100);
+        assert_eq!((ppu.line(), ppu.dot(), ppu.mode()), (100, 0, Mode::Scan));
+
+        // Step a single scanline
+        let mut modes = Vec::new();
+        for _ in 0..456 {
+            if modes.last() != Some(&ppu.mode()) {
+                modes.push(ppu.mode());
+            }
+            ppu.cycle();
+        }
+        assert_eq!(modes, [Mode::Scan, Mode::Draw, Mode::HBlank]);
+        assert_eq!((ppu.line(), ppu.dot(), ppu.mode()), (101, 0, Mode::Scan));
+    }
+
+    #[test]
+    fn scx_discard_works() {
+        for scx in [3, 11] {
+            let mut ppu = setup();
+            // Use tile data at 0x8000 with an identity palette
+            ppu.ctl.borrow_mut().write(0x00, 0x91);
+            ppu.ctl.borrow_mut().write(0x07, 0xe4);
+            // Mark the leftmost pixel of tile 0, and fill tile 1
+            let mut vram = ppu.vram.borrow_mut();
+            (0x00..0x10).for_each(|addr| vram.write(addr, 0x80));
+            (0x10..0x20).for_each(|addr| vram.write(addr, 0xff));
+            // Place tile 1 in the second column of the background map
+            vram.write(0x1801, 0x01);
+            drop(vram);
+
+            // Draw the first scanline
+            ppu.ctl.borrow_mut().write(0x03, scx);
+            (0..456).for_each(|_| ppu.cycle());
+            // Only the fine scroll is discarded from the first tile
+            let line = &ppu.lcd[..SCREEN.width];
+            assert!(line.iter().enumerate().all(|(x, &color)| {
+                let x = x + scx as usize;
+                let dark = x / 8 == 1 || x % 8 == 0;
+                color == [Color::C0, Color::C3][dark as usize]
+            }));
+        }
+    }
+
+    #[test]
+    fn window_line_works() {
+        let mut ppu = setup();
+        // Use tile data at 0x8000 with an identity palette
+        ppu.ctl.borrow_mut().write(0x07, 0xe4);
+        // Fill tile 1 with color 3, and tile 2 with color 1
+        let mut vram = ppu.vram.borrow_mut();
+        (0x10..0x20).for_each(|addr| vram.write(addr, 0xff));
+        (0x20..0x30).for_each(|addr| vram.write(addr, [0xff, 0x00][addr % 2]));
+        // Place tile 1 in the window map's first 7 rows, and tile 2 below
+        (0x1c00..0x2000).for_each(|addr| vram.write(addr, 1 + (addr >= 0x1ce0) as u8));
+        drop(vram);
+        // Position the window at the top-left of the screen
+        ppu.ctl.borrow_mut().write(0x0a, 0);
+        ppu.ctl.borrow_mut().write(0x0b, 7);
+
+        // Draw a frame, only enabling the window for scanlines 50..100
+        for ly in 0..SCREEN.height {
+            let lcdc = if (50..100).contains(&ly) { 0xf1 } else { 0xd1 };
+            ppu.ctl.borrow_mut().write(0x00, lcdc);
+            (0..456).for_each(|_| ppu.cycle());
+        }
+        // The window draws its rows 0..50, rather than those at LY
+        assert!(ppu.lcd.iter().enumerate().all(|(idx, &color)| {
+            let ly = idx / SCREEN.width;
+            color == [Color::C0, Color::C3][(50..100).contains(&ly) as usize]
+        }));
+    }
+
+    #[test]
+    fn sprite_priority_works() {
+        // Place overlapping sprites using tile 1 (color 1) and tile 2 (color 2)
+        #[rustfmt::skip]
+        let objs = [
+            [16, 20, 2, 0], // covers 12..20
+            [16, 14, 1, 0], // covers  6..14
+            [16, 40, 1, 0], // covers 32..40
+            [16, 40, 2, 0], // covers 32..40
+        ];
+        let line = draw_line(&[(1, 1), (2, 2)], &objs, |_| {});
+
+        // Sprites with a smaller x-position are drawn on top, with ties broken
+        // by OAM order
+        assert!(line.iter().enumerate().all(|(x, &color)| {
+            color
+                == match x {
+                    6..=13 => Color::C1,
+                    14..=19 => Color::C2,
+                    32..=39 => Color::C1,
+                    _ => Color::C0,
+                }
+        }));
+    }
+
+    #[test]
+    fn sprite_bg_priority_works() {
+        // Place a sprite using tile 1 (color 1), straddling the first two
+        // background tiles
+        let draw = |attr: u8| {
+            draw_line(&[(1, 1), (2, 2)], &[[16, 12, 1, attr]], |ppu| {
+                // Use tile 2 (color 2) for only the first background tile
+                ppu.vram.borrow_mut().write(0x1800, 2);
+            })[..16]
+                .to_vec()
+        };
+        let line = |hidden: bool| {
+            (0..16)
+                .map(|x| match x {
+                    0..=3 => Color::C2,
+                    4..=7 if hidden => Color::C2,
+                    4..=11 => Color::C1,
+                    _ => Color::C0,
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // Without priority, the sprite is drawn over the background
+        assert_eq!(draw(0x00), line(false));
+        // With priority, it's hidden behind background colors 1-3
+        assert_eq!(draw(0x80), line(true));
+    }
+
+    #[test]
+    fn lcdc_enable_works() {
+        // Place a sprite using tile 1 (color 1) over an inverted background
+        let draw = |lcdc: u8, attr: u8| {
+            draw_line(&[(1, 1)], &[[16, 16, 1, attr]], |ppu| {
+                ppu.ctl.borrow_mut().write(0x00, lcdc);
+                ppu.ctl.borrow_mut().write(0x07, 0x1b);
+            })
+        };
+
+        // Both enabled
+        assert_eq!(draw(0x93, 0x00), sprite_line(Color::C3, Some(Color::C1)));
+        // Background disabled (blank)
+        assert_eq!(draw(0x92, 0x00), sprite_line(Color::C0, Some(Color::C1)));
+        // Sprites disabled
+        assert_eq!(draw(0x91, 0x00), sprite_line(Color::C3, None));
+        // Sprites with background priority are hidden by the background...
+        assert_eq!(draw(0x93, 0x80), sprite_line(Color::C3, None));
+        // ... unless it is disabled
+        assert_eq!(draw(0x92, 0x80), sprite_line(Color::C0, Some(Color::C1)));
+    }
+
+    #[test]
+    fn set_layer_visible_works() {
+        // Place a sprite using tile 1 (color 2) over tile 0 (color 1)
+        let draw = |hide: Option<Layer>| {
+            draw_line(&[(0, 1), (1, 2)], &[[16, 16, 1, 0]], |ppu| {
+                if let Some(layer) = hide {
+                    ppu.set_layer_visible(layer, false);
+                }
+            })
+        };
+
+        // All layers visible
+        assert_eq!(draw(None), sprite_line(Color::C1, Some(Color::C2)));
+        // Background hidden
+        assert_eq!(
+            draw(Some(Layer::Background)),
+            sprite_line(Color::C0, Some(Color::C2))
+        );
+        // Objects hidden
+        assert_eq!(draw(Some(Layer::Objects)), sprite_line(Color::C1, None));
+    }
+
+    #[test]
+    fn lyc_write_works() {
+        let mut ppu = setup();
+
+        // Advance to scanline 5
+        (0..5 * 456 + 10).for_each(|_| ppu.cycle());
+        assert_eq!(ppu.ctl.borrow().read(0x04), 5);
+        // Enable LYC=LY interrupts
+        ppu.ctl.borrow_mut().write(0x01, 0x40);
+        **ppu.pic.borrow().active.borrow_mut() = 0;
+        ppu.cycle();
+        assert_eq!(ppu.ctl.borrow().read(0x01) & 0x04, 0);
+
+        // Write LYC to match the current scanline
+        ppu.ctl.borrow_mut().write(0x05, 5);
+        ppu.cycle();
+        assert_ne!(ppu.ctl.borrow().read(0x01) & 0x04, 0);
+        assert_eq!(
+            **ppu.pic.borrow().active.borrow() & Interrupt::LcdStat as u8,
+            Interrupt::LcdStat as u8
+        );
+    }
+}
@@ -78,7 +78,8 @@ impl TryFrom<u8> for Color {
     }
 }
 
-#[derive(Copy, Clone, Debug)]
+/// Pixel color palettes.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum Palette {
     BgWin,
     Obj0,
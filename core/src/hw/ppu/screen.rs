@@ -3,6 +3,57 @@ use std::ops::{Deref, DerefMut};
 
 use super::pixel::Color;
 use crate::dmg::SCREEN;
+use crate::model::Model;
+
+/// Pixel output interface.
+///
+/// Receives each pixel as it is shifted out of the PPU, allowing for custom
+/// output pipelines (such as filters, scalers, or capture).
+pub trait Renderer {
+    /// Outputs the pixel at the given screen coordinates.
+    fn put_pixel(&mut self, x: u8, y: u8, color: Color);
+}
+
+/// Framebuffer output format.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum FrameFormat {
+    /// Raw 2-bit shade indices.
+    #[default]
+    Indexed,
+    /// Shades converted to RGBA (8 bits per channel).
+    Rgba8888,
+}
+
+/// Output color for each LCD shade, from lightest to darkest.
+#[derive(Copy, Clone, Debug)]
+pub struct Shades(pub [u32; 4]);
+
+impl Shades {
+    /// Constructs the default `Shades` for the provided model.
+    pub fn with(model: Model) -> Self {
+        match model {
+            Model::Dmg => Self([0xe9efec, 0xa0a08b, 0x555568, 0x211e20]),
+            Model::Mgb | Model::Cgb => Self([0xffffff, 0xaaaaaa, 0x555555, 0x000000]),
+        }
+    }
+
+    /// Converts a color into its output `0x00RRGGBB` value.
+    pub fn get(&self, color: Color) -> u32 {
+        self.0[color as usize]
+    }
+
+    /// Converts a color into its output RGBA bytes.
+    pub fn rgba(&self, color: Color) -> [u8; 4] {
+        let [_, r, g, b] = self.get(color).to_be_bytes();
+        [r, g, b, 0xff]
+    }
+}
+
+impl Default for Shades {
+    fn default() -> Self {
+        Self::with(Model::default())
+    }
+}
 
 /// Screen data.
 #[derive(Debug)]
@@ -33,3 +84,9 @@ impl From<[Color; SCREEN.depth()]> for Screen {
         Self(buf)
     }
 }
+
+impl Renderer for Screen {
+    fn put_pixel(&mut self, x: u8, y: u8, color: Color) {
+        self[(y as usize * SCREEN.width) + x as usize] = color;
+    }
+}
@@ -1,6 +1,7 @@
 use super::pixel::Palette;
 
-#[derive(Debug)]
+/// Object attribute memory entry.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub struct Sprite {
     // Byte 0 - Y Position
     pub ypos: u8,
@@ -27,13 +28,13 @@ impl From<[u8; 4]> for Sprite {
             ypos:     bytes[0],
             xpos:     bytes[1],
             idx:      bytes[2],
-            priority: bytes[3] & 0x08 != 0,
-            yflip:    bytes[3] & 0x07 != 0,
-            xflip:    bytes[3] & 0x06 != 0,
+            priority: bytes[3] & 0x80 != 0,
+            yflip:    bytes[3] & 0x40 != 0,
+            xflip:    bytes[3] & 0x20 != 0,
             palette: [
                 Palette::Obj0,
                 Palette::Obj1,
-            ][(bytes[3] & 0x05 != 0) as usize],
+            ][(bytes[3] & 0x10 != 0) as usize],
         }
     }
 }
@@ -0,0 +1,157 @@
+//! Serial port.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use remus::bus::Bus;
+use remus::reg::Register;
+use remus::{Block, Device, Machine};
+
+use super::pic::{Interrupt, Pic};
+use crate::emu::hook::Hook;
+
+/// Number of cycles taken to shift a single bit (8192 Hz).
+const BIT: usize = 0x200;
+
+/// Serial model.
+///
+/// Transfers are only clocked by the internal clock, with no link partner
+/// attached. As such, each byte shifted out is replaced by `0xff`.
+#[rustfmt::skip]
+#[derive(Debug, Default)]
+pub struct Serial {
+    /// Serial control registers.
+    pub ctl: Rc<RefCell<Registers>>,
+    /// Programmable interrupt controller.
+    pic: Rc<RefCell<Pic>>,
+    /// Current cycle count of the transfer.
+    cycle: usize,
+    /// Bits shifted out so far.
+    data: u8,
+    /// Bytes sent (if captured).
+    out: Option<String>,
+    /// Byte sent callback.
+    hook: Hook<dyn FnMut(u8)>,
+}
+
+impl Serial {
+    /// Set the serial port's pic.
+    pub fn set_pic(&mut self, pic: Rc<RefCell<Pic>>) {
+        self.pic = pic;
+    }
+
+    /// Enables (or disables) capturing of the bytes sent.
+    pub fn capture(&mut self, enable: bool) {
+        self.out = enable.then(String::new);
+    }
+
+    /// Sets a callback to be invoked with each byte sent.
+    pub fn set_hook(&mut self, hook: Box<dyn FnMut(u8)>) {
+        self.hook = hook.into();
+    }
+
+    /// Gets the bytes sent since capturing was enabled.
+    pub fn output(&self) -> &str {
+        self.out.as_deref().unwrap_or_default()
+    }
+}
+
+impl Block for Serial {
+    fn reset(&mut self) {
+        // Reset registers
+        self.ctl.borrow_mut().reset();
+        // Reset transfer
+        self.cycle = Default::default();
+        self.data = Default::default();
+    }
+}
+
+impl Machine for Serial {
+    fn enabled(&self) -> bool {
+        true
+    }
+
+    fn cycle(&mut self) {
+        // Borrow registers
+        let regs = &*self.ctl.borrow();
+        let sc = &mut **regs.sc.borrow_mut();
+
+        // Only progress transfers using the internal clock
+        if *sc & 0x81 != 0x81 {
+            self.cycle = 0;
+            return;
+        }
+
+        // Shift out a bit every 512 cycles
+        self.cycle += 1;
+        if self.cycle % BIT == 0 {
+            let sb = &mut **regs.sb.borrow_mut();
+            self.data = (self.data << 1) | (*sb >> 7);
+            // NOTE: Without a link partner, the incoming bits are all high
+            *sb = (*sb << 1) | 0x01;
+        }
+
+        // Finish transfer after 8 bits
+        if self.cycle == 8 * BIT {
+            // Record the byte sent
+            if let Some(out) = &mut self.out {
+                out.push(self.data as char);
+            }
+            if let Some(hook) = self.hook.get() {
+                hook(self.data);
+            }
+            // Clear the transfer flag
+            *sc &= 0x7f;
+            // Schedule Serial interrupt
+            self.pic.borrow_mut().req(Interrupt::Serial);
+            self.cycle = 0;
+        }
+    }
+}
+
+/// Control registers.
+#[rustfmt::skip]
+#[derive(Debug, Default)]
+pub struct Registers {
+    bus: Bus,
+    // ┌────────┬──────────────────┬─────┬───────┐
+    // │  SIZE  │       NAME       │ DEV │ ALIAS │
+    // ├────────┼──────────────────┼─────┼───────┤
+    // │    1 B │    Transfer Data │ Reg │ SB    │
+    // │    1 B │ Transfer Control │ Reg │ SC    │
+    // └────────┴──────────────────┴─────┴───────┘
+    pub sb: Rc<RefCell<Register<u8>>>,
+    pub sc: Rc<RefCell<Register<u8>>>,
+}
+
+impl Block for Registers {
+    #[rustfmt::skip]
+    fn reset(&mut self) {
+        // Reset self
+        std::mem::take(self);
+        // Reset bus                         // ┌──────┬──────────────────┬─────┐
+        self.bus.reset();                    // │ SIZE │       NAME       │ DEV │
+                                             // ├──────┼──────────────────┼─────┤
+        self.bus.map(0x00, self.sb.clone()); // │  1 B │    Transfer Data │ Reg │
+        self.bus.map(0x01, self.sc.clone()); // │  1 B │ Transfer Control │ Reg │
+                                             // └──────┴──────────────────┴─────┘
+    }
+}
+
+impl Device for Registers {
+    fn contains(&self, index: usize) -> bool {
+        self.bus.contains(index)
+    }
+
+    fn len(&self) -> usize {
+        self.bus.len()
+    }
+
+    fn read(&self, index: usize) -> u8 {
+        self.bus.read(index)
+    }
+
+    fn write(&mut self, index: usize, value: u8) {
+        self.bus.write(index, value);
+    }
+}
@@ -26,6 +26,31 @@ impl Timer {
     pub fn set_pic(&mut self, pic: Rc<RefCell<Pic>>) {
         self.pic = pic;
     }
+
+    /// Gets the timer's internal state.
+    #[must_use]
+    pub fn state(&self) -> TimerState {
+        TimerState {
+            div: self.cycle as u16,
+            reload: self.ctl.borrow().reload,
+        }
+    }
+
+    /// Sets the timer's internal state.
+    pub fn set_state(&mut self, state: TimerState) {
+        self.cycle = state.div as usize;
+        self.ctl.borrow_mut().reload = state.reload;
+    }
+}
+
+/// Timer internal state.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct TimerState {
+    /// Internal divider, which clocks `DIV` and `TIMA`.
+    pub div: u16,
+    /// `TIMA` reload state.
+    pub reload: Reload,
 }
 
 impl Block for Timer {
@@ -42,7 +67,7 @@ impl Machine for Timer {
 
     fn cycle(&mut self) {
         // Borrow registers
-        let regs = &*self.ctl.borrow();
+        let regs = &mut *self.ctl.borrow_mut();
 
         // Increment DIV every 256 cycles
         if self.cycle % 0x100 == 0 {
@@ -50,6 +75,20 @@ impl Machine for Timer {
             *div = div.wrapping_add(1);
         }
 
+        // Advance any TIMA reload
+        regs.reload = match regs.reload {
+            Reload::Overflow(1) => {
+                // Schedule Timer interrupt
+                self.pic.borrow_mut().req(Interrupt::Timer);
+                // Restart from TMA
+                **regs.tima.borrow_mut() = **regs.tma.borrow();
+                Reload::Reload(4)
+            }
+            Reload::Overflow(delay) => Reload::Overflow(delay - 1),
+            Reload::Reload(1) | Reload::Idle => Reload::Idle,
+            Reload::Reload(delay) => Reload::Reload(delay - 1),
+        };
+
         // Increment TIMA if enabled
         let tac = **regs.tac.borrow();
         if tac & 0x04 != 0 {
@@ -68,10 +107,10 @@ impl Machine for Timer {
                 *tima = match tima.checked_add(1) {
                     Some(tima) => tima,
                     None => {
-                        // Schedule Timer interrupt
-                        self.pic.borrow_mut().req(Interrupt::Timer);
-                        // Restart from TMA
-                        **regs.tma.borrow()
+                        // Delay the reload by a cycle
+                        // NOTE: TIMA reads as zero in the meantime.
+                        regs.reload = Reload::Overflow(4);
+                        0x00
                     }
                 };
             };
@@ -82,11 +121,28 @@ impl Machine for Timer {
     }
 }
 
+/// TIMA reload state.
+///
+/// Each state lasts for a single M-cycle (4 cycles), tracked by the number of
+/// cycles remaining.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum Reload {
+    /// No reload is in progress.
+    #[default]
+    Idle,
+    /// TIMA has overflowed, and has yet to be reloaded.
+    Overflow(u8),
+    /// TIMA has been reloaded from TMA.
+    Reload(u8),
+}
+
 /// Control registers.
 #[rustfmt::skip]
 #[derive(Debug, Default)]
 pub struct Registers {
     bus: Bus,
+    reload: Reload,
     // ┌────────┬──────────────────┬─────┬───────┐
     // │  SIZE  │       NAME       │ DEV │ ALIAS │
     // ├────────┼──────────────────┼─────┼───────┤
@@ -131,6 +187,83 @@ impl Device for Registers {
     }
 
     fn write(&mut self, index: usize, value: u8) {
+        match (index, self.reload) {
+            // Writing TIMA before the reload cancels it
+            (0x01, Reload::Overflow(_)) => self.reload = Reload::Idle,
+            // Writing TIMA during the reload is ignored
+            (0x01, Reload::Reload(_)) => return,
+            // Writing TMA during the reload is also loaded into TIMA
+            (0x02, Reload::Reload(_)) => self.bus.write(0x01, value),
+            _ => (),
+        }
         self.bus.write(index, value);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Sets up a timer which overflows on its first cycle.
+    fn setup() -> Timer {
+        let mut timer = Timer::default();
+        timer.reset();
+        let mut regs = timer.ctl.borrow_mut();
+        regs.write(0x01, 0xff);
+        regs.write(0x02, 0x42);
+        regs.write(0x03, 0x05);
+        drop(regs);
+        timer
+    }
+
+    /// Checks if the timer has requested an interrupt.
+    fn int(timer: &Timer) -> bool {
+        **timer.pic.borrow().active.borrow() & 0x04 != 0
+    }
+
+    #[test]
+    fn tima_reload_works() {
+        let mut timer = setup();
+        // TIMA reads zero for a cycle after overflowing
+        (0..4).for_each(|_| timer.cycle());
+        assert_eq!(timer.ctl.borrow().read(0x01), 0x00);
+        assert!(!int(&timer));
+        // It is then reloaded from TMA
+        timer.cycle();
+        assert_eq!(timer.ctl.borrow().read(0x01), 0x42);
+        assert!(int(&timer));
+    }
+
+    #[test]
+    fn tima_write_during_reload_works() {
+        // Writing TIMA before the reload cancels it
+        let mut timer = setup();
+        (0..4).for_each(|_| timer.cycle());
+        timer.ctl.borrow_mut().write(0x01, 0x10);
+        (0..8).for_each(|_| timer.cycle());
+        assert_eq!(timer.ctl.borrow().read(0x01), 0x10);
+        assert!(!int(&timer));
+
+        // Writing TIMA during the reload is ignored
+        let mut timer = setup();
+        (0..8).for_each(|_| timer.cycle());
+        timer.ctl.borrow_mut().write(0x01, 0x10);
+        timer.cycle();
+        assert_eq!(timer.ctl.borrow().read(0x01), 0x42);
+        assert!(int(&timer));
+    }
+
+    #[test]
+    fn tma_write_during_reload_works() {
+        let mut timer = setup();
+        (0..8).for_each(|_| timer.cycle());
+        // Writing TMA during the reload also updates TIMA
+        timer.ctl.borrow_mut().write(0x02, 0x99);
+        timer.cycle();
+        assert_eq!(timer.ctl.borrow().read(0x01), 0x99);
+        assert_eq!(timer.ctl.borrow().read(0x02), 0x99);
+        // Afterwards, TMA writes leave TIMA intact
+        timer.ctl.borrow_mut().write(0x02, 0x11);
+        assert_eq!(timer.ctl.borrow().read(0x01), 0x99);
+    }
+}
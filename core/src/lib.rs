@@ -2,11 +2,15 @@
 //!
 //! This library implements the core behaviour of the various hardware
 //! components of the Nintendo Game Boy family of consoles.
+//!
+//! # Features
+//!
+//! - `serde`: Enables serialization of state types.
 
 mod dev;
 mod emu;
 mod hw;
 mod model;
 
-pub use self::emu::Emulator;
+pub use self::emu::{Emulator, Frontend, Headless};
 pub use self::model::dmg;
@@ -0,0 +1,97 @@
+//! Bus arbitration.
+
+use std::cell::{Cell, RefCell};
+use std::ops::RangeInclusive;
+use std::rc::Rc;
+
+use remus::bus::Bus;
+use remus::{Block, Device};
+
+use crate::hw::ppu::{self, OamAccess};
+
+/// Addresses which remain accessible to the CPU during an OAM DMA.
+///
+/// NOTE: I/O, HRAM, and the IE register sit on a separate bus from the one
+///       used by the DMA.
+const OPEN: RangeInclusive<usize> = 0xff00..=0xffff;
+
+/// Addresses whose access may trigger the OAM corruption bug.
+const OAM: RangeInclusive<usize> = 0xfe00..=0xfeff;
+
+/// Checks if CPU accesses to an address are blocked during an OAM DMA.
+pub fn blocked(index: usize) -> bool {
+    !OPEN.contains(&index)
+}
+
+/// Reports an address output by the CPU's increment/decrement unit.
+///
+/// NOTE: Although no access takes place, addresses in [`OAM`] still trigger
+///       the OAM corruption bug, as would a write.
+pub fn idu(lcd: &RefCell<ppu::Registers>, addr: u16) {
+    if OAM.contains(&(addr as usize)) {
+        lcd.borrow().oam_access(OamAccess::Write);
+    }
+}
+
+/// CPU bus arbiter.
+///
+/// While an OAM DMA is in progress, the DMA holds the bus. CPU reads outside
+/// of [`OPEN`] instead see the byte being transferred, and writes are dropped.
+///
+/// Accesses to [`OAM`] are additionally reported to the PPU.
+///
+/// The value of each access is latched as the last value on the bus.
+#[derive(Debug, Default)]
+pub struct Arbiter {
+    pub mmu: Rc<RefCell<Bus>>,
+    pub lcd: Rc<RefCell<ppu::Registers>>,
+    pub last: Rc<Cell<u8>>,
+}
+
+impl Arbiter {
+    /// Gets the byte held on the bus by the DMA, if any.
+    fn conflict(&self, index: usize) -> Option<u8> {
+        if !blocked(index) {
+            return None;
+        }
+        self.lcd.borrow().dma_transfer()
+    }
+}
+
+impl Block for Arbiter {
+    fn reset(&mut self) {
+        self.mmu.borrow_mut().reset();
+    }
+}
+
+impl Device for Arbiter {
+    fn contains(&self, index: usize) -> bool {
+        (0..self.len()).contains(&index)
+    }
+
+    fn len(&self) -> usize {
+        0x10000
+    }
+
+    fn read(&self, index: usize) -> u8 {
+        if OAM.contains(&index) {
+            self.lcd.borrow().oam_access(OamAccess::Read);
+        }
+        let data = match self.conflict(index) {
+            Some(data) => data,
+            None => self.mmu.borrow().read(index),
+        };
+        self.last.set(data);
+        data
+    }
+
+    fn write(&mut self, index: usize, value: u8) {
+        if OAM.contains(&index) {
+            self.lcd.borrow().oam_access(OamAccess::Write);
+        }
+        if self.conflict(index).is_none() {
+            self.mmu.borrow_mut().write(index, value);
+        }
+        self.last.set(value);
+    }
+}
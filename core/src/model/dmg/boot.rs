@@ -8,8 +8,10 @@ use remus::dev::Device;
 use remus::reg::Register;
 use remus::{mem, Block};
 
+use crate::model::Model;
+
 /// Boot ROM raw bytes.
-const BOOTROM: [u8; 0x100] = [
+pub const BOOTROM: [u8; 0x100] = [
     0x31, 0xfe, 0xff, 0xaf, 0x21, 0xff, 0x9f, 0x32, 0xcb, 0x7c, 0x20, 0xfb, 0x21, 0x26, 0xff, 0x0e,
     0x11, 0x3e, 0x80, 0x32, 0xe2, 0x0c, 0x3e, 0xf3, 0xe2, 0x32, 0x3e, 0x77, 0x77, 0x3e, 0xfc, 0xe0,
     0x47, 0x11, 0x04, 0x01, 0x21, 0x10, 0x80, 0x1a, 0xcd, 0x95, 0x00, 0xcd, 0x96, 0x00, 0x13, 0x7b,
@@ -36,6 +38,23 @@ pub struct Rom {
     rom: Rc<RefCell<mem::Rom<0x100>>>,
 }
 
+impl Rom {
+    /// Constructs a new `Rom` for the provided model.
+    ///
+    /// NOTE: The MGB boot ROM differs only in the value of A upon completion,
+    ///       which is used by games to detect the model.
+    pub fn with(model: Model) -> Self {
+        let mut boot = BOOTROM;
+        if let Model::Mgb = model {
+            boot[0xfd] = 0xff;
+        }
+        Self {
+            rom: Rc::new(RefCell::new(mem::Rom::from(&boot))),
+            ..Default::default()
+        }
+    }
+}
+
 impl Block for Rom {
     fn reset(&mut self) {
         // Reset controller
@@ -107,6 +126,7 @@ impl Device for RomDisable {
 
     fn write(&mut self, index: usize, value: u8) {
         self.reg.write(index, value);
-        self.bank.borrow_mut().set(value as usize);
+        // NOTE: Any non-zero value disables the boot ROM
+        self.bank.borrow_mut().set((value != 0) as usize);
     }
 }
@@ -3,8 +3,11 @@
 use std::cell::RefCell;
 use std::rc::Rc;
 
+use remus::bus::adapt::{Bank, View};
 use remus::mem::Ram;
-use remus::Block;
+use remus::reg::Register;
+use remus::{Block, Device};
+use thiserror::Error;
 
 use super::boot;
 
@@ -14,21 +17,96 @@ use super::boot;
 /// [`GameBoy`](super::GameBoy).
 #[derive(Debug, Default)]
 pub struct Memory {
-    // ┌────────┬──────┬─────┬───────┐
-    // │  SIZE  │ NAME │ DEV │ ALIAS │
-    // ├────────┼──────┼─────┼───────┤
-    // │  256 B │ Boot │ ROM │       │
-    // │ 8 Ki B │ Work │ RAM │ WRAM  │
-    // │  127 B │ High │ RAM │ HRAM  │
-    // └────────┴──────┴─────┴───────┘
+    // ┌─────────┬──────────────────┬─────┬───────┐
+    // │  SIZE   │       NAME       │ DEV │ ALIAS │
+    // ├─────────┼──────────────────┼─────┼───────┤
+    // │   256 B │             Boot │ ROM │       │
+    // │  8 Ki B │             Work │ RAM │ WRAM  │
+    // │   127 B │             High │ RAM │ HRAM  │
+    // │ 24 Ki B │ Work (Banks 2-7) │ RAM │ WRAMX │
+    // │     1 B │        WRAM Bank │ Reg │ SVBK  │
+    // └─────────┴──────────────────┴─────┴───────┘
     pub boot: Rc<RefCell<boot::Rom>>,
     pub wram: Rc<RefCell<Ram<0x2000>>>,
     pub hram: Rc<RefCell<Ram<0x007f>>>,
+    // NOTE: WRAM banking is only mapped on the CGB
+    pub wramx: Rc<RefCell<Ram<0x6000>>>,
+    pub svbk: Rc<RefCell<Svbk>>,
 }
 
 impl Block for Memory {
     fn reset(&mut self) {
         // Reset boot ROM
         self.boot.borrow_mut().reset();
+        // Reset WRAM, HRAM
+        self.wram.borrow_mut().reset();
+        self.hram.borrow_mut().reset();
+        self.wramx.borrow_mut().reset();
+
+        // Reset WRAM bank
+        let mut svbk = self.svbk.borrow_mut();
+        svbk.reset();
+        let mut bank = svbk.bank.borrow_mut();
+        bank.reset();
+        bank.add(View::new(self.wram.clone(), 0x1000..=0x1fff).to_shared());
+        for base in (0x0000..0x6000).step_by(0x1000) {
+            bank.add(View::new(self.wramx.clone(), base..=base + 0xfff).to_shared());
+        }
+        bank.set(0);
+    }
+}
+
+/// WRAM bank select register.
+///
+/// Selects which bank of WRAM is mapped at `$D000..=$DFFF` for the CPU, with
+/// all but the lowest 3 bits reading as set. Selecting bank 0 instead selects
+/// bank 1.
+#[derive(Debug, Default)]
+pub struct Svbk {
+    reg: Register<u8>,
+    bank: Rc<RefCell<Bank>>,
+}
+
+impl Svbk {
+    /// Gets the WRAM bank selected by this register.
+    #[must_use]
+    pub fn wram(&self) -> Rc<RefCell<Bank>> {
+        self.bank.clone()
+    }
+}
+
+impl Block for Svbk {
+    fn reset(&mut self) {
+        // NOTE: Banks are added (and selected) by the memory on reset.
+        self.reg.reset();
     }
 }
+
+impl Device for Svbk {
+    fn contains(&self, index: usize) -> bool {
+        self.reg.contains(index)
+    }
+
+    fn len(&self) -> usize {
+        self.reg.len()
+    }
+
+    fn read(&self, _: usize) -> u8 {
+        0xf8 | *self.reg
+    }
+
+    fn write(&mut self, _: usize, value: u8) {
+        *self.reg = value & 0x07;
+        self.bank.borrow_mut().set((*self.reg).max(1) as usize - 1);
+    }
+}
+
+/// A type specifying general categories of memory image error.
+///
+/// Raised when loading the contents of a memory, such as external RAM, VRAM,
+/// or OAM.
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("mismatched RAM size: expected {expected}, found {found}")]
+    SizeMismatch { expected: usize, found: usize },
+}
@@ -4,12 +4,14 @@ use std::cell::RefCell;
 use std::rc::Rc;
 
 use remus::bus::Bus;
+use remus::dev::Null;
 use remus::mem::Ram;
 use remus::reg::Register;
 use remus::{Block, Device};
 
-use super::boot;
-use crate::hw::{joypad, ppu, timer};
+use super::{boot, mem};
+use crate::hw::{joypad, ppu, serial, timer};
+use crate::model::Model;
 
 /// Mapped I/O ports.
 ///
@@ -19,6 +21,7 @@ use crate::hw::{joypad, ppu, timer};
 #[derive(Debug, Default)]
 pub struct Mmio {
     pub bus: Rc<RefCell<Bus>>,
+    pub model: Model,
     // ┌────────┬──────────────────┬─────┐
     // │  SIZE  │       NAME       │ DEV │
     // ├────────┼──────────────────┼─────┤
@@ -32,13 +35,27 @@ pub struct Mmio {
     // │    1 B │ Boot ROM Disable │ Reg │
     // └────────┴──────────────────┴─────┘
     pub con:   Rc<RefCell<joypad::Register>>,
-    pub com:   Rc<RefCell<Register<u16>>>,
+    pub com:   Rc<RefCell<serial::Registers>>,
     pub timer: Rc<RefCell<timer::Registers>>,
     pub iflag: Rc<RefCell<Register<u8>>>,
     pub sound: Rc<RefCell<Ram<0x17>>>,
     pub wave:  Rc<RefCell<Ram<0x10>>>,
     pub lcd:   Rc<RefCell<ppu::Registers>>,
     pub boot:  Rc<RefCell<boot::RomDisable>>,
+    // ┌────────┬──────────────────┬──────┐
+    // │  SIZE  │    NAME (CGB)    │ DEV  │
+    // ├────────┼──────────────────┼──────┤
+    // │    1 B │     Speed Switch │ Reg  │
+    // │    1 B │        VRAM Bank │ PPU  │
+    // │    2 B │       BG Palette │ CRAM │
+    // │    2 B │      OBJ Palette │ CRAM │
+    // │    1 B │        WRAM Bank │ Mem  │
+    // └────────┴──────────────────┴──────┘
+    pub key1:  Rc<RefCell<Register<u8>>>,
+    pub vbk:   Rc<RefCell<ppu::Vbk>>,
+    pub bcp:   Rc<RefCell<ppu::Cram>>,
+    pub ocp:   Rc<RefCell<ppu::Cram>>,
+    pub svbk:  Rc<RefCell<mem::Svbk>>,
 }
 
 impl Mmio {
@@ -75,11 +92,35 @@ impl Mmio {
         bus.map(0x50, boot);  // │    1 B │   Boot ROM Bank │ Reg │
                               // │   47 B │        Unmapped │ --- │
                               // └────────┴─────────────────┴─────┘
+
+        // Map CGB devices in I/O
+        if let Model::Cgb = self.model {
+            let key1 = self.key1.clone();
+            let vbk = self.vbk.clone();
+            let bcp = self.bcp.clone();
+            let ocp = self.ocp.clone();
+            let svbk = self.svbk.clone();
+                                  // ┌────────┬──────────────┬──────┐
+                                  // │  SIZE  │     NAME     │ DEV  │
+                                  // ├────────┼──────────────┼──────┤
+            bus.map(0x4d, key1);  // │    1 B │ Speed Switch │ Reg  │
+            bus.map(0x4f, vbk);   // │    1 B │    VRAM Bank │ PPU  │
+            bus.map(0x68, bcp);   // │    2 B │   BG Palette │ CRAM │
+            bus.map(0x6a, ocp);   // │    2 B │  OBJ Palette │ CRAM │
+            bus.map(0x70, svbk);  // │    1 B │    WRAM Bank │ Mem  │
+                                  // └────────┴──────────────┴──────┘
+        }
+
+        // NOTE: Unmapped I/O ports always read as `0xff`, irrespective of any
+        //       seeded fallback on the MMU.
+        bus.map(0x00, Null::<0x80>::with(0xff).to_shared());
     }
 }
 
 impl Block for Mmio {
     fn reset(&mut self) {
+        // Reset CGB registers
+        self.key1.borrow_mut().reset();
         // Re-map bus
         self.memmap();
     }
@@ -102,3 +143,79 @@ impl Device for Mmio {
         self.bus.borrow_mut().write(index, value);
     }
 }
+
+/// Named I/O registers.
+///
+/// Each variant's discriminant is the register's address on the memory bus.
+#[rustfmt::skip]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum IoReg {
+    // Controller
+    P1   = 0xff00,
+    // Communication
+    Sb   = 0xff01,
+    Sc   = 0xff02,
+    // Divider & Timer
+    Div  = 0xff04,
+    Tima = 0xff05,
+    Tma  = 0xff06,
+    Tac  = 0xff07,
+    // Interrupt Flag
+    If   = 0xff0f,
+    // Sound
+    Nr10 = 0xff10,
+    Nr11 = 0xff11,
+    Nr12 = 0xff12,
+    Nr13 = 0xff13,
+    Nr14 = 0xff14,
+    Nr21 = 0xff16,
+    Nr22 = 0xff17,
+    Nr23 = 0xff18,
+    Nr24 = 0xff19,
+    Nr30 = 0xff1a,
+    Nr31 = 0xff1b,
+    Nr32 = 0xff1c,
+    Nr33 = 0xff1d,
+    Nr34 = 0xff1e,
+    Nr41 = 0xff20,
+    Nr42 = 0xff21,
+    Nr43 = 0xff22,
+    Nr44 = 0xff23,
+    Nr50 = 0xff24,
+    Nr51 = 0xff25,
+    Nr52 = 0xff26,
+    // LCD
+    Lcdc = 0xff40,
+    Stat = 0xff41,
+    Scy  = 0xff42,
+    Scx  = 0xff43,
+    Ly   = 0xff44,
+    Lyc  = 0xff45,
+    Dma  = 0xff46,
+    Bgp  = 0xff47,
+    Obp0 = 0xff48,
+    Obp1 = 0xff49,
+    Wy   = 0xff4a,
+    Wx   = 0xff4b,
+    // Speed Switch (CGB)
+    Key1 = 0xff4d,
+    // VRAM Bank (CGB)
+    Vbk  = 0xff4f,
+    // Boot ROM Disable
+    Boot = 0xff50,
+    // Color Palettes (CGB)
+    Bcps = 0xff68,
+    Bcpd = 0xff69,
+    Ocps = 0xff6a,
+    Ocpd = 0xff6b,
+    // WRAM Bank (CGB)
+    Svbk = 0xff70,
+    // Interrupt Enable
+    Ie   = 0xffff,
+}
+
+impl From<IoReg> for u16 {
+    fn from(value: IoReg) -> Self {
+        value as u16
+    }
+}
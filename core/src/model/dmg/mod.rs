@@ -2,31 +2,60 @@
 //!
 //! [Game Boy]: https://en.wikipedia.org/wiki/Game_Boy
 
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
+use std::collections::BTreeMap;
+use std::io;
+use std::path::Path;
 use std::rc::Rc;
+use std::time::{Duration, Instant};
 
+use log::error;
 use remus::bus::adapt::View;
 use remus::bus::Bus;
-use remus::{Block, Device, Machine};
+use remus::dev::Null;
+use remus::{Block, Device, Machine, SharedDevice};
 
+use self::arbiter::Arbiter;
 use self::mem::Memory;
 use self::mmio::Mmio;
-use crate::dev::Unmapped;
-use crate::emu::{screen, Emulator};
+use crate::dev::{mix, Unmapped};
+use crate::emu::{png, screen, Emulator, Frontend};
 use crate::hw::cart::Cartridge;
-use crate::hw::cpu::{Processor, Sm83 as Cpu};
+use crate::hw::cpu::{disasm, Processor};
 use crate::hw::joypad::Joypad;
 use crate::hw::pic::Pic;
-use crate::hw::ppu::Ppu;
+use crate::hw::ppu::Shades;
+use crate::hw::serial::Serial;
 use crate::hw::timer::Timer;
 
+mod arbiter;
 mod boot;
 mod mem;
 mod mmio;
+mod movie;
+mod state;
+mod trace;
 
+pub use self::mem::Error as RamError;
+pub use self::mmio::IoReg;
+pub use self::movie::{Error as MovieError, Movie};
+pub use self::state::{Error as StateError, Snapshot, StateDiff};
+pub use self::trace::{Error as TraceError, TraceEntry, TraceReader, TraceWriter};
+pub use crate::dev::Overlay;
+pub use crate::emu::clock::{Clock, ManualClock, SystemClock};
 pub use crate::hw::cart;
-pub use crate::hw::joypad::Button;
-pub use crate::hw::ppu::Screen;
+pub use crate::hw::cart::mbc::{MbcState, RtcState};
+pub use crate::hw::cpu::{
+    Access, BusAccess, CpuControl, CpuState, CpuStats, CpuStatus, HookAction, Ime, Sm83 as Cpu,
+};
+pub use crate::hw::joypad::{Button, JoypadState};
+pub use crate::hw::pic::Interrupt;
+pub use crate::hw::ppu::{
+    Color, FrameFormat, Layer, Mode as PpuMode, Palette, Ppu, PpuState, Renderer, Screen,
+    SpriteEntry,
+};
+pub use crate::hw::timer::{Reload as TimerReload, TimerState};
+pub use crate::model::Model;
 
 /// Screen info.
 pub const SCREEN: screen::Info = screen::Info {
@@ -34,22 +63,151 @@ pub const SCREEN: screen::Info = screen::Info {
     height: 144,
 };
 
+/// Clock frequency (Hz).
+pub const FREQ: u32 = 4_194_304;
+
+/// Number of dots (clock cycles) per frame.
+pub const FRAME: u32 = 70_224;
+
+/// Mapped memory region.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MemRegion {
+    /// First address of the region.
+    pub start: u16,
+    /// Last address of the region (inclusive).
+    pub end: u16,
+    /// Name of the mapped device.
+    pub name: &'static str,
+    /// Kind of the mapped device.
+    pub kind: MemKind,
+    /// Whether CPU accesses are blocked during an OAM DMA.
+    pub blocked: bool,
+}
+
+/// Kind of device mapped to a [`MemRegion`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum MemKind {
+    Rom,
+    Ram,
+    Bus,
+    Reg,
+    Unmapped,
+}
+
+/// Regions mapped to the MMU, in the order they are mapped.
+///
+/// NOTE: The boot ROM is mapped over the cartridge ROM until disabled.
+#[rustfmt::skip]
+const MEMMAP: [(u16, u16, &str, MemKind); 11] = [
+                                                      // ┌──────────┐
+                                                      // │   SIZE   │
+                                                      // ├──────────┤
+    (0x0000, 0x00ff, "Boot",      MemKind::Rom),      // │    256 B │
+    (0x0000, 0x7fff, "Cartridge", MemKind::Rom),      // │  32 Ki B │
+    (0x8000, 0x9fff, "Video",     MemKind::Ram),      // │   8 Ki B │
+    (0xa000, 0xbfff, "External",  MemKind::Ram),      // │   8 Ki B │
+    (0xc000, 0xdfff, "Work",      MemKind::Ram),      // │   8 Ki B │
+    (0xe000, 0xfdff, "Echo",      MemKind::Ram),      // │   7680 B │
+    (0xfe00, 0xfe9f, "OAM",       MemKind::Ram),      // │    160 B │
+    (0xfea0, 0xfeff, "Unusable",  MemKind::Unmapped), // │     96 B │
+    (0xff00, 0xff7f, "I/O",       MemKind::Bus),      // │    128 B │
+    (0xff80, 0xfffe, "High",      MemKind::Ram),      // │    127 B │
+    (0xffff, 0xffff, "Interrupt", MemKind::Reg),      // │      1 B │
+                                                      // └──────────┘
+];
+
+/// Snapshot of RAM contents, as read from the bus.
+#[derive(Clone, Debug)]
+pub struct BusSnapshot(Vec<(u16, u8)>);
+
+impl BusSnapshot {
+    /// Lists the addresses that differ from another snapshot.
+    ///
+    /// Each difference is reported as `(address, old, new)`, where `old` is
+    /// from this snapshot and `new` from the other.
+    #[must_use]
+    pub fn diff(&self, other: &BusSnapshot) -> Vec<(u16, u8, u8)> {
+        self.0
+            .iter()
+            .zip(&other.0)
+            .filter(|((_, old), (_, new))| old != new)
+            .map(|(&(addr, old), &(_, new))| (addr, old, new))
+            .collect()
+    }
+}
+
+/// Kind of reset, as performed by [`GameBoy::reset_with`].
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum ResetKind {
+    /// Power cycle, leaving RAM with indeterminate contents.
+    PowerOn,
+    /// Reset line pulse, preserving RAM contents.
+    #[default]
+    Reset,
+}
+
+/// Real-time pacing.
+#[derive(Debug)]
+struct Pacing {
+    /// Speed relative to the console's clock.
+    factor: f32,
+    /// Time of the previous slice.
+    last: Option<Duration>,
+    /// Fraction of a cycle owed from the previous slice.
+    debt: f64,
+    /// Maximum frames run per slice.
+    limit: Option<u32>,
+}
+
+impl Default for Pacing {
+    fn default() -> Self {
+        Self {
+            factor: 1.0,
+            last: None,
+            debt: 0.0,
+            limit: None,
+        }
+    }
+}
+
+impl Pacing {
+    /// Advances the deadline of the next frame by a frame's `period`.
+    ///
+    /// When the deadline has fallen behind `now` by more than the catch-up
+    /// limit, the excess is dropped.
+    fn advance(&self, deadline: Instant, now: Instant, period: Duration) -> Instant {
+        let deadline = deadline + period;
+        match self.limit.and_then(|limit| now.checked_sub(period * limit)) {
+            Some(floor) => deadline.max(floor),
+            None => deadline,
+        }
+    }
+}
+
 /// DMG-01 Game Boy emulator.
 #[derive(Debug, Default)]
 pub struct GameBoy {
     // State
     cycle: usize,
+    model: Model,
+    seed: Option<u64>,
+    inputs: BTreeMap<u64, JoypadState>,
+    movie: Option<Movie>,
+    pacing: Pacing,
     // Devices
     cart: Cartridge,
     cpu: Cpu,
     joypad: Joypad,
     pic: Rc<RefCell<Pic>>,
     ppu: Ppu,
+    serial: Serial,
     timer: Timer,
     // Memory
     mem: Memory,
     mmio: Mmio,
     mmu: Rc<RefCell<Bus>>,
+    bus: Rc<RefCell<Bus>>,
 }
 
 impl GameBoy {
@@ -58,193 +216,1161 @@ impl GameBoy {
     /// This will call [`Block::reset`] before returning, allowing emulation to
     /// begin without further prior setup.
     pub fn new(cart: Cartridge) -> Self {
+        Self::with_model(cart, Model::Dmg)
+    }
+
+    /// Constructs a new, reset `GameBoy` of the provided model.
+    ///
+    /// The model determines the boot ROM, initial register values, default
+    /// shade palette, and whether CGB registers are mapped.
+    ///
+    /// NOTE: As the CGB boot ROM is not bundled, CGB models start as if it
+    ///       had already run (see [`GameBoy::skip_boot`]).
+    pub fn with_model(cart: Cartridge, model: Model) -> Self {
+        Self::build(cart, model, None)
+    }
+
+    /// Constructs a new, reset `GameBoy` of the provided model.
+    ///
+    /// This is an alias of [`GameBoy::with_model`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gameboy_core::dmg::cart::Cartridge;
+    /// use gameboy_core::dmg::{GameBoy, Model};
+    ///
+    /// # fn build(cart: Cartridge) {
+    /// // Emulate a Game Boy Color
+    /// let emu = GameBoy::new_with_model(cart, Model::Cgb);
+    /// assert_eq!(emu.model(), Model::Cgb);
+    /// # }
+    /// ```
+    pub fn new_with_model(cart: Cartridge, model: Model) -> Self {
+        Self::with_model(cart, model)
+    }
+
+    /// Constructs a new, reset `GameBoy` whose randomized behaviour is derived
+    /// from the provided `seed`.
+    ///
+    /// Reads from unmapped addresses yield pseudo-random values (rather than
+    /// `0xff`), which are reproducible across runs with the same seed.
+    pub fn with_seed(cart: Cartridge, seed: u64) -> Self {
+        Self::build(cart, Model::Dmg, Some(seed))
+    }
+
+    /// Constructs a new, reset `GameBoy` of the provided model, whose
+    /// randomized behaviour is derived from the provided `seed`.
+    ///
+    /// See [`GameBoy::with_model`] and [`GameBoy::with_seed`].
+    pub fn with_model_and_seed(cart: Cartridge, model: Model, seed: u64) -> Self {
+        Self::build(cart, model, Some(seed))
+    }
+
+    /// Constructs a new, reset `GameBoy` from its configuration.
+    fn build(cart: Cartridge, model: Model, seed: Option<u64>) -> Self {
         let mut this = Self {
             cart,
+            model,
+            seed,
+            mem: Memory {
+                boot: Rc::new(RefCell::new(boot::Rom::with(model))),
+                ..Default::default()
+            },
             ..Default::default()
         };
+        this.ppu.set_shades(Shades::with(model));
         this.reset();
         this
     }
 
-    #[rustfmt::skip]
-    fn memmap(&mut self) {
-        // Prepare MMU
-        self.mmu.take();
-        let mut mmu = self.mmu.borrow_mut();
+    /// Gets the emulated hardware model.
+    #[must_use]
+    pub fn model(&self) -> Model {
+        self.model
+    }
 
-        // Prepare devices
-        let boot = self.mem.boot.clone();
-        let rom  = self.cart.rom().clone();
-        let vram = self.ppu.vram.clone();
-        let eram = self.cart.ram().clone();
-        let wram = self.mem.wram.clone();
-        let echo = View::new(wram.clone(), 0x0000..=0x1dff).to_shared();
-        let oam  = self.ppu.oam.clone();
-        let mmio = self.mmio.bus.clone();
-        let hram = self.mem.hram.clone();
-        let pic  = self.pic.borrow().enable.clone();
-        let unmapped = Unmapped::<0x10000>::new().to_shared();
-
-        // Map devices in MMU  // ┌──────────┬────────────┬─────┐
-                               // │   SIZE   │    NAME    │ DEV │
-                               // ├──────────┼────────────┼─────┤
-        mmu.map(0x0000, boot); // │    256 B │       Boot │ ROM │
-        mmu.map(0x0000, rom);  // │  32 Ki B │  Cartridge │ ROM │
-        mmu.map(0x8000, vram); // │   8 Ki B │      Video │ RAM │
-        mmu.map(0xa000, eram); // │   8 Ki B │   External │ RAM │
-        mmu.map(0xc000, wram); // │   8 Ki B │       Work │ RAM │
-        mmu.map(0xe000, echo); // │   7680 B │       Echo │ RAM │
-        mmu.map(0xfe00, oam);  // │    160 B │        OAM │ RAM │
-                               // │     96 B │   Unmapped │ --- │
-        mmu.map(0xff00, mmio); // │    128 B │        I/O │ Bus │
-        mmu.map(0xff80, hram); // │    127 B │       High │ RAM │
-        mmu.map(0xffff, pic);  // │      1 B │  Interrupt │ Reg │
-                               // └──────────┴────────────┴─────┘
-        // NOTE: use `Unmapped` as a fallback to report reads as `0xff`
-        mmu.map(0x0000, unmapped);
+    /// Inserts a new cartridge, returning the previously inserted one.
+    ///
+    /// Once inserted, the emulator is reset to begin running the new
+    /// cartridge. As the previous cartridge is returned to the caller, its
+    /// battery-backed RAM may still be saved.
+    pub fn insert(&mut self, rom: &[u8]) -> Result<Cartridge, cart::Error> {
+        let cart = Cartridge::new(rom)?;
+        let prev = std::mem::replace(&mut self.cart, cart);
+        self.reset();
+        Ok(prev)
     }
-}
 
-impl Block for GameBoy {
-    #[rustfmt::skip]
-    fn reset(&mut self) {
-        // Reset CPU
-        self.cpu.reset();
-        self.cpu.set_bus(self.mmu.clone()); // link MMU to CPU
+    /// Resets the emulator, as either a power cycle or a reset line pulse.
+    ///
+    /// A power cycle fills work and video RAM with pseudo-random values
+    /// (derived from the seed, if any), whereas a reset line pulse preserves
+    /// their contents. All other state is reset as by [`Block::reset`].
+    pub fn reset_with(&mut self, kind: ResetKind) {
+        // Save RAM contents
+        let wram = dump(&*self.mem.wram.borrow());
+        let wramx = dump(&*self.mem.wramx.borrow());
+        let vram = dump(&*self.ppu.vram.borrow());
 
-        // Reset cartridge
-        self.cart.reset();
+        // Reset the emulator
+        self.reset();
 
-        // Re-map I/O
-        self.mmio.con = self.joypad.con.clone();             // link I/O to joypad
-        self.mmio.timer = self.timer.ctl.clone();           // link I/O to timer registers
-        self.mmio.iflag = self.pic.borrow().active.clone();  // link I/O to IF register
-        self.mmio.lcd = self.ppu.ctl.clone();                // link I/O to LCD controller
-        self.mmio.boot = self.mem.boot.borrow().ctl.clone(); // link I/O to BOOT controller
-        self.mmio.reset();
+        // Restore RAM contents
+        let data = match kind {
+            ResetKind::PowerOn => {
+                let seed = self.seed.unwrap_or_default();
+                let noise = |base: u64, len: usize| {
+                    (base..)
+                        .take(len)
+                        .map(|addr| mix(seed ^ (addr << 8)) as u8)
+                        .collect::<Vec<_>>()
+                };
+                // NOTE: Upper WRAM banks are seeded as if they followed the
+                //       address space.
+                [
+                    noise(0xc000, wram.len()),
+                    noise(0x10000, wramx.len()),
+                    noise(0x8000, vram.len()),
+                ]
+            }
+            ResetKind::Reset => [wram, wramx, vram],
+        };
+        let mems: [SharedDevice; 3] = [
+            self.mem.wram.clone(),
+            self.mem.wramx.clone(),
+            self.ppu.vram.clone(),
+        ];
+        for (mem, data) in mems.into_iter().zip(data) {
+            let mut mem = mem.borrow_mut();
+            for (index, byte) in data.into_iter().enumerate() {
+                mem.write(index, byte);
+            }
+        }
+    }
 
-        // Reset memory
-        self.mem.reset();
+    /// Skips the boot ROM, replicating its side effects.
+    ///
+    /// This should be called on a freshly reset emulator, before any cycles
+    /// have been run. It sets up the following state, as would be left behind
+    /// by the DMG boot ROM:
+    /// - CPU: `AF=$01B0`, `BC=$0013`, `DE=$00D8`, `HL=$014D`, `SP=$FFFE`,
+    ///   `PC=$0100`. (`H` and `C` are cleared if the header checksum is zero.)
+    /// - I/O: `P1=$CF`, `SB=$00`, `SC=$7E`, `TIMA=$00`, `TMA=$00`, `TAC=$F8`,
+    ///   and `IF=$E1` (a VBlank interrupt is pending).
+    /// - Sound: the documented values read back from each register, such as
+    ///   `NR52=$F1`, `NR51=$F3`, and `NR50=$77`.
+    /// - LCD: `LCDC=$91`, `STAT=$85`, `SCY=$00`, `SCX=$00`, `LYC=$00`,
+    ///   `BGP=$FC`, `WY=$00`, `WX=$00`.
+    /// - Boot ROM: disabled by writing `BOOT=$01`.
+    /// - VRAM: the cartridge's logo is decoded into tiles `$01..=$18` at
+    ///   `$8010`, followed by the `®` in tile `$19`. These are placed on the
+    ///   background map at `$9904..=$990F`, `$9910`, and `$9924..=$992F`.
+    ///
+    /// Notably, `DIV` is left unchanged, as any write to it would reset it.
+    /// Likewise, `DMA` is not written, as that would start a transfer. As the
+    /// PPU starts the frame, `STAT` only reports VBlank until its first dot.
+    pub fn skip_boot(&mut self) {
+        // Set up CPU registers
+        let hc = if self.cart.header().hchk == 0 {
+            0x00
+        } else {
+            0x30
+        };
+        let (af, bc, de, hl) = match self.model {
+            Model::Dmg => (0x0180 | hc, 0x0013, 0x00d8, 0x014d),
+            Model::Mgb => (0xff80 | hc, 0x0013, 0x00d8, 0x014d),
+            Model::Cgb => (0x1180, 0x0000, 0xff56, 0x000d),
+        };
+        self.cpu.set_regs(CpuState {
+            af,
+            bc,
+            de,
+            hl,
+            sp: 0xfffe,
+            pc: 0x0100,
+        });
 
-        // Reset interrupts
-        self.pic.borrow_mut().reset();
-        self.cpu.set_pic(self.pic.clone());    // link PIC to CPU
-        self.joypad.set_pic(self.pic.clone()); // link PIC to joypad
-        self.ppu.set_pic(self.pic.clone());    // link PIC to PPU
-        self.timer.set_pic(self.pic.clone());  // link PIC to timer
+        // Set up I/O registers
+        #[rustfmt::skip]
+        let io = [
+            (IoReg::P1,   0x00),
+            (IoReg::Sb,   0x00),
+            (IoReg::Sc,   if let Model::Cgb = self.model { 0x7f } else { 0x7e }),
+            (IoReg::Tima, 0x00),
+            (IoReg::Tma,  0x00),
+            (IoReg::Tac,  0xf8),
+            (IoReg::If,   0xe1),
+            (IoReg::Nr10, 0x80),
+            (IoReg::Nr11, 0xbf),
+            (IoReg::Nr12, 0xf3),
+            (IoReg::Nr13, 0xff),
+            (IoReg::Nr14, 0xbf),
+            (IoReg::Nr21, 0x3f),
+            (IoReg::Nr22, 0x00),
+            (IoReg::Nr23, 0xff),
+            (IoReg::Nr24, 0xbf),
+            (IoReg::Nr30, 0x7f),
+            (IoReg::Nr31, 0xff),
+            (IoReg::Nr32, 0x9f),
+            (IoReg::Nr33, 0xff),
+            (IoReg::Nr34, 0xbf),
+            (IoReg::Nr41, 0xff),
+            (IoReg::Nr42, 0x00),
+            (IoReg::Nr43, 0x00),
+            (IoReg::Nr44, 0xbf),
+            (IoReg::Nr50, 0x77),
+            (IoReg::Nr51, 0xf3),
+            (IoReg::Nr52, 0xf1),
+            (IoReg::Scy,  0x00),
+            (IoReg::Scx,  0x00),
+            (IoReg::Lyc,  0x00),
+            (IoReg::Bgp,  0xfc),
+            (IoReg::Wy,   0x00),
+            (IoReg::Wx,   0x00),
+            (IoReg::Boot, 0x01),
+        ];
+        for (reg, value) in io {
+            self.io_write(reg, value);
+        }
 
-        // Reset joypad
-        self.joypad.reset();
+        // Set up VRAM
+        let mut vram = self.ppu.vram.borrow_mut();
+        // - Decode logo tiles, scaling up each nibble to a pair of rows
+        let scale = |nibble: u8| {
+            (0..4).fold(0, |row, bit| {
+                row | ((((nibble >> bit) & 1) * 0b11) << (2 * bit))
+            })
+        };
+        let logo = (0x0104..0x0134)
+            .map(|addr| self.cart.rom().borrow().read(addr))
+            .flat_map(|byte| [scale(byte >> 4), scale(byte & 0x0f)])
+            .flat_map(|row| [row, row]);
+        // - Append the trademark tile
+        let mark = boot::BOOTROM[0xd8..0xe0].iter().copied();
+        // - Write rows into the low bitplane
+        for (idx, row) in logo.chain(mark).enumerate() {
+            vram.write(0x0010 + 2 * idx, row);
+        }
+        // - Place tiles on the background map
+        vram.write(0x1910, 0x19);
+        for idx in 0..0x0c {
+            vram.write(0x1904 + idx, 0x01 + idx as u8);
+            vram.write(0x1924 + idx, 0x0d + idx as u8);
+        }
+        drop(vram);
 
-        // Reset PPU
-        self.ppu.set_bus(self.mmu.clone()); // link MMU to CPU
-        self.ppu.reset();
+        // Enable the LCD
+        self.io_write(IoReg::Lcdc, 0x91);
+        // NOTE: The boot ROM hands over as the PPU leaves VBlank, with LY=LYC.
+        self.ppu.set_stat(PpuMode::VBlank, true);
+    }
 
-        // Reset timer
-        self.timer.reset();
+    /// Performs a soft reset, as if the console's reset were triggered.
+    ///
+    /// Unlike [`Block::reset`], the contents of the cartridge's external RAM
+    /// are preserved, such that battery-backed saves are not lost.
+    pub fn soft_reset(&mut self) {
+        let eram = self.cart.save_ram();
+        self.reset();
+        self.cart.load_ram(&eram);
+    }
 
-        // Re-map MMU
-        self.memmap();
+    /// Exports the contents of the cartridge's external RAM (across all
+    /// banks).
+    ///
+    /// Allows frontends to manage battery-backed saves without file I/O.
+    #[must_use]
+    pub fn export_ram(&self) -> Vec<u8> {
+        self.cart.save_ram()
     }
-}
 
-impl Emulator for GameBoy {
-    type Input = Button;
+    /// Imports the contents of the cartridge's external RAM (across all
+    /// banks).
+    ///
+    /// # Errors
+    ///
+    /// Errors if the size of `data` does not match that of the RAM.
+    pub fn import_ram(&mut self, data: &[u8]) -> Result<(), RamError> {
+        let expected = self.cart.ram_size();
+        if data.len() != expected {
+            return Err(RamError::SizeMismatch {
+                expected,
+                found: data.len(),
+            });
+        }
+        self.cart.load_ram(data);
+        Ok(())
+    }
 
-    type Screen = Screen;
+    /// Dumps the contents of video RAM.
+    ///
+    /// NOTE: On the CGB, this only includes the first bank.
+    #[must_use]
+    pub fn dump_vram(&self) -> Vec<u8> {
+        dump(&*self.ppu.vram.borrow())
+    }
 
-    fn send(&mut self, keys: Vec<Self::Input>) {
-        self.joypad.input(keys);
+    /// Loads the contents of video RAM.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the size of `data` does not match that of the RAM.
+    pub fn load_vram(&mut self, data: &[u8]) -> Result<(), RamError> {
+        load(&mut *self.ppu.vram.borrow_mut(), data)
     }
 
-    fn redraw(&self, mut callback: impl FnMut(&Screen)) {
-        if self.ppu.ready() {
-            callback(self.ppu.screen())
+    /// Dumps the contents of object attribute memory.
+    #[must_use]
+    pub fn dump_oam(&self) -> Vec<u8> {
+        dump(&*self.ppu.oam.borrow())
+    }
+
+    /// Loads the contents of object attribute memory.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the size of `data` does not match that of the RAM.
+    pub fn load_oam(&mut self, data: &[u8]) -> Result<(), RamError> {
+        load(&mut *self.ppu.oam.borrow_mut(), data)
+    }
+
+    /// Disassembles the cartridge's ROM.
+    ///
+    /// Linearly disassembles every bank from the entry point (`0x0100`),
+    /// yielding each instruction with its address. Banks beyond the first are
+    /// addressed as if mapped at `0x4000..=0x7fff`.
+    ///
+    /// NOTE: As this is a static disassembly, data is disassembled as if it
+    ///       were code.
+    pub fn disasm_rom(&self) -> impl Iterator<Item = (u16, String)> {
+        let rom = self.cart.dump_rom();
+        let mut index = 0x0100;
+        std::iter::from_fn(move || {
+            let addr = match index {
+                0x0000..=0x3fff => index,
+                _ => 0x4000 | (index & 0x3fff),
+            } as u16;
+            let (text, len) = disasm(addr, rom.get(index..)?)?;
+            index += len;
+            Some((addr, text))
+        })
+    }
+
+    /// Queues the buttons to be pressed from the start of the provided frame.
+    ///
+    /// Frames are counted from power-on (or the most recent reset), with each
+    /// lasting [`FRAME`] cycles. The state replaces any buttons previously
+    /// pressed.
+    pub fn queue_input(&mut self, frame: u64, state: JoypadState) {
+        self.inputs.insert(frame, state);
+    }
+
+    /// Queues a script of inputs, as `(frame, state)` pairs.
+    ///
+    /// See [`GameBoy::queue_input`].
+    pub fn load_inputs(&mut self, script: &[(u64, JoypadState)]) {
+        self.inputs.extend(script.iter().cloned());
+    }
+
+    /// Begins recording the inputs sent to the emulator.
+    ///
+    /// Inputs are recorded from the frame in which they first apply. As queued
+    /// inputs only apply at the start of a frame, playback is exact so long as
+    /// inputs were sent between frames (as is the case with
+    /// [`GameBoy::run_with`]).
+    ///
+    /// As frames are counted from the most recent reset, resetting the
+    /// emulator restarts the recording.
+    pub fn start_recording(&mut self) {
+        self.movie = Some(Movie::default());
+    }
+
+    /// Gets the inputs recorded so far.
+    #[must_use]
+    pub fn movie(&self) -> Option<&Movie> {
+        self.movie.as_ref()
+    }
+
+    /// Writes the inputs recorded so far to a file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if inputs are not being recorded, or if the movie
+    /// could not be written.
+    pub fn save_movie(&self, path: &Path) -> io::Result<()> {
+        let movie = self
+            .movie
+            .as_ref()
+            .ok_or_else(|| io::Error::other("inputs are not being recorded"))?;
+        std::fs::write(path, movie.to_bytes())
+    }
+
+    /// Reads a movie from a file, queueing its inputs for playback.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the movie could not be read or is malformed.
+    pub fn load_movie(&mut self, path: &Path) -> io::Result<()> {
+        let movie = Movie::from_bytes(&std::fs::read(path)?)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        self.play(&movie);
+        Ok(())
+    }
+
+    /// Queues a movie's inputs for playback.
+    ///
+    /// See [`GameBoy::queue_input`].
+    pub fn play(&mut self, movie: &Movie) {
+        self.load_inputs(movie.inputs());
+    }
+
+    /// Starts recording a binary trace of each instruction executed to
+    /// `sink`.
+    ///
+    /// Should writing an entry fail, the error is logged and recording stops.
+    /// Traces can be reloaded using a [`TraceReader`].
+    ///
+    /// # Errors
+    ///
+    /// Errors if the trace's header could not be written.
+    pub fn start_trace(&mut self, sink: impl io::Write + 'static) -> io::Result<()> {
+        let mut trace = Some(TraceWriter::new(sink)?);
+        self.cpu.set_trace_hook(Box::new(move |regs, opcode| {
+            if let Some(writer) = &mut trace {
+                if let Err(err) = writer.record(&TraceEntry { regs, opcode }) {
+                    error!("Failed to record trace: {err}");
+                    trace = None;
+                }
+            }
+        }));
+        Ok(())
+    }
+
+    /// Stops recording a trace, dropping its sink.
+    pub fn stop_trace(&mut self) {
+        self.cpu.clear_trace_hook();
+    }
+
+    /// Runs the emulator for a fixed number of cycles.
+    ///
+    /// Useful for benchmarking headless runs, as no output is produced.
+    pub fn run_cycles(&mut self, cycles: usize) {
+        (0..cycles).for_each(|_| self.cycle());
+    }
+
+    /// Runs the emulator until the end of the current scanline.
+    ///
+    /// When called at the start of a scanline, this advances exactly one
+    /// scanline (456 dots). Returns the pixels of the completed scanline, or
+    /// `None` if it was not visible (i.e. during VBlank).
+    pub fn run_scanline(&mut self) -> Option<&[Color]> {
+        // Run until the next scanline begins
+        let ly = self.ppu.line() as usize;
+        for _ in 0..456 {
+            self.cycle();
+            if self.ppu.dot() == 0 {
+                break;
+            }
         }
+
+        // Extract the completed scanline
+        let width = SCREEN.width;
+        self.ppu.screen().get(width * ly..width * (ly + 1))
     }
-}
 
-impl Machine for GameBoy {
-    fn enabled(&self) -> bool {
-        self.cpu.enabled()
+    /// Executes the next instruction, stepping over any subroutine it calls.
+    ///
+    /// If the instruction is a `CALL` or `RST`, the emulator runs until the
+    /// subroutine returns to the following instruction. Stops early after
+    /// `max_cycles` cycles, or should the CPU break or lock up.
+    ///
+    /// Returns `false` if stopped early.
+    pub fn step_over(&mut self, max_cycles: usize) -> bool {
+        let mut budget = max_cycles;
+        // Determine where a call would return to
+        let CpuState { pc, sp, .. } = self.cpu.regs();
+        let ret = match self.mmu.borrow().read(pc as usize) {
+            0xc4 | 0xcc | 0xcd | 0xd4 | 0xdc => Some(pc.wrapping_add(3)),
+            opcode if opcode & 0xc7 == 0xc7 => Some(pc.wrapping_add(1)),
+            _ => None,
+        };
+        // Execute the instruction
+        if !self.step_inst(&mut budget) {
+            return false;
+        }
+        // Run until the subroutine returns
+        if let Some(ret) = ret {
+            loop {
+                let regs = self.cpu.regs();
+                if regs.pc == ret && regs.sp >= sp {
+                    break;
+                }
+                if !self.step_inst(&mut budget) {
+                    return false;
+                }
+            }
+        }
+        true
     }
 
-    fn cycle(&mut self) {
-        // CPU runs on a 1 MiHz clock: implement using a simple clock divider
-        if self.cycle % 4 == 0 {
-            // Wake disabled CPU if interrupts pending
-            if !self.cpu.enabled() && self.pic.borrow().int().is_some() {
-                self.cpu.wake();
+    /// Runs until the current subroutine returns to its caller.
+    ///
+    /// Stops early after `max_cycles` cycles, or should the CPU break or lock
+    /// up.
+    ///
+    /// Returns `false` if stopped early.
+    pub fn step_out(&mut self, max_cycles: usize) -> bool {
+        let mut budget = max_cycles;
+        let sp = self.cpu.regs().sp;
+        loop {
+            // Check if the next instruction is a return
+            let pc = self.cpu.regs().pc;
+            let ret = matches!(
+                self.mmu.borrow().read(pc as usize),
+                0xc0 | 0xc8 | 0xc9 | 0xd0 | 0xd8 | 0xd9
+            );
+            // Execute the instruction
+            if !self.step_inst(&mut budget) {
+                return false;
             }
-            // Cycle CPU if enabled
-            if self.cpu.enabled() {
-                self.cpu.cycle();
+            // Stop once the current stack frame has been popped
+            if ret && self.cpu.regs().sp > sp {
+                return true;
             }
         }
+    }
 
-        // PPU runs on a 4 MiHz clock
-        if self.ppu.enabled() {
-            self.ppu.cycle();
+    /// Runs until the CPU completes an instruction, within a budget of cycles.
+    fn step_inst(&mut self, budget: &mut usize) -> bool {
+        while *budget > 0 && !self.cpu.suspended() {
+            let cycles = self.cpu.cycles();
+            self.cycle();
+            *budget -= 1;
+            // Only check for completion once the CPU has been cycled
+            if self.cpu.cycles() != cycles && self.cpu.done() {
+                return true;
+            }
         }
+        false
+    }
 
-        // Timer runs on a 4 MiHz clock
-        if self.timer.enabled() {
-            self.timer.cycle();
+    /// Sets the speed of the emulated clock relative to real time.
+    ///
+    /// A factor of `2.0` runs twice as many cycles per second (and `0.5` half
+    /// as many), without otherwise affecting emulation. This is consulted when
+    /// pacing emulation in real time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `factor` is not positive.
+    pub fn set_clock_divider(&mut self, factor: f32) {
+        assert!(factor > 0.0, "clock factor must be positive: {factor}");
+        self.pacing.factor = factor;
+    }
+
+    /// Gets the speed of the emulated clock relative to real time.
+    #[must_use]
+    pub fn clock_divider(&self) -> f32 {
+        self.pacing.factor
+    }
+
+    /// Limits how far [`GameBoy::run_throttled`] and [`GameBoy::run_with`] may
+    /// catch up, in frames.
+    ///
+    /// When emulation falls behind by more than this, the remaining time is
+    /// dropped rather than run, keeping the host responsive. Use `None` to
+    /// catch up without limit (the default).
+    pub fn set_max_catchup(&mut self, frames: Option<u32>) {
+        self.pacing.limit = frames;
+    }
+
+    /// Runs the emulator for the time elapsed since the previous call.
+    ///
+    /// The elapsed time is measured by `clock`, and scaled according to
+    /// [`GameBoy::set_clock_divider`]. Returns the number of cycles run, which
+    /// is bounded by [`GameBoy::set_max_catchup`].
+    ///
+    /// NOTE: The first call only starts timing, so runs no cycles.
+    pub fn run_throttled(&mut self, clock: &dyn Clock) -> usize {
+        // Measure the elapsed time
+        let now = clock.now();
+        let Some(last) = self.pacing.last.replace(now) else {
+            return 0;
+        };
+        let elapsed = now.saturating_sub(last).as_secs_f64();
+
+        // Convert to whole cycles, carrying over any remainder
+        let owed = self.pacing.debt + elapsed * f64::from(FREQ) * f64::from(self.pacing.factor);
+        let cycles = owed.floor();
+        self.pacing.debt = owed - cycles;
+        let mut cycles = cycles as usize;
+
+        // Drop any time beyond the catch-up limit
+        if let Some(limit) = self.pacing.limit {
+            let limit = limit as usize * FRAME as usize;
+            if cycles > limit {
+                cycles = limit;
+                self.pacing.debt = 0.0;
+            }
         }
 
-        // Keep track of cycles executed
-        self.cycle = self.cycle.wrapping_add(1);
+        // Run for the elapsed time
+        self.run_cycles(cycles);
+        cycles
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use remus::Device;
+    /// Runs the emulator using a frontend, until it requests to stop.
+    ///
+    /// Once per frame, input is polled, then the completed frame is presented
+    /// and its audio queued. Frames are paced to the console's refresh rate
+    /// (~59.7 Hz), scaled by [`GameBoy::set_clock_divider`].
+    pub fn run_with(&mut self, frontend: &mut impl Frontend<Self>) {
+        let period = Duration::from_secs(FRAME.into()).div_f64(self.pacing.factor.into()) / FREQ;
+        let mut deadline = Instant::now();
+        while let Some(keys) = frontend.poll_input() {
+            // Run for a frame
+            self.send(keys);
+            self.run_cycles(FRAME as usize);
+            // Output the frame
+            frontend.present(self.ppu.screen());
+            // NOTE: Audio is not yet emulated, so no samples are produced.
+            frontend.queue_audio(&[]);
+            // Wait until the next frame is due
+            let now = Instant::now();
+            deadline = self.pacing.advance(deadline, now, period);
+            if let Some(wait) = deadline.checked_duration_since(now) {
+                std::thread::sleep(wait);
+            }
+        }
+    }
 
-    use super::*;
+    /// Saves a snapshot of the emulator's state.
+    #[must_use]
+    pub fn save_state(&self) -> Vec<u8> {
+        state::save(self)
+    }
 
-    fn setup() -> GameBoy {
-        // Define cartridge ROM
-        let rom: [u8; 0x150] = [
-            0xc3, 0x8b, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0xc3, 0x8b, 0x02, 0xff, 0xff, 0xff,
-            0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
-            0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x87, 0xe1,
-            0x5f, 0x16, 0x00, 0x19, 0x5e, 0x23, 0x56, 0xd5, 0xe1, 0xe9, 0xff, 0xff, 0xff, 0xff,
-            0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xc3, 0xfd, 0x01, 0xff, 0xff, 0xff,
-            0xff, 0xff, 0xc3, 0x12, 0x27, 0xff, 0xff, 0xff, 0xff, 0xff, 0xc3, 0x12, 0x27, 0xff,
-            0xff, 0xff, 0xff, 0xff, 0xc3, 0x7e, 0x01, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
-            0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
-            0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
-            0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
-            0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
-            0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
-            0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
-            0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
-            0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
-            0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
-            0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
-            0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
-            0xff, 0xff, 0xff, 0xff, 0x00, 0xc3, 0x50, 0x01, 0xce, 0xed, 0x66, 0x66, 0xcc, 0x0d,
-            0x00, 0x0b, 0x03, 0x73, 0x00, 0x83, 0x00, 0x0c, 0x00, 0x0d, 0x00, 0x08, 0x11, 0x1f,
-            0x88, 0x89, 0x00, 0x0e, 0xdc, 0xcc, 0x6e, 0xe6, 0xdd, 0xdd, 0xd9, 0x99, 0xbb, 0xbb,
-            0x67, 0x63, 0x6e, 0x0e, 0xec, 0xcc, 0xdd, 0xdc, 0x99, 0x9f, 0xbb, 0xb9, 0x33, 0x3e,
-            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-            0x00, 0x00, 0x00, 0x00, 0x00, 0x08, 0x00, 0x02, 0x01, 0x00, 0x00, 0xdc, 0x31, 0xbb,
-        ];
-        let cart = Cartridge::new(&rom).unwrap();
-        // Create a default GameBoy instance
-        GameBoy::new(cart)
+    /// Restores a snapshot of the emulator's state.
+    ///
+    /// Along with the contents of memory, this restores internal state such as
+    /// the CPU's IME and run status, the selected cartridge banks, and the
+    /// PPU's position within the frame.
+    ///
+    /// NOTE: Version 1 states lack this internal state, which is left
+    ///       untouched when loading them.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the state's version is unsupported, if it is corrupt, if its
+    /// size doesn't match this emulator's, or if it was saved from a cartridge
+    /// with a different MBC.
+    pub fn load_state(&mut self, state: &[u8]) -> Result<(), StateError> {
+        state::load(self, state)
     }
 
-    #[test]
-    fn boot_disable_works() {
-        let emu = setup();
+    /// Captures a decoded snapshot of the emulator's state.
+    ///
+    /// This holds the same state as [`GameBoy::save_state`].
+    #[must_use]
+    pub fn snapshot(&self) -> Snapshot {
+        state::snapshot(self)
+    }
 
-        // Ensure boot ROM starts enabled:
-        // - Perform comparison against boot ROM contents
-        (0x0000..=0x0100)
-            .map(|addr| emu.mmu.borrow().read(addr))
-            .zip([
+    /// Restores a decoded snapshot of the emulator's state.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the snapshot's size doesn't match this emulator's, or if it
+    /// was captured from a cartridge with a different MBC.
+    pub fn restore(&mut self, snap: &Snapshot) -> Result<(), StateError> {
+        state::restore(self, snap)
+    }
+
+    /// Compares two save states, reporting the regions where they differ.
+    ///
+    /// Both states must be of the same version and size.
+    pub fn diff_state(a: &[u8], b: &[u8]) -> Result<Vec<StateDiff>, StateError> {
+        state::diff(a, b)
+    }
+
+    /// Captures a snapshot of RAM contents.
+    ///
+    /// Includes video, external, work, object attribute, and high RAM.
+    #[must_use]
+    pub fn bus_snapshot(&self) -> BusSnapshot {
+        // NOTE: Only the selected bank of external RAM is mapped.
+        let eram = self.cart.ram().borrow().len().min(0x2000);
+        let mmu = self.mmu.borrow();
+        BusSnapshot(
+            [
+                0x8000..0xa000,
+                0xa000..0xa000 + eram,
+                0xc000..0xe000,
+                0xfe00..0xfea0,
+                0xff80..0xffff,
+            ]
+            .into_iter()
+            .flatten()
+            .map(|addr| (addr as u16, mmu.read(addr)))
+            .collect(),
+        )
+    }
+
+    /// Gets a reference to the cartridge.
+    pub fn cart(&self) -> &Cartridge {
+        &self.cart
+    }
+
+    /// Gets a reference to the CPU.
+    pub fn cpu(&self) -> &Cpu {
+        &self.cpu
+    }
+
+    /// Gets a mutable reference to the CPU.
+    pub fn cpu_mut(&mut self) -> &mut Cpu {
+        &mut self.cpu
+    }
+
+    /// Gets the number of times each opcode has executed since reset.
+    ///
+    /// The first 256 entries count base opcodes, and the remaining 256 count
+    /// `0xcb`-prefixed opcodes.
+    #[must_use]
+    pub fn opcode_histogram(&self) -> [u64; 0x200] {
+        self.cpu.stats().opcodes
+    }
+
+    /// Gets a reference to the PPU.
+    pub fn ppu(&self) -> &Ppu {
+        &self.ppu
+    }
+
+    /// Gets a mutable reference to the PPU.
+    ///
+    /// Allows the PPU to be cycled independently of the rest of the system.
+    pub fn ppu_mut(&mut self) -> &mut Ppu {
+        &mut self.ppu
+    }
+
+    /// Enables (or disables) capturing of bytes sent over the serial port.
+    ///
+    /// Useful for test ROMs, which often report their results over serial.
+    pub fn set_serial_capture(&mut self, enable: bool) {
+        self.serial.capture(enable);
+    }
+
+    /// Sets a callback to be invoked at the end of each visible scanline.
+    ///
+    /// The callback receives the scanline (`LY`) and its pixels, allowing
+    /// mid-frame (raster) effects to be captured.
+    pub fn set_scanline_callback(&mut self, callback: Box<dyn FnMut(u8, &[Color; SCREEN.width])>) {
+        self.ppu.on_scanline(callback);
+    }
+
+    /// Sets a callback to be invoked with each byte sent over the serial port.
+    ///
+    /// Useful for streaming debug output, as bytes are reported as soon as
+    /// they have been shifted out (irrespective of any link partner).
+    pub fn on_serial_byte(&mut self, callback: Box<dyn FnMut(u8)>) {
+        self.serial.set_hook(callback);
+    }
+
+    /// Gets the bytes sent over the serial port since capturing was enabled.
+    #[must_use]
+    pub fn serial_output(&self) -> &str {
+        self.serial.output()
+    }
+
+    /// Sets the output color of each of the LCD's shades.
+    ///
+    /// Shades are ordered from lightest to darkest, and are output verbatim by
+    /// [`GameBoy::frame_rgba`] (defaulting to `0x00RRGGBB` colors). Unlike the
+    /// BGP and OBP registers, this is independent of the running game.
+    pub fn set_shade_palette(&mut self, shades: [u32; 4]) {
+        self.ppu.set_shades(Shades(shades));
+    }
+
+    /// Sets a custom renderer to receive each pixel as it is drawn.
+    ///
+    /// The internal framebuffer (see [`Ppu::screen`]) is still updated.
+    pub fn set_renderer(&mut self, render: Box<dyn Renderer>) {
+        self.ppu.set_renderer(render);
+    }
+
+    /// Converts the screen's pixels to output colors.
+    ///
+    /// See [`GameBoy::set_shade_palette`].
+    #[must_use]
+    pub fn frame_rgba(&self) -> Vec<u32> {
+        self.ppu.frame_rgba()
+    }
+
+    /// Sets the format of the PPU's output framebuffer.
+    ///
+    /// See [`GameBoy::frame_rgba8888`].
+    pub fn set_frame_format(&mut self, fmt: FrameFormat) {
+        self.ppu.set_frame_format(fmt);
+    }
+
+    /// Gets the screen's pixels as RGBA bytes, ready to be uploaded.
+    ///
+    /// Returns `None` unless the [`FrameFormat::Rgba8888`] format is selected.
+    #[must_use]
+    pub fn frame_rgba8888(&self) -> Option<&[u8]> {
+        self.ppu.frame_rgba8888()
+    }
+
+    /// Writes the current frame to a PNG image.
+    ///
+    /// Pixels are colored using the shade palette (see
+    /// [`GameBoy::set_shade_palette`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no frame has been rendered yet, or if the image
+    /// could not be written.
+    pub fn screenshot(&self, path: &Path) -> io::Result<()> {
+        if self.ppu.frames() == 0 {
+            return Err(io::Error::other("no frame has been rendered"));
+        }
+        let rgba: Vec<u8> = self
+            .ppu
+            .frame_rgba()
+            .into_iter()
+            .flat_map(|color| {
+                let [_, r, g, b] = color.to_be_bytes();
+                [r, g, b, 0xff]
+            })
+            .collect();
+        let png = png::encode(SCREEN.width as u32, SCREEN.height as u32, &rgba);
+        std::fs::write(path, png)
+    }
+
+    /// Gets the CPU's most recently logged bus accesses.
+    ///
+    /// Logging must first be enabled with [`Cpu::set_access_log`].
+    pub fn access_log(&self) -> &[BusAccess] {
+        self.cpu.access_log()
+    }
+
+    /// Reads the value of an I/O register.
+    pub fn io_read(&self, reg: IoReg) -> u8 {
+        self.mmu.borrow().read(u16::from(reg) as usize)
+    }
+
+    /// Writes a value to an I/O register.
+    pub fn io_write(&mut self, reg: IoReg, value: u8) {
+        self.mmu.borrow_mut().write(u16::from(reg) as usize, value);
+    }
+
+    /// Gets the current memory map.
+    ///
+    /// Regions are reported in ascending order, reflecting whether the boot
+    /// ROM is currently mapped over the cartridge.
+    pub fn memory_map(&self) -> Vec<MemRegion> {
+        // Check if the boot ROM has been disabled
+        let boot = self.io_read(IoReg::Boot) == 0;
+
+        // Describe mapped regions
+        let mut map: Vec<_> = MEMMAP
+            .into_iter()
+            .skip(usize::from(!boot))
+            .map(|(start, end, name, kind)| MemRegion {
+                start,
+                end,
+                name,
+                kind,
+                blocked: arbiter::blocked(start.into()),
+            })
+            .collect();
+        // Trim regions shadowed by the ones mapped before them
+        for idx in 1..map.len() {
+            map[idx].start = map[idx].start.max(map[idx - 1].end + 1);
+        }
+        map
+    }
+
+    #[rustfmt::skip]
+    fn memmap(&mut self) {
+        // Prepare MMU
+        self.mmu.take();
+        let mut mmu = self.mmu.borrow_mut();
+
+        // Prepare devices
+        let boot = self.mem.boot.clone();
+        let rom  = self.cart.rom().clone();
+        let vram: SharedDevice = match self.model {
+            Model::Cgb => self.ppu.vbk.borrow().vram(),
+            _ => self.ppu.vram.clone(),
+        };
+        let eram = self.cart.ram().clone();
+        let wram: SharedDevice = match self.model {
+            Model::Cgb => {
+                // NOTE: The CGB maps the selected bank over the upper half.
+                let mut bus = Bus::default();
+                bus.map(0x1000, self.mem.svbk.borrow().wram());
+                bus.map(0x0000, self.mem.wram.clone());
+                bus.to_shared()
+            }
+            _ => self.mem.wram.clone(),
+        };
+        let echo = View::new(wram.clone(), 0x0000..=0x1dff).to_shared();
+        let oam  = self.ppu.oam.clone();
+        let none = Null::<0x60>::with(0x00).to_shared();
+        let mmio = self.mmio.bus.clone();
+        let hram = self.mem.hram.clone();
+        let pic  = self.pic.borrow().enable.clone();
+        let last = Rc::new(Cell::new(0));
+        let unmapped = match self.seed {
+            Some(seed) => Unmapped::<0x10000>::with_seed(seed, last.clone()),
+            None => Unmapped::<0x10000>::new(),
+        }
+        .to_shared();
+
+        // Map devices in MMU
+        // NOTE: Devices are listed in the same order as `MEMMAP`.
+        let devs: [SharedDevice; MEMMAP.len()] = [
+            boot, rom, vram, eram, wram, echo, oam, none, mmio, hram, pic,
+        ];
+        for ((base, ..), dev) in MEMMAP.into_iter().zip(devs) {
+            mmu.map(base.into(), dev);
+        }
+        // NOTE: use `Unmapped` as a fallback to report reads as `0xff` (or
+        //       seeded values)
+        mmu.map(0x0000, unmapped);
+
+        // Arbitrate CPU accesses with the DMA
+        let arbiter = Arbiter {
+            mmu: self.mmu.clone(),
+            lcd: self.ppu.ctl.clone(),
+            last,
+        };
+        self.bus.take();
+        self.bus.borrow_mut().map(0x0000, arbiter.to_shared());
+    }
+}
+
+impl Block for GameBoy {
+    #[rustfmt::skip]
+    fn reset(&mut self) {
+        // Reset frame count
+        // NOTE: As frames are counted from the reset, queued inputs are
+        //       discarded.
+        self.cycle = 0;
+        self.inputs.clear();
+        // NOTE: Likewise, a recording in progress restarts.
+        if let Some(movie) = &mut self.movie {
+            *movie = Movie::default();
+        }
+
+        // Reset CPU
+        self.cpu.reset();
+        self.cpu.set_bus(self.bus.clone()); // link arbiter to CPU
+        self.cpu.set_mem(self.mmu.clone()); // link MMU to CPU
+        let lcd = self.ppu.ctl.clone();
+        self.cpu.set_idu_hook(Box::new(move |addr| arbiter::idu(&lcd, addr))); // link IDU to LCD controller
+
+        // Reset cartridge
+        self.cart.reset();
+
+        // Re-map I/O
+        self.mmio.con = self.joypad.con.clone();             // link I/O to joypad
+        self.mmio.com = self.serial.ctl.clone();             // link I/O to serial registers
+        self.mmio.timer = self.timer.ctl.clone();           // link I/O to timer registers
+        self.mmio.iflag = self.pic.borrow().active.clone();  // link I/O to IF register
+        self.mmio.lcd = self.ppu.ctl.clone();                // link I/O to LCD controller
+        self.mmio.boot = self.mem.boot.borrow().ctl.clone(); // link I/O to BOOT controller
+        self.mmio.vbk = self.ppu.vbk.clone();                // link I/O to VRAM bank
+        self.mmio.svbk = self.mem.svbk.clone();              // link I/O to WRAM bank
+        self.mmio.bcp = self.ppu.bcp.clone();                // link I/O to BG palettes
+        self.mmio.ocp = self.ppu.ocp.clone();                // link I/O to OBJ palettes
+        self.mmio.model = self.model;
+        self.mmio.reset();
+
+        // Reset memory
+        self.mem.reset();
+
+        // Reset interrupts
+        self.pic.borrow_mut().reset();
+        self.cpu.set_pic(self.pic.clone());    // link PIC to CPU
+        self.joypad.set_pic(self.pic.clone()); // link PIC to joypad
+        self.ppu.set_pic(self.pic.clone());    // link PIC to PPU
+        self.serial.set_pic(self.pic.clone()); // link PIC to serial
+        self.timer.set_pic(self.pic.clone());  // link PIC to timer
+
+        // Reset joypad
+        self.joypad.reset();
+
+        // Reset PPU
+        self.ppu.set_bus(self.mmu.clone()); // link MMU to CPU
+        self.ppu.reset();
+
+        // Reset serial
+        self.serial.reset();
+
+        // Reset timer
+        self.timer.reset();
+
+        // Re-map MMU
+        self.memmap();
+
+        // Skip the boot ROM (if not bundled)
+        if let Model::Cgb = self.model {
+            self.skip_boot();
+        }
+    }
+}
+
+impl Emulator for GameBoy {
+    type Input = Button;
+
+    type Screen = Screen;
+
+    fn send(&mut self, keys: Vec<Self::Input>) {
+        if let Some(movie) = &mut self.movie {
+            let frame = self.cycle.div_ceil(FRAME as usize) as u64;
+            movie.record(frame, keys.clone());
+        }
+        self.joypad.input(keys);
+    }
+
+    fn redraw(&self, mut callback: impl FnMut(&Screen)) {
+        if self.ppu.ready() {
+            callback(self.ppu.screen())
+        }
+    }
+}
+
+impl Machine for GameBoy {
+    fn enabled(&self) -> bool {
+        self.cpu.enabled()
+    }
+
+    fn cycle(&mut self) {
+        // Apply queued inputs at the start of each frame
+        if self.cycle % FRAME as usize == 0 {
+            let frame = (self.cycle / FRAME as usize) as u64;
+            while let Some(entry) = self.inputs.first_entry() {
+                if *entry.key() > frame {
+                    break;
+                }
+                self.joypad.input(entry.remove());
+            }
+        }
+
+        // CPU runs on a 1 MiHz clock: implement using a simple clock divider
+        if self.cycle % 4 == 0 {
+            // Wake disabled CPU if interrupts pending
+            if !self.cpu.enabled() && self.pic.borrow().int().is_some() {
+                self.cpu.wake();
+            }
+            // Resume stopped CPU if buttons pressed
+            if !self.cpu.enabled() && self.joypad.pressed() {
+                self.cpu.press();
+            }
+            // Cycle CPU if enabled
+            if self.cpu.enabled() {
+                self.cpu.cycle();
+            }
+        }
+
+        // PPU runs on a 4 MiHz clock
+        if self.ppu.enabled() {
+            self.ppu.cycle();
+        }
+
+        // DMA runs on a 1 MiHz clock
+        if self.cycle % 4 == 0 {
+            self.ppu.cycle_dma();
+        }
+
+        // Serial runs on a 4 MiHz clock
+        if self.serial.enabled() {
+            self.serial.cycle();
+        }
+
+        // Timer runs on a 4 MiHz clock
+        if self.timer.enabled() {
+            self.timer.cycle();
+        }
+
+        // Keep track of cycles executed
+        self.cycle = self.cycle.wrapping_add(1);
+    }
+}
+
+/// Dumps the contents of a memory block.
+fn dump(mem: &impl Device) -> Vec<u8> {
+    (0..mem.len()).map(|index| mem.read(index)).collect()
+}
+
+/// Loads the contents of a memory block.
+fn load(mem: &mut impl Device, data: &[u8]) -> Result<(), RamError> {
+    let expected = mem.len();
+    if data.len() != expected {
+        return Err(RamError::SizeMismatch {
+            expected,
+            found: data.len(),
+        });
+    }
+    for (index, &byte) in data.iter().enumerate() {
+        mem.write(index, byte);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use remus::Device;
+
+    use super::*;
+    use crate::emu::Headless;
+
+    /// Cartridge ROM used for testing.
+    const ROM: [u8; 0x150] = [
+        0xc3, 0x8b, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0xc3, 0x8b, 0x02, 0xff, 0xff, 0xff, 0xff,
+        0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+        0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x87, 0xe1, 0x5f, 0x16, 0x00,
+        0x19, 0x5e, 0x23, 0x56, 0xd5, 0xe1, 0xe9, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+        0xff, 0xff, 0xff, 0xff, 0xc3, 0xfd, 0x01, 0xff, 0xff, 0xff, 0xff, 0xff, 0xc3, 0x12, 0x27,
+        0xff, 0xff, 0xff, 0xff, 0xff, 0xc3, 0x12, 0x27, 0xff, 0xff, 0xff, 0xff, 0xff, 0xc3, 0x7e,
+        0x01, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+        0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+        0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+        0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+        0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+        0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+        0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+        0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+        0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+        0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+        0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+        0xff, 0x00, 0xc3, 0x50, 0x01, 0xce, 0xed, 0x66, 0x66, 0xcc, 0x0d, 0x00, 0x0b, 0x03, 0x73,
+        0x00, 0x83, 0x00, 0x0c, 0x00, 0x0d, 0x00, 0x08, 0x11, 0x1f, 0x88, 0x89, 0x00, 0x0e, 0xdc,
+        0xcc, 0x6e, 0xe6, 0xdd, 0xdd, 0xd9, 0x99, 0xbb, 0xbb, 0x67, 0x63, 0x6e, 0x0e, 0xec, 0xcc,
+        0xdd, 0xdc, 0x99, 0x9f, 0xbb, 0xb9, 0x33, 0x3e, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x08, 0x00, 0x02,
+        0x01, 0x00, 0x00, 0xdc, 0x31, 0xbb,
+    ];
+
+    fn setup() -> GameBoy {
+        // Define cartridge ROM
+        let rom = ROM;
+        let cart = Cartridge::new(&rom).unwrap();
+        // Create a default GameBoy instance
+        GameBoy::new(cart)
+    }
+
+    #[test]
+    fn boot_disable_works() {
+        let emu = setup();
+
+        // Ensure boot ROM starts enabled:
+        // - Perform comparison against boot ROM contents
+        (0x0000..=0x0100)
+            .map(|addr| emu.mmu.borrow().read(addr))
+            .zip([
                 0x31, 0xfe, 0xff, 0xaf, 0x21, 0xff, 0x9f, 0x32, 0xcb, 0x7c, 0x20, 0xfb, 0x21, 0x26,
                 0xff, 0x0e, 0x11, 0x3e, 0x80, 0x32, 0xe2, 0x0c, 0x3e, 0xf3, 0xe2, 0x32, 0x3e, 0x77,
                 0x77, 0x3e, 0xfc, 0xe0, 0x47, 0x11, 0x04, 0x01, 0x21, 0x10, 0x80, 0x1a, 0xcd, 0x95,
@@ -379,15 +1505,17 @@ mod tests {
                 .for_each(|byte| assert_eq!(byte, 0x66));
             // LCD
             (0xff40..=0xff4b).for_each(|addr| emu.mmu.borrow_mut().write(addr, 0x67));
+            // NOTE: Only STAT bits 3-6 are writable, and bit 7 reads as set
+            let lcd = |addr| if addr & 0xff == 0x41 { 0xe0 } else { 0x67 };
             (0x40..=0x4b)
-                .map(|addr| emu.mmio.bus.borrow().read(addr))
-                .for_each(|byte| assert_eq!(byte, 0x67));
+                .map(|addr| (addr, emu.mmio.bus.borrow().read(addr)))
+                .for_each(|(addr, byte)| assert_eq!(byte, lcd(addr)));
             (0x00..=0x0b)
-                .map(|addr| emu.mmio.lcd.borrow().read(addr))
-                .for_each(|byte| assert_eq!(byte, 0x67));
+                .map(|addr| (addr, emu.mmio.lcd.borrow().read(addr)))
+                .for_each(|(addr, byte)| assert_eq!(byte, lcd(0x40 + addr)));
             (0x00..=0x0b)
-                .map(|addr| emu.ppu.ctl.borrow().read(addr))
-                .for_each(|byte| assert_eq!(byte, 0x67));
+                .map(|addr| (addr, emu.ppu.ctl.borrow().read(addr)))
+                .for_each(|(addr, byte)| assert_eq!(byte, lcd(0x40 + addr)));
             // Boot ROM Disable
             (0xff50..=0xff50).for_each(|addr| emu.mmu.borrow_mut().write(addr, 0x68));
             (0x50..=0x50)
@@ -413,32 +1541,1464 @@ mod tests {
     }
 
     #[test]
-    #[should_panic]
-    fn mmu_boot_write_panics() {
-        let emu = setup();
+    fn insert_works() {
+        let mut emu = setup();
+        (0..0x1000).for_each(|_| emu.cycle());
+        assert_ne!(emu.cpu.regs().pc, 0x0000);
 
-        // Write to boot ROM (should panic)
-        emu.mmu.borrow_mut().write(0x0000, 0xaa);
+        // Prepare a cartridge with a different title
+        let rom = cart::rom(0x8000, 0x00, b"TEST");
+
+        // Hot-swap the cartridge
+        let prev = emu.insert(&rom).unwrap();
+        assert_eq!(prev.header().title, "\0".repeat(16));
+        assert_eq!(
+            emu.cart().header().title,
+            format!("TEST{}", "\0".repeat(12))
+        );
+        // Emulation restarts from the boot ROM
+        assert_eq!(emu.cpu.regs().pc, 0x0000);
+        assert_eq!(emu.mmu.borrow().read(0x0000), 0x31);
     }
 
     #[test]
-    fn mmu_unmapped_works() {
-        let emu = setup();
-
-        // Disable boot ROM
-        emu.mmu.borrow_mut().write(0xff50, 0x01);
+    fn with_seed_works() {
+        // Prepare a cartridge without external RAM
+        let rom = cart::rom(0x8000, 0x00, b"");
+        let read = |emu: GameBoy| {
+            (0xa000..0xa010)
+                .map(|addr| emu.mmu.borrow().read(addr))
+                .collect::<Vec<_>>()
+        };
 
-        // Define unmapped addresses
-        let unmapped = [0xfea0..=0xfeff, 0xff03..=0xff03, 0xff27..=0xff2f];
+        // Unmapped reads are reproducible with the same seed
+        let a = GameBoy::with_seed(Cartridge::new(&rom).unwrap(), 0x5eed);
+        let b = GameBoy::with_seed(Cartridge::new(&rom).unwrap(), 0x5eed);
+        assert_eq!(read(a), read(b));
+        // Without a seed, unmapped reads are fixed
+        let c = GameBoy::new(Cartridge::new(&rom).unwrap());
+        assert_eq!(read(c), [0xff; 0x10]);
+        // Seeds apply to any model
+        let d = GameBoy::with_model_and_seed(Cartridge::new(&rom).unwrap(), Model::Cgb, 0x5eed);
+        assert_eq!(d.model(), Model::Cgb);
+        let e = GameBoy::with_seed(Cartridge::new(&rom).unwrap(), 0x5eed);
+        assert_eq!(read(d), read(e));
+    }
 
-        // Test unmapped addresses
-        for gap in unmapped {
-            for addr in gap {
-                // Write to every unmapped address
-                emu.mmu.borrow_mut().write(addr, 0xaa);
-                // Check the write didn't work
-                assert_eq!(emu.mmu.borrow().read(addr), 0xff);
+    #[test]
+    fn with_seed_peek_works() {
+        // Prepare a cartridge without external RAM
+        let rom = cart::rom(0x8000, 0x00, b"");
+        let run = |peek: bool| {
+            let mut emu = GameBoy::with_seed(Cartridge::new(&rom).unwrap(), 0x5eed);
+            // Copy unmapped reads into WRAM
+            #[rustfmt::skip]
+            let prog = [
+                0x21, 0x00, 0xa0, // LD HL, $a000
+                0x11, 0x00, 0xc1, // LD DE, $c100
+                0x2a, 0x12, 0x1c, // LD A, (HL+); LD (DE), A; INC E
+                0x2a, 0x12, 0x1c, // LD A, (HL+); LD (DE), A; INC E
+                0x2a, 0x12, 0x1c, // LD A, (HL+); LD (DE), A; INC E
+                0x2a, 0x12, 0x1c, // LD A, (HL+); LD (DE), A; INC E
+            ];
+            for (addr, &byte) in (0xc000..).zip(prog.iter()) {
+                emu.mmu.borrow_mut().write(addr, byte);
             }
-        }
+            let regs = emu.cpu().regs();
+            emu.cpu_mut().set_regs(CpuState { pc: 0xc000, ..regs });
+            // Peek memory (as debugging does) between each cycle
+            if peek {
+                emu.cpu_mut().set_trace_hook(Box::new(|_, _| {}));
+            }
+            while emu.cpu().regs().pc < 0xc000 + prog.len() as u16 {
+                if peek {
+                    let _ = emu.cpu().next_cycles();
+                }
+                emu.cycle();
+            }
+            (0xc100..0xc104)
+                .map(|addr| emu.mmu.borrow().read(addr))
+                .collect::<Vec<_>>()
+        };
+
+        // Peeking must not disturb seeded reads
+        assert_eq!(run(false), run(true));
+    }
+
+    #[test]
+    fn reset_with_works() {
+        let wram = |emu: &GameBoy| dump(&*emu.mem.wram.borrow());
+        let mut emu = GameBoy::with_seed(Cartridge::new(&ROM).unwrap(), 0x5eed);
+
+        // A reset line pulse preserves RAM
+        emu.mmu.borrow_mut().write(0xc000, 0x42);
+        emu.mmu.borrow_mut().write(0x8000, 0x24);
+        emu.reset_with(ResetKind::Reset);
+        assert_eq!(emu.mmu.borrow().read(0xc000), 0x42);
+        assert_eq!(emu.mmu.borrow().read(0x8000), 0x24);
+        // Whereas a bare reset clears it
+        emu.reset();
+        assert_eq!(wram(&emu), [0; 0x2000]);
+
+        // Power-on randomizes RAM, reproducibly with the same seed
+        emu.reset_with(ResetKind::PowerOn);
+        let seeded = wram(&emu);
+        assert_ne!(seeded, [0; 0x2000]);
+        let mut other = GameBoy::with_seed(Cartridge::new(&ROM).unwrap(), 0x5eed);
+        other.reset_with(ResetKind::PowerOn);
+        assert_eq!(wram(&other), seeded);
+        let mut other = GameBoy::with_seed(Cartridge::new(&ROM).unwrap(), 0xcafe);
+        other.reset_with(ResetKind::PowerOn);
+        assert_ne!(wram(&other), seeded);
+    }
+
+    #[test]
+    fn boot_logo_check_works() {
+        let boot = |cart: Cartridge| {
+            let mut emu = GameBoy::new(cart);
+            // Jump to the boot ROM's logo check
+            let regs = emu.cpu().regs();
+            emu.cpu_mut().set_regs(CpuState { pc: 0x00e0, ..regs });
+            emu.run_cycles(0x2000);
+            emu
+        };
+
+        // A valid logo boots the cartridge
+        let cart = Cartridge::new(&ROM).unwrap();
+        assert!(cart.header().logo_valid());
+        let emu = boot(cart);
+        assert_eq!(emu.io_read(IoReg::Boot), 0x01);
+
+        // A corrupted logo locks up
+        let mut rom = ROM;
+        rom[0x0110] ^= 0xff;
+        let cart = Cartridge::new(&rom).unwrap();
+        assert!(!cart.header().logo_valid());
+        let emu = boot(cart);
+        assert_eq!(emu.io_read(IoReg::Boot), 0x00);
+        assert!((0x00e9..=0x00eb).contains(&emu.cpu().regs().pc));
+    }
+
+    #[test]
+    fn skip_boot_works() {
+        let mut emu = setup();
+        emu.skip_boot();
+
+        // Check CPU registers
+        assert_eq!(
+            emu.cpu.regs(),
+            CpuState {
+                af: 0x01b0,
+                bc: 0x0013,
+                de: 0x00d8,
+                hl: 0x014d,
+                sp: 0xfffe,
+                pc: 0x0100,
+            }
+        );
+        // Check I/O registers
+        #[rustfmt::skip]
+        let io = [
+            (IoReg::P1,   0xcf),
+            (IoReg::Sb,   0x00),
+            (IoReg::Sc,   0x7e),
+            (IoReg::Tima, 0x00),
+            (IoReg::Tma,  0x00),
+            (IoReg::Tac,  0xf8),
+            (IoReg::If,   0xe1),
+            (IoReg::Nr10, 0x80),
+            (IoReg::Nr11, 0xbf),
+            (IoReg::Nr12, 0xf3),
+            (IoReg::Nr14, 0xbf),
+            (IoReg::Nr30, 0x7f),
+            (IoReg::Nr50, 0x77),
+            (IoReg::Nr51, 0xf3),
+            (IoReg::Nr52, 0xf1),
+            (IoReg::Lcdc, 0x91),
+            (IoReg::Stat, 0x85),
+            (IoReg::Scy,  0x00),
+            (IoReg::Scx,  0x00),
+            (IoReg::Ly,   0x00),
+            (IoReg::Lyc,  0x00),
+            (IoReg::Bgp,  0xfc),
+            (IoReg::Wy,   0x00),
+            (IoReg::Wx,   0x00),
+            (IoReg::Boot, 0x01),
+            (IoReg::Ie,   0x00),
+        ];
+        for (reg, value) in io {
+            assert_eq!(emu.io_read(reg), value, "{reg:?}");
+        }
+        // STAT is maintained by the PPU once it runs
+        emu.cycle();
+        assert_eq!(emu.io_read(IoReg::Stat), 0x86);
+        assert_eq!(emu.memory_map()[0].name, "Cartridge");
+        // Check logo tiles
+        let vram = emu.ppu.vram.borrow();
+        assert_eq!(
+            [0x10, 0x12, 0x14, 0x16].map(|idx| vram.read(idx)),
+            [0xf0, 0xf0, 0xfc, 0xfc]
+        );
+        assert_eq!(vram.read(0x0190), 0x3c);
+        // Check background map
+        assert_eq!(vram.read(0x1904), 0x01);
+        assert_eq!(vram.read(0x1910), 0x19);
+        assert_eq!(vram.read(0x192f), 0x18);
+    }
+
+    #[test]
+    fn with_model_works() {
+        let setup = |model| GameBoy::with_model(Cartridge::new(&ROM).unwrap(), model);
+
+        // DMG ignores CGB registers
+        let mut dmg = setup(Model::Dmg);
+        dmg.io_write(IoReg::Vbk, 0x00);
+        assert_eq!(dmg.io_read(IoReg::Vbk), 0xff);
+        assert_eq!(dmg.io_read(IoReg::Boot), 0x00);
+        assert_eq!(dmg.mmu.borrow().read(0x00fd), 0x01);
+
+        // MGB uses its own boot ROM
+        let mut mgb = setup(Model::Mgb);
+        assert_eq!(mgb.mmu.borrow().read(0x00fd), 0xff);
+        mgb.skip_boot();
+        assert_eq!(mgb.cpu.regs().af, 0xffb0);
+
+        // CGB responds to CGB registers
+        let mut cgb = setup(Model::Cgb);
+        cgb.io_write(IoReg::Vbk, 0x00);
+        assert_eq!(cgb.io_read(IoReg::Vbk), 0xfe);
+        // CGB starts after its boot ROM
+        assert_eq!(cgb.io_read(IoReg::Boot), 0x01);
+        assert_eq!(cgb.cpu.regs().af, 0x1180);
+        assert_eq!(cgb.cpu.regs().pc, 0x0100);
+    }
+
+    #[test]
+    fn cgb_hardware_works() {
+        let setup = |model| GameBoy::with_model(Cartridge::new(&ROM).unwrap(), model);
+
+        // CGB has 16 KiB of banked VRAM
+        let mut cgb = setup(Model::Cgb);
+        assert_eq!(cgb.model(), Model::Cgb);
+        cgb.io_write(IoReg::Vbk, 0x00);
+        cgb.mmu.borrow_mut().write(0x8000, 0xaa);
+        cgb.io_write(IoReg::Vbk, 0x01);
+        assert_eq!(cgb.io_read(IoReg::Vbk), 0xff);
+        assert_eq!(cgb.mmu.borrow().read(0x8000), 0x00);
+        cgb.mmu.borrow_mut().write(0x9fff, 0xbb);
+        cgb.io_write(IoReg::Vbk, 0xfe);
+        assert_eq!(cgb.io_read(IoReg::Vbk), 0xfe);
+        assert_eq!(cgb.mmu.borrow().read(0x8000), 0xaa);
+        assert_eq!(cgb.ppu.vram1.borrow().read(0x1fff), 0xbb);
+        // CGB has color palette registers
+        cgb.io_write(IoReg::Bcps, 0x80);
+        cgb.io_write(IoReg::Bcpd, 0x12);
+        assert_eq!(cgb.io_read(IoReg::Bcps), 0xc1);
+        assert_eq!(cgb.ppu.bcp.borrow().color(0, 0), 0x0012);
+
+        // DMG has neither
+        let mut dmg = setup(Model::Dmg);
+        assert_eq!(dmg.model(), Model::Dmg);
+        dmg.io_write(IoReg::Vbk, 0x01);
+        dmg.mmu.borrow_mut().write(0x8000, 0xaa);
+        assert_eq!(dmg.ppu.vram.borrow().read(0x0000), 0xaa);
+        assert_eq!(dmg.ppu.vram1.borrow().read(0x0000), 0x00);
+        assert_eq!(dmg.io_read(IoReg::Bcps), 0xff);
+    }
+
+    #[test]
+    fn cgb_wram_bank_works() {
+        let setup = |model| GameBoy::with_model(Cartridge::new(&ROM).unwrap(), model);
+
+        // CGB has 32 KiB of banked WRAM
+        let mut cgb = setup(Model::Cgb);
+        assert_eq!(cgb.io_read(IoReg::Svbk), 0xf8);
+        cgb.mmu.borrow_mut().write(0xc000, 0xcc);
+        cgb.mmu.borrow_mut().write(0xd000, 0xaa);
+        cgb.io_write(IoReg::Svbk, 0x02);
+        assert_eq!(cgb.io_read(IoReg::Svbk), 0xfa);
+        assert_eq!(cgb.mmu.borrow().read(0xd000), 0x00);
+        cgb.mmu.borrow_mut().write(0xd000, 0xbb);
+        cgb.mmu.borrow_mut().write(0xdfff, 0xdd);
+        // Bank 0 is fixed
+        assert_eq!(cgb.mmu.borrow().read(0xc000), 0xcc);
+        // Selecting bank 0 selects bank 1
+        cgb.io_write(IoReg::Svbk, 0x00);
+        assert_eq!(cgb.mmu.borrow().read(0xd000), 0xaa);
+        cgb.io_write(IoReg::Svbk, 0xfa);
+        assert_eq!(cgb.mmu.borrow().read(0xd000), 0xbb);
+        // Echo RAM mirrors the selected bank
+        assert_eq!(cgb.mmu.borrow().read(0xf000), 0xbb);
+        // Upper banks are laid out in order
+        assert_eq!(cgb.mem.wramx.borrow().read(0x0fff), 0xdd);
+        cgb.io_write(IoReg::Svbk, 0x07);
+        cgb.mmu.borrow_mut().write(0xdfff, 0xee);
+        assert_eq!(cgb.mem.wramx.borrow().read(0x5fff), 0xee);
+
+        // DMG has a single bank
+        let mut dmg = setup(Model::Dmg);
+        dmg.mmu.borrow_mut().write(0xd000, 0xaa);
+        dmg.io_write(IoReg::Svbk, 0x02);
+        assert_eq!(dmg.io_read(IoReg::Svbk), 0xff);
+        assert_eq!(dmg.mmu.borrow().read(0xd000), 0xaa);
+    }
+
+    #[test]
+    fn soft_reset_works() {
+        let mut emu = setup();
+
+        // Write to external and work RAM
+        emu.mmu.borrow_mut().write(0xa000, 0xaa);
+        emu.mmu.borrow_mut().write(0xbfff, 0xbb);
+        emu.mmu.borrow_mut().write(0xc000, 0xcc);
+        (0..0x1000).for_each(|_| emu.cycle());
+
+        // External RAM survives a soft reset...
+        emu.soft_reset();
+        assert_eq!(emu.mmu.borrow().read(0xa000), 0xaa);
+        assert_eq!(emu.mmu.borrow().read(0xbfff), 0xbb);
+        // ... while work RAM is cleared
+        assert_eq!(emu.mmu.borrow().read(0xc000), 0x00);
+        assert_eq!(emu.cpu.regs().pc, 0x0000);
+
+        // External RAM is cleared by a full reset
+        emu.reset();
+        assert_eq!(emu.mmu.borrow().read(0xa000), 0x00);
+    }
+
+    #[test]
+    fn export_ram_works() {
+        let emu = setup();
+
+        // Fill external RAM with a pattern
+        (0xa000..=0xbfff).for_each(|addr| emu.mmu.borrow_mut().write(addr, addr as u8 ^ 0x5a));
+        let eram = emu.export_ram();
+        assert_eq!(eram.len(), 0x2000);
+
+        // Import into a cleared emulator
+        let mut other = setup();
+        other.import_ram(&eram).unwrap();
+        assert_eq!(other.export_ram(), eram);
+        assert_eq!(other.mmu.borrow().read(0xa123), 0x23 ^ 0x5a);
+        // Sizes must match exactly
+        assert!(matches!(
+            other.import_ram(&eram[1..]),
+            Err(RamError::SizeMismatch {
+                expected: 0x2000,
+                found: 0x1fff
+            })
+        ));
+    }
+
+    #[test]
+    fn dump_vram_works() {
+        let mut emu = setup();
+
+        // Fill VRAM and OAM with a pattern
+        (0x8000..=0x9fff).for_each(|addr| emu.mmu.borrow_mut().write(addr, addr as u8 ^ 0xa5));
+        (0xfe00..=0xfe9f).for_each(|addr| emu.mmu.borrow_mut().write(addr, addr as u8));
+        let vram = emu.dump_vram();
+        let oam = emu.dump_oam();
+        assert_eq!(vram.len(), 0x2000);
+        assert_eq!(oam.len(), 0xa0);
+
+        // Clear, then reload
+        emu.ppu.vram.borrow_mut().reset();
+        emu.ppu.oam.borrow_mut().reset();
+        assert_ne!(emu.dump_vram(), vram);
+        emu.load_vram(&vram).unwrap();
+        emu.load_oam(&oam).unwrap();
+        assert_eq!(emu.dump_vram(), vram);
+        assert_eq!(emu.dump_oam(), oam);
+        assert_eq!(emu.mmu.borrow().read(0x8123), 0x23 ^ 0xa5);
+        // Sizes must match exactly
+        assert!(matches!(
+            emu.load_oam(&vram),
+            Err(RamError::SizeMismatch {
+                expected: 0xa0,
+                found: 0x2000
+            })
+        ));
+    }
+
+    #[test]
+    fn diff_state_works() {
+        let emu = setup();
+
+        // Mutate a single byte between snapshots
+        let a = emu.save_state();
+        emu.mmu.borrow_mut().write(0xc123, 0x42);
+        let b = emu.save_state();
+        assert_eq!(
+            GameBoy::diff_state(&a, &b).unwrap(),
+            [StateDiff {
+                region: "WRAM",
+                start: 0xc123,
+                end: 0xc123,
+            }]
+        );
+        // Identical snapshots have no differences
+        assert!(GameBoy::diff_state(&b, &b).unwrap().is_empty());
+
+        // Unknown versions are rejected
+        let mut c = b.clone();
+        c[0] = 0xff;
+        assert!(matches!(
+            GameBoy::diff_state(&b, &c),
+            Err(StateError::UnsupportedVersion(0xff))
+        ));
+    }
+
+    #[test]
+    fn trace_works() {
+        /// Sink shared with the test.
+        struct Shared(Rc<RefCell<Vec<u8>>>);
+
+        impl io::Write for Shared {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                self.0.borrow_mut().extend_from_slice(buf);
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let mut emu = setup();
+        let trace = Rc::new(RefCell::new(Vec::new()));
+        emu.start_trace(Shared(trace.clone())).unwrap();
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        emu.cpu_mut().set_pre_exec_hook(Box::new({
+            let seen = seen.clone();
+            move |pc, opcode| {
+                seen.borrow_mut().push((pc, opcode));
+                HookAction::Continue
+            }
+        }));
+        emu.run_cycles(FRAME as usize);
+        emu.stop_trace();
+
+        // Reload the trace
+        let trace = trace.borrow();
+        let entries = TraceReader::new(&trace[..])
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(
+            entries
+                .iter()
+                .map(|entry| (entry.regs.pc, entry.opcode))
+                .collect::<Vec<_>>(),
+            *seen.borrow()
+        );
+        assert_eq!(entries[0].regs, CpuState::default());
+    }
+
+    #[test]
+    fn load_state_works() {
+        let mut emu = setup();
+        emu.skip_boot();
+
+        // Restore a modified state
+        emu.mmu.borrow_mut().write(0xc123, 0x42);
+        let state = emu.save_state();
+        emu.mmu.borrow_mut().write(0xc123, 0x00);
+        emu.run_cycles(FRAME as usize);
+        emu.load_state(&state).unwrap();
+        assert_eq!(emu.mmu.borrow().read(0xc123), 0x42);
+        assert_eq!(emu.cpu().regs().pc, 0x0100);
+
+        // Timer and interrupt registers round-trip
+        emu.run_cycles(0x1234);
+        emu.io_write(IoReg::Tima, 0x42);
+        emu.io_write(IoReg::If, 0x05);
+        let io = |emu: &GameBoy| [IoReg::Div, IoReg::Tima, IoReg::If].map(|reg| emu.io_read(reg));
+        let prev = io(&emu);
+        let other = emu.save_state();
+        emu.reset();
+        emu.load_state(&other).unwrap();
+        assert_eq!(io(&emu), prev);
+        assert_ne!(prev[0], 0x00);
+
+        // Unknown versions are rejected
+        let mut future = state.clone();
+        future[0] = state::VERSION + 1;
+        assert!(matches!(
+            emu.load_state(&future),
+            Err(StateError::UnsupportedVersion(version)) if version == state::VERSION + 1
+        ));
+        // Truncated states are rejected
+        assert!(matches!(
+            emu.load_state(&state[..state.len() - 1]),
+            Err(StateError::SizeMismatch(..))
+        ));
+        // Invalid internal state is rejected
+        let mut corrupt = state.clone();
+        corrupt[13] = 0xff;
+        assert!(matches!(
+            emu.load_state(&corrupt),
+            Err(StateError::Corrupt("CPU"))
+        ));
+    }
+
+    #[test]
+    fn load_state_internal_works() {
+        // Prepare a 64 KiB MBC1 ROM, marking the start of each bank
+        let mut rom = cart::rom(0x10000, 0x01, b"");
+        (1..4).for_each(|bank| rom[0x4000 * bank] = bank as u8);
+        let boot = || {
+            let mut emu = GameBoy::new(Cartridge::new(&rom).unwrap());
+            emu.skip_boot();
+            emu
+        };
+        let mut emu = boot();
+
+        // Halt with interrupts enabled (`EI; HALT`)
+        emu.io_write(IoReg::Ie, 0x00);
+        emu.mmu.borrow_mut().write(0xc000, 0xfb);
+        emu.mmu.borrow_mut().write(0xc001, 0x76);
+        let regs = emu.cpu().regs();
+        emu.cpu_mut().set_regs(CpuState { pc: 0xc000, ..regs });
+        // Select ROM bank 2
+        emu.mmu.borrow_mut().write(0x2000, 0x02);
+        // Stop partway through drawing a scanline
+        emu.run_cycles(456 * 3 + 100);
+        let snap = emu.snapshot();
+        assert_eq!(snap.cpu.ime, Ime::Enabled);
+        assert_eq!(snap.cpu.status, CpuStatus::Halted);
+        assert_eq!(snap.ppu.mode, PpuMode::Draw);
+        let state = emu.save_state();
+
+        // Restore into a fresh machine
+        let mut other = boot();
+        other.load_state(&state).unwrap();
+        assert_eq!(other.snapshot(), snap);
+        assert_eq!(other.mmu.borrow().read(0x4000), 0x02);
+        // Both machines continue identically
+        emu.run_cycles(FRAME as usize);
+        other.run_cycles(FRAME as usize);
+        assert_eq!(other.save_state(), emu.save_state());
+
+        // States from a different MBC are rejected
+        let mut emu = setup();
+        assert!(matches!(
+            emu.load_state(&state),
+            Err(StateError::MbcMismatch)
+        ));
+    }
+
+    #[test]
+    fn load_state_v1_works() {
+        let mut emu = setup();
+        emu.skip_boot();
+        emu.mmu.borrow_mut().write(0xc123, 0x42);
+        let state = emu.save_state();
+
+        // Strip the state down to version 1
+        let mut v1 = vec![1];
+        v1.extend(&state[1..13]);
+        v1.extend(&state[1 + 0x34 + 0x82 + 0x2000 + 0x6000..]);
+        let regs = emu.cpu().regs();
+
+        // Internal state is left untouched
+        emu.reset();
+        let ppu = emu.snapshot().ppu;
+        emu.load_state(&v1).unwrap();
+        assert_eq!(emu.cpu().regs(), regs);
+        assert_eq!(emu.mmu.borrow().read(0xc123), 0x42);
+        assert_eq!(emu.snapshot().ppu, ppu);
+        // Version 1 states can still be compared
+        assert!(GameBoy::diff_state(&v1, &v1).unwrap().is_empty());
+    }
+
+    #[test]
+    fn load_state_cgb_works() {
+        let mut emu = GameBoy::with_model(Cartridge::new(&ROM).unwrap(), Model::Cgb);
+
+        // Fill both VRAM banks, leaving bank 1 selected
+        emu.io_write(IoReg::Vbk, 0x00);
+        emu.mmu.borrow_mut().write(0x8000, 0xaa);
+        emu.io_write(IoReg::Vbk, 0x01);
+        emu.mmu.borrow_mut().write(0x8000, 0xbb);
+        // Fill two WRAM banks, leaving bank 3 selected
+        emu.io_write(IoReg::Svbk, 0x01);
+        emu.mmu.borrow_mut().write(0xd000, 0xcc);
+        emu.io_write(IoReg::Svbk, 0x03);
+        emu.mmu.borrow_mut().write(0xd000, 0xdd);
+        // Write palettes, leaving their indices mid-way
+        emu.io_write(IoReg::Bcps, 0x80);
+        emu.io_write(IoReg::Bcpd, 0x12);
+        emu.io_write(IoReg::Bcpd, 0x34);
+        emu.io_write(IoReg::Ocps, 0x85);
+        emu.io_write(IoReg::Ocpd, 0x56);
+        let bcps = emu.io_read(IoReg::Bcps);
+        let ocps = emu.io_read(IoReg::Ocps);
+        let state = emu.save_state();
+
+        // Restore into a fresh machine
+        let mut other = GameBoy::with_model(Cartridge::new(&ROM).unwrap(), Model::Cgb);
+        other.load_state(&state).unwrap();
+        assert_eq!(other.ppu.vram.borrow().read(0x0000), 0xaa);
+        assert_eq!(other.ppu.vram1.borrow().read(0x0000), 0xbb);
+        assert_eq!(other.mmu.borrow().read(0x8000), 0xbb);
+        assert_eq!(other.mem.wram.borrow().read(0x1000), 0xcc);
+        assert_eq!(other.mem.wramx.borrow().read(0x1000), 0xdd);
+        assert_eq!(other.mmu.borrow().read(0xd000), 0xdd);
+        // Palette contents and indices are restored as-is
+        assert_eq!(other.ppu.bcp.borrow().color(0, 0), 0x3412);
+        assert_eq!(other.ppu.ocp.borrow().color(0, 2), 0x5600);
+        assert_eq!(other.io_read(IoReg::Bcps), bcps);
+        assert_eq!(other.io_read(IoReg::Ocps), ocps);
+        assert_eq!(other.save_state(), state);
+    }
+
+    #[test]
+    fn load_state_v2_works() {
+        let mut emu = GameBoy::with_model(Cartridge::new(&ROM).unwrap(), Model::Cgb);
+        emu.io_write(IoReg::Svbk, 0x02);
+        emu.mmu.borrow_mut().write(0xd000, 0xaa);
+        let state = emu.save_state();
+
+        // Strip the state down to version 2
+        let mut v2 = vec![2];
+        v2.extend(&state[1..1 + 0x34 + 0x82 + 0x2000]);
+        v2.extend(&state[1 + 0x34 + 0x82 + 0x2000 + 0x6000..]);
+
+        // Upper WRAM banks are left untouched
+        let mut other = GameBoy::with_model(Cartridge::new(&ROM).unwrap(), Model::Cgb);
+        other.mem.wramx.borrow_mut().write(0x0000, 0xbb);
+        other.load_state(&v2).unwrap();
+        assert_eq!(other.io_read(IoReg::Svbk), 0xfa);
+        assert_eq!(other.mmu.borrow().read(0xd000), 0xbb);
+        // Version 2 states can still be compared
+        assert!(GameBoy::diff_state(&v2, &v2).unwrap().is_empty());
+    }
+
+    #[test]
+    fn bus_snapshot_works() {
+        let emu = setup();
+
+        // Write a single byte between snapshots
+        let prev = emu.bus_snapshot();
+        emu.mmu.borrow_mut().write(0xc010, 0x5a);
+        let next = emu.bus_snapshot();
+        assert_eq!(prev.diff(&next), [(0xc010, 0x00, 0x5a)]);
+        assert_eq!(next.diff(&prev), [(0xc010, 0x5a, 0x00)]);
+
+        // Banked external RAM is captured through its mapped window
+        let mut rom = cart::rom(0x8000, 0x03, b"");
+        rom[0x149] = 0x03;
+        rom[0x14d] = rom[0x14d].wrapping_sub(0x03);
+        let emu = GameBoy::new(Cartridge::new(&rom).unwrap());
+        assert_eq!(emu.cart().ram_size(), 0x8000);
+        emu.mmu.borrow_mut().write(0x1000, 0x0a);
+        let prev = emu.bus_snapshot();
+        emu.mmu.borrow_mut().write(0xbfff, 0x5a);
+        let next = emu.bus_snapshot();
+        assert_eq!(prev.diff(&next), [(0xbfff, 0x00, 0x5a)]);
+    }
+
+    #[test]
+    fn serial_output_works() {
+        let mut emu = setup();
+        emu.set_serial_capture(true);
+
+        // Send each byte over the serial port using the internal clock
+        for byte in *b"Passed" {
+            emu.io_write(IoReg::Sb, byte);
+            emu.io_write(IoReg::Sc, 0x81);
+            emu.run_cycles(8 * 512);
+            // Transfer completes after shifting out 8 bits
+            assert_eq!(emu.io_read(IoReg::Sc) & 0x80, 0);
+            assert_eq!(emu.io_read(IoReg::Sb), 0xff);
+        }
+        assert_eq!(emu.serial_output(), "Passed");
+        assert_ne!(emu.io_read(IoReg::If) & 0x08, 0);
+    }
+
+    #[test]
+    fn ppu_step_works() {
+        let mut emu = setup();
+        emu.io_write(IoReg::Lcdc, 0x80);
+        let cpu = emu.cpu().regs();
+
+        // Advance only the PPU by a single scanline
+        let ppu = emu.ppu_mut();
+        (0..456).for_each(|_| ppu.cycle());
+        assert_eq!((ppu.line(), ppu.dot()), (1, 0));
+        // CPU is left untouched
+        assert_eq!(emu.cpu().regs(), cpu);
+    }
+
+    #[test]
+    fn queue_input_works() {
+        let mut emu = setup();
+        emu.queue_input(10, vec![Button::A]);
+        emu.queue_input(11, vec![]);
+        // Select the action buttons
+        emu.io_write(IoReg::P1, 0x10);
+
+        // Button is pressed precisely at the start of frame 10
+        emu.run_cycles(10 * FRAME as usize);
+        assert_eq!(emu.io_read(IoReg::P1) & 0x0f, 0x0f);
+        emu.run_cycles(1);
+        assert_eq!(emu.io_read(IoReg::P1) & 0x0f, 0x0e);
+        // ... and released the following frame
+        emu.run_cycles(FRAME as usize);
+        assert_eq!(emu.io_read(IoReg::P1) & 0x0f, 0x0f);
+    }
+
+    #[test]
+    fn queue_input_reset_works() {
+        let mut emu = setup();
+        emu.queue_input(20, vec![Button::B]);
+        emu.run_cycles(5 * FRAME as usize + 123);
+
+        // Queued inputs are discarded on reset...
+        emu.reset();
+        emu.queue_input(2, vec![Button::A]);
+        emu.io_write(IoReg::P1, 0x10);
+        // ... with frames counted from the reset
+        emu.run_cycles(2 * FRAME as usize);
+        assert_eq!(emu.io_read(IoReg::P1) & 0x0f, 0x0f);
+        emu.run_cycles(1);
+        assert_eq!(emu.io_read(IoReg::P1) & 0x0f, 0x0e);
+        emu.run_cycles(20 * FRAME as usize);
+        assert_eq!(emu.io_read(IoReg::P1) & 0x0f, 0x0e);
+    }
+
+    #[test]
+    fn movie_works() {
+        let path = std::env::temp_dir().join(format!("gameboy-{}.movie", std::process::id()));
+        let boot = |emu: &mut GameBoy| {
+            emu.reset();
+            emu.skip_boot();
+            // Log the action buttons into WRAM from $C100
+            #[rustfmt::skip]
+            let prog = [
+                0x21, 0x00, 0xc1, // LD HL, 0xc100
+                0x3e, 0x10,       // LD A, 0x10
+                0xe0, 0x00,       // LDH (0x00), A
+                0xf0, 0x00,       // LDH A, (0x00)
+                0x22,             // LD (HL+), A
+                0x18, 0xfb,       // JR -5
+            ];
+            for (addr, byte) in (0xc000..).zip(prog) {
+                emu.mmu.borrow_mut().write(addr, byte);
+            }
+            let regs = emu.cpu().regs();
+            emu.cpu_mut().set_regs(CpuState { pc: 0xc000, ..regs });
+        };
+        let record = |emu: &mut GameBoy| {
+            for keys in [vec![Button::A], vec![Button::A, Button::Start], vec![]] {
+                emu.send(keys);
+                emu.run_cycles(FRAME as usize);
+            }
+        };
+
+        // Record a short input sequence
+        let mut emu = setup();
+        boot(&mut emu);
+        emu.start_recording();
+        record(&mut emu);
+        emu.save_movie(&path).unwrap();
+        assert_eq!(emu.movie().unwrap().inputs().len(), 3);
+        let mmu = emu.mmu.borrow();
+        assert!((0xc100..0xe000).any(|addr| mmu.read(addr) == 0xd6));
+        drop(mmu);
+
+        // Replay it from a fresh machine
+        let mut other = setup();
+        boot(&mut other);
+        other.load_movie(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        other.run_cycles(3 * FRAME as usize);
+        assert_eq!(other.cpu().regs(), emu.cpu().regs());
+        assert_eq!(other.save_state(), emu.save_state());
+
+        // Record again, resetting mid-frame
+        let mut emu = setup();
+        emu.start_recording();
+        emu.send(vec![Button::B]);
+        emu.run_cycles(2 * FRAME as usize + 1234);
+        boot(&mut emu);
+        record(&mut emu);
+        // Inputs from before the reset are discarded
+        let movie = emu.movie().unwrap().clone();
+        assert_eq!(movie.inputs().len(), 3);
+
+        // Replay it from a fresh machine
+        let mut other = setup();
+        boot(&mut other);
+        other.play(&movie);
+        other.run_cycles(3 * FRAME as usize);
+        assert_eq!(other.save_state(), emu.save_state());
+    }
+
+    #[test]
+    fn opcode_histogram_works() {
+        let mut emu = setup();
+        emu.skip_boot();
+        // Run a loop from WRAM
+        let mut mmu = emu.mmu.borrow_mut();
+        for (addr, byte) in (0xc000..).zip([
+            0x06, 0x0a, // LD B, 10
+            0xcb, 0x37, // SWAP A
+            0x05, // DEC B
+            0x20, 0xfb, // JR NZ, -5
+            0x18, 0xfe, // JR -2
+        ]) {
+            mmu.write(addr, byte);
+        }
+        drop(mmu);
+        let regs = emu.cpu().regs();
+        emu.cpu_mut().set_regs(CpuState { pc: 0xc000, ..regs });
+        emu.run_cycles(0x1000);
+
+        // Each opcode in the loop body executes once per iteration
+        let hist = emu.opcode_histogram();
+        assert_eq!(hist[0x06], 1);
+        assert_eq!(hist[0xcb], 10);
+        assert_eq!(hist[0x100 + 0x37], 10);
+        assert_eq!(hist[0x05], 10);
+        assert_eq!(hist[0x20], 10);
+        assert_eq!(hist[0x100..].iter().sum::<u64>(), 10);
+    }
+
+    #[test]
+    fn stop_works() {
+        let mut emu = setup();
+        emu.skip_boot();
+        // Run STOP from WRAM
+        let mut mmu = emu.mmu.borrow_mut();
+        mmu.write(0xc000, 0x10); // STOP
+        mmu.write(0xc001, 0x00);
+        mmu.write(0xc002, 0x3c); // INC A
+        drop(mmu);
+        let regs = emu.cpu().regs();
+        emu.cpu_mut().set_regs(CpuState {
+            af: 0x0000,
+            pc: 0xc000,
+            ..regs
+        });
+        // Select the action buttons
+        emu.io_write(IoReg::P1, 0x10);
+
+        // Stop until a button is pressed
+        emu.run_cycles(FRAME as usize);
+        assert!(!emu.cpu().enabled());
+        assert_eq!(emu.cpu().regs().pc, 0xc002);
+        // Other interrupts leave the CPU stopped
+        emu.io_write(IoReg::Ie, Interrupt::Timer as u8);
+        emu.io_write(IoReg::Tac, 0x05);
+        emu.run_cycles(0x1000);
+        assert_ne!(emu.io_read(IoReg::If) & Interrupt::Timer as u8, 0);
+        assert!(!emu.cpu().enabled());
+        assert_eq!(emu.cpu().regs().pc, 0xc002);
+        // Resume at the instruction following STOP
+        emu.send(vec![Button::A]);
+        emu.run_cycles(8);
+        assert!(emu.cpu().enabled());
+        assert_eq!(emu.cpu().regs().af >> 8, 0x01);
+    }
+
+    #[test]
+    fn set_scanline_callback_works() {
+        let mut emu = setup();
+        emu.skip_boot();
+        // Spin in WRAM
+        emu.mmu.borrow_mut().write(0xc000, 0x18); // JR -2
+        emu.mmu.borrow_mut().write(0xc001, 0xfe);
+        let regs = emu.cpu().regs();
+        emu.cpu_mut().set_regs(CpuState { pc: 0xc000, ..regs });
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        emu.set_scanline_callback(Box::new({
+            let seen = seen.clone();
+            move |ly, line| seen.borrow_mut().push((ly, line.to_vec()))
+        }));
+
+        // Run a single frame
+        emu.run_cycles(FRAME as usize);
+        let seen = seen.borrow();
+        assert_eq!(seen.len(), SCREEN.height);
+        for (idx, (ly, line)) in seen.iter().enumerate() {
+            // Scanlines are reported in order
+            assert_eq!(*ly as usize, idx);
+            // Each with the pixels drawn
+            let start = idx * SCREEN.width;
+            assert_eq!(line[..], emu.ppu().screen()[start..start + SCREEN.width]);
+        }
+    }
+
+    #[test]
+    fn on_serial_byte_works() {
+        let mut emu = setup();
+        let sent = Rc::new(RefCell::new(Vec::new()));
+        emu.on_serial_byte(Box::new({
+            let sent = sent.clone();
+            move |byte| sent.borrow_mut().push(byte)
+        }));
+
+        // Each byte is reported once shifted out
+        for byte in [0x12, 0x34, 0x56] {
+            emu.io_write(IoReg::Sb, byte);
+            emu.io_write(IoReg::Sc, 0x81);
+            emu.run_cycles(8 * 512 - 1);
+            assert_ne!(sent.borrow().last(), Some(&byte));
+            emu.run_cycles(1);
+            assert_eq!(sent.borrow().last(), Some(&byte));
+        }
+        assert_eq!(*sent.borrow(), [0x12, 0x34, 0x56]);
+    }
+
+    #[test]
+    fn disasm_rom_works() {
+        let emu = setup();
+
+        // Disassemble from the entry point
+        let listing: Vec<_> = emu.disasm_rom().collect();
+        assert_eq!(
+            listing[..5],
+            [
+                (0x0100, "NOP".to_string()),
+                (0x0101, "JP $0150".to_string()),
+                (0x0104, "ADC A, $ed".to_string()),
+                (0x0106, "LD H, (HL)".to_string()),
+                (0x0107, "LD H, (HL)".to_string()),
+            ]
+        );
+        // Continue until the end of the ROM
+        assert_eq!(listing.last(), Some(&(0x7fff, "NOP".to_string())));
+    }
+
+    #[test]
+    fn machine_works() {
+        // Drive an emulator generically through `Machine`
+        fn run(machine: &mut impl Machine, cycles: usize) {
+            (0..cycles).for_each(|_| machine.cycle());
+        }
+        // Execute a run of `NOP`s from WRAM
+        let boot = || {
+            let mut emu = setup();
+            emu.skip_boot();
+            (0xc000..0xd000).for_each(|addr| emu.mmu.borrow_mut().write(addr, 0x00));
+            let regs = emu.cpu().regs();
+            emu.cpu_mut().set_regs(CpuState { pc: 0xc000, ..regs });
+            emu
+        };
+        let mut emu = boot();
+        let line = emu.ppu.line();
+        assert!(Machine::enabled(&emu));
+        run(&mut emu, 0x1000);
+        // Each `NOP` takes 4 cycles
+        assert_eq!(emu.cpu.regs().pc, 0xc400);
+        // Each scanline takes 456 cycles
+        assert_eq!(emu.ppu.line(), line + 8);
+
+        // Compare against an emulator driven directly
+        let mut other = boot();
+        other.run_cycles(0x1000);
+        assert_eq!(emu.cpu.regs(), other.cpu.regs());
+        assert!(GameBoy::diff_state(&emu.save_state(), &other.save_state())
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn run_scanline_works() {
+        let mut emu = setup();
+        emu.skip_boot();
+
+        // Mark the leftmost pixel of each background tile
+        (0x8000..0x8010).for_each(|addr| emu.mmu.borrow_mut().write(addr, 0x80));
+
+        // Change the scroll between scanlines
+        for scx in 0..4 {
+            emu.io_write(IoReg::Scx, scx);
+            let line = emu.run_scanline().unwrap().to_vec();
+            assert_eq!(emu.ppu.line(), scx + 1);
+            assert!(line.iter().enumerate().all(|(x, &color)| {
+                let marked = (x + scx as usize) % 8 == 0;
+                color == [Color::C0, Color::C3][marked as usize]
+            }));
+        }
+        // Scanlines are 456 dots apart
+        let cycle = emu.cycle;
+        emu.run_scanline();
+        assert_eq!(emu.cycle - cycle, 456);
+    }
+
+    #[test]
+    fn step_over_works() {
+        let mut emu = setup();
+        emu.skip_boot();
+
+        // Load a program that calls a subroutine
+        #[rustfmt::skip]
+        let prog = [
+            0xcd, 0x04, 0xc0, // CALL 0xc004
+            0x00,             // NOP
+            0x3e, 0x42,       // LD A, 0x42
+            0xc9,             // RET
+            0x18, 0xfe,       // JR -2
+        ];
+        for (addr, byte) in (0xc000..).zip(prog) {
+            emu.mmu.borrow_mut().write(addr, byte);
+        }
+        let acc = |emu: &GameBoy| (emu.cpu().regs().af >> 8) as u8;
+
+        // Step over the call
+        let regs = emu.cpu().regs();
+        emu.cpu_mut().set_regs(CpuState { pc: 0xc000, ..regs });
+        let cycle = emu.cycle;
+        assert!(emu.step_over(0x100));
+        assert_eq!(emu.cpu().regs().pc, 0xc003);
+        assert_eq!(acc(&emu), 0x42);
+        // The whole machine was cycled (up to alignment with the CPU's clock)
+        assert!((45..=48).contains(&(emu.cycle - cycle)));
+
+        // Step into the call, then out of it
+        let regs = emu.cpu().regs();
+        emu.cpu_mut().set_regs(CpuState { pc: 0xc000, ..regs });
+        emu.run_cycles(24);
+        assert_eq!(emu.cpu().regs().pc, 0xc004);
+        assert!(emu.step_out(0x100));
+        assert_eq!(emu.cpu().regs().pc, 0xc003);
+
+        // Stop early without a return
+        let regs = emu.cpu().regs();
+        emu.cpu_mut().set_regs(CpuState { pc: 0xc007, ..regs });
+        let cycle = emu.cycle;
+        assert!(!emu.step_out(0x1000));
+        assert_eq!(emu.cycle - cycle, 0x1000);
+    }
+
+    #[test]
+    fn run_with_works() {
+        // Count calls to each of the frontend's hooks
+        #[derive(Default)]
+        struct Counter {
+            input: usize,
+            video: usize,
+            audio: usize,
+        }
+
+        impl Frontend<GameBoy> for Counter {
+            fn poll_input(&mut self) -> Option<Vec<Button>> {
+                self.input += 1;
+                (self.input <= 3).then(Vec::new)
+            }
+
+            fn present(&mut self, _: &Screen) {
+                self.video += 1;
+            }
+
+            fn queue_audio(&mut self, _: &[(f32, f32)]) {
+                self.audio += 1;
+            }
+        }
+
+        // Run for 3 frames
+        let mut emu = setup();
+        let mut frontend = Counter::default();
+        emu.run_with(&mut frontend);
+        assert_eq!(frontend.input, 4);
+        assert_eq!(frontend.video, 3);
+        assert_eq!(frontend.audio, 3);
+        assert_eq!(emu.cycle, 3 * FRAME as usize);
+
+        // Run headless for another frame
+        emu.run_with(&mut Headless::new(1));
+        assert_eq!(emu.cycle, 4 * FRAME as usize);
+    }
+
+    #[test]
+    fn shade_palette_works() {
+        let mut emu = setup();
+        emu.skip_boot();
+
+        // Draw each color in turn using an identity palette
+        emu.io_write(IoReg::Bgp, 0xe4);
+        for addr in (0x8000..0x8010).step_by(2) {
+            emu.mmu.borrow_mut().write(addr, 0x50);
+            emu.mmu.borrow_mut().write(addr + 1, 0x30);
+        }
+        emu.run_scanline();
+        assert_eq!(
+            emu.frame_rgba()[..4],
+            [0xe9efec, 0xa0a08b, 0x555568, 0x211e20]
+        );
+
+        // Override the shades
+        let shades = [0xffffff, 0xaaaaaa, 0x555555, 0x000000];
+        emu.set_shade_palette(shades);
+        assert_eq!(emu.frame_rgba()[..4], shades);
+        assert_eq!(emu.frame_rgba()[8..12], shades);
+    }
+
+    #[test]
+    fn screenshot_works() {
+        let mut emu = setup();
+        emu.skip_boot();
+        let path = std::env::temp_dir().join(format!("gameboy-{}.png", std::process::id()));
+
+        // Nothing to capture before the first frame
+        assert!(emu.screenshot(&path).is_err());
+
+        // Spin in WRAM, leaving VRAM untouched
+        emu.mmu.borrow_mut().write(0xc000, 0x18); // JR -2
+        emu.mmu.borrow_mut().write(0xc001, 0xfe);
+        let regs = emu.cpu().regs();
+        emu.cpu_mut().set_regs(CpuState { pc: 0xc000, ..regs });
+        // Draw each color in turn using an identity palette
+        emu.io_write(IoReg::Bgp, 0xe4);
+        for addr in (0x8000..0x8010).step_by(2) {
+            emu.mmu.borrow_mut().write(addr, 0x50);
+            emu.mmu.borrow_mut().write(addr + 1, 0x30);
+        }
+        emu.run_cycles(FRAME as usize);
+        emu.screenshot(&path).unwrap();
+
+        // Read back the image
+        let (width, height, rgba) = png::decode(&std::fs::read(&path).unwrap());
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!((width, height), (160, 144));
+        assert_eq!(rgba[4..8], [0xa0, 0xa0, 0x8b, 0xff]);
+        assert_eq!(rgba[12..16], [0x21, 0x1e, 0x20, 0xff]);
+    }
+
+    #[test]
+    fn run_throttled_works() {
+        let clock = ManualClock::default();
+        let window = Duration::from_millis(10);
+        let run = |factor| {
+            let mut emu = setup();
+            emu.set_clock_divider(factor);
+            assert_eq!(emu.run_throttled(&clock), 0);
+            clock.advance(window);
+            emu.run_throttled(&clock)
+        };
+
+        // Cycles are proportional to the clock's speed
+        let cycles = run(1.0);
+        assert_eq!(cycles, 41_943);
+        assert_eq!(run(2.0), 2 * cycles);
+        assert_eq!(run(0.5), cycles / 2);
+    }
+
+    #[test]
+    fn max_catchup_works() {
+        let clock = ManualClock::default();
+        let mut emu = setup();
+        emu.set_max_catchup(Some(2));
+        emu.run_throttled(&clock);
+
+        // Fall far behind
+        clock.advance(Duration::from_secs(10));
+        assert_eq!(emu.run_throttled(&clock), 2 * FRAME as usize);
+        // The dropped time is not caught up later
+        clock.advance(Duration::from_millis(10));
+        assert_eq!(emu.run_throttled(&clock), 41_943);
+        assert_eq!(emu.cycle, 2 * FRAME as usize + 41_943);
+    }
+
+    #[test]
+    fn max_catchup_run_with_works() {
+        let period = Duration::from_millis(16);
+        let start = Instant::now();
+        let now = start + Duration::from_secs(10);
+        let mut emu = setup();
+
+        // Without a limit, every frame is caught up
+        assert_eq!(emu.pacing.advance(start, now, period), start + period);
+        // Otherwise, frames beyond the limit are dropped
+        emu.set_max_catchup(Some(2));
+        assert_eq!(emu.pacing.advance(start, now, period), now - 2 * period);
+        // Frames within the limit are still caught up
+        let late = now - period;
+        assert_eq!(emu.pacing.advance(late, now, period), now);
+    }
+
+    #[test]
+    fn memory_map_works() {
+        let mut emu = setup();
+        let map = emu.memory_map();
+
+        // Video RAM is mapped
+        assert!(map.contains(&MemRegion {
+            start: 0x8000,
+            end: 0x9fff,
+            name: "Video",
+            kind: MemKind::Ram,
+            blocked: true,
+        }));
+        // As is the unusable region
+        assert!(map.contains(&MemRegion {
+            start: 0xfea0,
+            end: 0xfeff,
+            name: "Unusable",
+            kind: MemKind::Unmapped,
+            blocked: true,
+        }));
+        // High RAM remains accessible during an OAM DMA
+        assert!(map
+            .iter()
+            .any(|region| region.name == "High" && !region.blocked));
+        // Regions cover the address space
+        assert!(map.windows(2).all(|pair| pair[0].end + 1 == pair[1].start));
+        assert_eq!(map.last().unwrap().end, 0xffff);
+        // Boot ROM is initially mapped
+        assert_eq!(map[0].start, 0x0000);
+        assert_eq!(map[0].name, "Boot");
+        assert_eq!(map[1].start, 0x0100);
+        // Cartridge ROM is mapped once boot ROM is disabled
+        emu.io_write(IoReg::Boot, 0x01);
+        let map = emu.memory_map();
+        assert_eq!(map[0].start, 0x0000);
+        assert_eq!(map[0].name, "Cartridge");
+    }
+
+    #[test]
+    fn io_regs_work() {
+        let mut emu = setup();
+
+        // Write through named register
+        emu.io_write(IoReg::Bgp, 0xe4);
+        assert_eq!(emu.mmu.borrow().read(0xff47), 0xe4);
+        // Read through named register
+        emu.mmu.borrow_mut().write(0xff42, 0x12);
+        assert_eq!(emu.io_read(IoReg::Scy), 0x12);
+        emu.mmu.borrow_mut().write(0xffff, 0x1f);
+        assert_eq!(emu.io_read(IoReg::Ie), 0x1f);
+    }
+
+    #[test]
+    #[should_panic]
+    fn mmu_boot_write_panics() {
+        let emu = setup();
+
+        // Write to boot ROM (should panic)
+        emu.mmu.borrow_mut().write(0x0000, 0xaa);
+    }
+
+    #[test]
+    fn mmu_unusable_works() {
+        let emu = setup();
+
+        // Test unusable addresses
+        for addr in 0xfea0..=0xfeff {
+            // Write to every unusable address
+            emu.mmu.borrow_mut().write(addr, 0xaa);
+            // Check the write didn't work
+            assert_eq!(emu.mmu.borrow().read(addr), 0x00);
+        }
+    }
+
+    #[test]
+    fn mmu_unmapped_works() {
+        let emu = setup();
+
+        // Disable boot ROM
+        emu.mmu.borrow_mut().write(0xff50, 0x01);
+
+        // Define unmapped addresses
+        let unmapped = [0xff03..=0xff03, 0xff27..=0xff2f];
+
+        // Test unmapped addresses
+        for gap in unmapped {
+            for addr in gap {
+                // Write to every unmapped address
+                emu.mmu.borrow_mut().write(addr, 0xaa);
+                // Check the write didn't work
+                assert_eq!(emu.mmu.borrow().read(addr), 0xff);
+            }
+        }
+    }
+
+    #[test]
+    fn mmio_unmapped_works() {
+        // Seed the MMU's fallback, which must not leak into I/O
+        let emu = GameBoy::with_seed(Cartridge::new(&ROM).unwrap(), 0x5eed);
+
+        // Define unmapped I/O ports
+        let unmapped = [
+            0xff03..=0xff03,
+            0xff08..=0xff0e,
+            0xff27..=0xff2f,
+            0xff4c..=0xff4f,
+            0xff51..=0xff7f,
+        ];
+
+        // Test unmapped I/O ports
+        for gap in unmapped {
+            for addr in gap {
+                emu.mmu.borrow_mut().write(addr, 0xaa);
+                assert_eq!(emu.mmu.borrow().read(addr), 0xff, "{addr:#06x}");
+            }
+        }
+    }
+
+    #[test]
+    fn vblank_interrupt_works() {
+        let mut emu = setup();
+        emu.skip_boot();
+        // Wait for interrupts, running from WRAM
+        #[rustfmt::skip]
+        let prog = [
+            0xfb,       // EI
+            0x18, 0xfe, // JR -2
+        ];
+        for (addr, byte) in (0xc000..).zip(prog) {
+            emu.mmu.borrow_mut().write(addr, byte);
+        }
+        let regs = emu.cpu().regs();
+        emu.cpu_mut().set_regs(CpuState { pc: 0xc000, ..regs });
+        emu.io_write(IoReg::If, 0x00);
+        emu.io_write(IoReg::Ie, Interrupt::VBlank as u8);
+        // Record interrupts, and entries to the VBlank handler
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        emu.cpu_mut().on_interrupt(Box::new({
+            let seen = seen.clone();
+            move |int, _| seen.borrow_mut().push(int)
+        }));
+        let entered = Rc::new(RefCell::new(0));
+        emu.cpu_mut().set_pre_exec_hook(Box::new({
+            let entered = entered.clone();
+            move |pc, _| {
+                *entered.borrow_mut() += usize::from(pc == 0x0040);
+                HookAction::Continue
+            }
+        }));
+
+        // VBlank is requested exactly once per frame
+        emu.run_cycles(FRAME as usize);
+        assert_eq!(*seen.borrow(), [Interrupt::VBlank]);
+        assert_eq!(*entered.borrow(), 1);
+    }
+
+    #[test]
+    fn dma_conflict_works() {
+        let mut emu = setup();
+        emu.skip_boot();
+        // Prepare WRAM, with the DMA's source page at $C100
+        emu.mmu.borrow_mut().write(0xc000, 0x42);
+        (0xc100..0xc1a0).for_each(|addr| emu.mmu.borrow_mut().write(addr, 0x99));
+        // Repeatedly read from WRAM, running from HRAM
+        #[rustfmt::skip]
+        let prog = [
+            0xfa, 0x00, 0xc0, // LD A, (0xc000)
+            0x18, 0xfb,       // JR -5
+        ];
+        for (addr, byte) in (0xff80..).zip(prog) {
+            emu.mmu.borrow_mut().write(addr, byte);
+        }
+        let regs = emu.cpu().regs();
+        emu.cpu_mut().set_regs(CpuState { pc: 0xff80, ..regs });
+        let acc = |emu: &GameBoy| (emu.cpu().regs().af >> 8) as u8;
+
+        // Read during a DMA
+        emu.mmu.borrow_mut().write(0xff46, 0xc1);
+        emu.run_cycles(16);
+        assert_eq!(acc(&emu), 0x99);
+        // Read after the DMA completes
+        emu.run_cycles(640);
+        assert_eq!(emu.mmu.borrow().read(0xfe9f), 0x99);
+        emu.run_cycles(32);
+        assert_eq!(acc(&emu), 0x42);
+    }
+
+    #[test]
+    fn step_back_mbc_works() {
+        // Prepare a 64 KiB MBC1 ROM, marking the start of each bank
+        let mut rom = cart::rom(0x10000, 0x01, b"");
+        (1..4).for_each(|bank| rom[0x4000 * bank] = bank as u8);
+        let mut emu = GameBoy::new(Cartridge::new(&rom).unwrap());
+        emu.skip_boot();
+        // Select ROM bank 2, running from WRAM
+        #[rustfmt::skip]
+        let prog = [
+            0x3e, 0x02,       // LD A, 0x02
+            0xea, 0x00, 0x20, // LD (0x2000), A
+            0x18, 0xfe,       // JR -2
+        ];
+        for (addr, byte) in (0xc000..).zip(prog) {
+            emu.mmu.borrow_mut().write(addr, byte);
+        }
+        let regs = emu.cpu().regs();
+        emu.cpu_mut().set_regs(CpuState { pc: 0xc000, ..regs });
+
+        // Writing the bank register can't be undone...
+        assert!(emu.step_over(100));
+        emu.cpu_mut().checkpoint();
+        assert!(emu.step_over(100));
+        assert!(!emu.cpu_mut().step_back());
+        // ... so the bank remains selected
+        assert_eq!(emu.cpu().regs().pc, 0xc005);
+        assert_eq!(emu.mmu.borrow().read(0x4000), 0x02);
+    }
+
+    #[test]
+    fn step_back_dma_works() {
+        let mut emu = setup();
+        emu.skip_boot();
+        // Prepare the DMA's source page at $C100
+        (0xc100..0xc1a0).for_each(|addr| emu.mmu.borrow_mut().write(addr, 0x99));
+        // Start a DMA, running from HRAM
+        #[rustfmt::skip]
+        let prog = [
+            0x3e, 0xc1, // LD A, 0xc1
+            0xe0, 0x46, // LDH (0x46), A
+            0x18, 0xfe, // JR -2
+        ];
+        for (addr, byte) in (0xff80..).zip(prog) {
+            emu.mmu.borrow_mut().write(addr, byte);
+        }
+        let regs = emu.cpu().regs();
+        emu.cpu_mut().set_regs(CpuState { pc: 0xff80, ..regs });
+
+        // Writing the DMA register can't be undone...
+        assert!(emu.step_over(100));
+        emu.cpu_mut().checkpoint();
+        assert!(emu.step_over(100));
+        assert!(!emu.cpu_mut().step_back());
+        // ... so the transfer completes undisturbed
+        assert_eq!(emu.io_read(IoReg::Dma), 0xc1);
+        emu.run_cycles(640);
+        assert_eq!(emu.ppu.ctl.borrow().dma_transfer(), None);
+        assert_eq!(emu.mmu.borrow().read(0xfe9f), 0x99);
+    }
+
+    #[test]
+    fn oam_bug_idu_works() {
+        let run = |hl| {
+            let mut emu = setup();
+            emu.skip_boot();
+            emu.ppu_mut().set_oam_bug(true);
+            // Fill OAM with distinct bytes
+            (0xfe00..0xfea0).for_each(|addr| emu.mmu.borrow_mut().write(addr, addr as u8));
+            // Increment HL, running from WRAM
+            emu.mmu.borrow_mut().write(0xc000, 0x23); // INC HL
+            let regs = emu.cpu().regs();
+            emu.cpu_mut().set_regs(CpuState {
+                hl,
+                pc: 0xc000,
+                ..regs
+            });
+            // Execute during the OAM scan
+            emu.ppu_mut().set_ly(0);
+            emu.run_cycles(12);
+            assert_eq!(emu.ppu().mode(), PpuMode::Scan);
+            assert_eq!(emu.cpu().regs().hl, hl + 1);
+            (0xfe00..0xfea0)
+                .map(|addr| emu.ppu.oam.borrow().read(addr - 0xfe00))
+                .collect::<Vec<_>>()
+        };
+        let oam = (0x00..0xa0).collect::<Vec<u8>>();
+
+        // Incrementing an address in OAM corrupts it
+        assert_ne!(run(0xfe00), oam);
+        // Whereas other addresses leave it untouched
+        assert_eq!(run(0xc100), oam);
     }
 }
@@ -0,0 +1,129 @@
+//! Input movies.
+//!
+//! A movie is a recording of joypad inputs, serialized as follows:
+//!
+//! ```text
+//! ┌────────┬─────────┬───────────────────────────────┐
+//! │  SIZE  │  NAME   │          DESCRIPTION          │
+//! ├────────┼─────────┼───────────────────────────────┤
+//! │    1 B │ Version │ Format version                │
+//! │    9 B │   Input │ Frame (LE), followed by the   │
+//! │        │         │ buttons pressed (bitset)      │
+//! │    ... │     ... │ (repeated for each input)     │
+//! └────────┴─────────┴───────────────────────────────┘
+//! ```
+
+use thiserror::Error;
+
+use crate::hw::joypad::{Button, JoypadState};
+
+/// Movie format version.
+pub const VERSION: u8 = 1;
+
+/// Buttons, ordered by their bit within a serialized input.
+const BUTTONS: [Button; 8] = [
+    Button::A,
+    Button::B,
+    Button::Select,
+    Button::Start,
+    Button::Right,
+    Button::Left,
+    Button::Up,
+    Button::Down,
+];
+
+/// Recorded joypad inputs.
+///
+/// Only changes to the buttons pressed are recorded, each alongside the
+/// frame from which it applies.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Movie {
+    inputs: Vec<(u64, JoypadState)>,
+}
+
+impl Movie {
+    /// Records the buttons pressed from the start of the provided frame.
+    ///
+    /// Inputs which don't change the buttons pressed are ignored.
+    pub fn record(&mut self, frame: u64, state: JoypadState) {
+        let mask = encode(&state);
+        match self.inputs.last_mut() {
+            Some((_, last)) if encode(last) == mask => (),
+            Some((prev, last)) if *prev == frame => *last = state,
+            _ => self.inputs.push((frame, state)),
+        }
+    }
+
+    /// Gets the recorded inputs, as `(frame, state)` pairs.
+    #[must_use]
+    pub fn inputs(&self) -> &[(u64, JoypadState)] {
+        &self.inputs
+    }
+
+    /// Serializes the movie.
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut movie = vec![VERSION];
+        for (frame, state) in &self.inputs {
+            movie.extend(frame.to_le_bytes());
+            movie.push(encode(state));
+        }
+        movie
+    }
+
+    /// Deserializes a movie.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the movie's version is unsupported, or if its inputs are
+    /// truncated.
+    pub fn from_bytes(movie: &[u8]) -> Result<Self, Error> {
+        // Check version
+        let inputs = match movie.split_first() {
+            Some((&VERSION, inputs)) => inputs,
+            Some((&version, _)) => return Err(Error::UnsupportedVersion(version)),
+            None => return Err(Error::Truncated),
+        };
+        if inputs.len() % 9 != 0 {
+            return Err(Error::Truncated);
+        }
+
+        // Decode inputs
+        let inputs = inputs
+            .chunks(9)
+            .map(|input| {
+                let frame = u64::from_le_bytes(input[..8].try_into().unwrap());
+                (frame, decode(input[8]))
+            })
+            .collect();
+        Ok(Self { inputs })
+    }
+}
+
+/// Encodes the buttons pressed as a bitset.
+fn encode(state: &JoypadState) -> u8 {
+    BUTTONS
+        .iter()
+        .enumerate()
+        .filter(|(_, btn)| state.contains(*btn))
+        .fold(0, |mask, (bit, _)| mask | (1 << bit))
+}
+
+/// Decodes the buttons pressed from a bitset.
+fn decode(mask: u8) -> JoypadState {
+    BUTTONS
+        .iter()
+        .enumerate()
+        .filter(|(bit, _)| mask & (1 << *bit) != 0)
+        .map(|(_, &btn)| btn)
+        .collect()
+}
+
+/// A type specifying general categories of movie error.
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("truncated movie")]
+    Truncated,
+    #[error("unsupported movie version: {0}")]
+    UnsupportedVersion(u8),
+}
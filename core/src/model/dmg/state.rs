@@ -0,0 +1,640 @@
+//! Save states.
+//!
+//! A save state is a snapshot of the emulator, serialized as follows:
+//!
+//! ```text
+//! ┌─────────┬─────────┬───────────────────────────────┐
+//! │  SIZE   │  NAME   │          DESCRIPTION          │
+//! ├─────────┼─────────┼───────────────────────────────┤
+//! │     1 B │ Version │ Format version                │
+//! │    16 B │     CPU │ Registers, IME, status        │
+//! │     4 B │     PPU │ Mode, dot, window line        │
+//! │     4 B │   Timer │ Divider, TIMA reload          │
+//! │     8 B │   Clock │ Cycles since reset (LE)       │
+//! │    20 B │     MBC │ Bank registers, RTC           │
+//! │   130 B │    CRAM │ BG and OBJ color palettes     │
+//! │  8 Ki B │   VRAM1 │ $8000..=$9FFF (bank 1)        │
+//! │ 24 Ki B │   WRAMX │ $D000..=$DFFF (banks 2-7)     │
+//! │  8 Ki B │    VRAM │ $8000..=$9FFF (bank 0)        │
+//! │  8 Ki B │    WRAM │ $C000..=$DFFF (banks 0-1)     │
+//! │   160 B │     OAM │ $FE00..=$FE9F                 │
+//! │   128 B │     I/O │ $FF00..=$FF7F                 │
+//! │   127 B │    HRAM │ $FF80..=$FFFE                 │
+//! │     1 B │      IE │ $FFFF                         │
+//! │     N B │    ERAM │ External RAM (all banks)      │
+//! └─────────┴─────────┴───────────────────────────────┘
+//! ```
+//!
+//! Internal state is laid out as follows (multi-byte values are LE):
+//!
+//! ```text
+//! ┌─────────┬─────────────────────────────────────────────────────────────┐
+//! │ SECTION │                           LAYOUT                            │
+//! ├─────────┼─────────────────────────────────────────────────────────────┤
+//! │     CPU │ AF, BC, DE, HL, SP, PC, IME, status, opcode, HALT bug       │
+//! │     PPU │ mode, dot (2 B), window line                                │
+//! │   Timer │ divider (2 B), reload kind, reload delay                    │
+//! │     MBC │ kind, bank, bank/select, mode/latch, enable, RTC flags,     │
+//! │         │ RTC seconds (8 B), RTC latched registers (5 B), reserved    │
+//! │    CRAM │ BCPS, BG palettes (64 B), OCPS, OBJ palettes (64 B)         │
+//! └─────────┴─────────────────────────────────────────────────────────────┘
+//! ```
+//!
+//! All VRAM and WRAM banks are saved irrespective of the model, as is palette
+//! memory. These are only mapped on the CGB.
+//!
+//! Version 1 states, which lack the PPU, timer, clock, MBC, CRAM, VRAM1, and
+//! WRAMX sections (and whose CPU section holds only the registers), are still
+//! accepted, as are version 2 states, which lack only the WRAMX section. On
+//! load, the missing state is left as-is.
+
+use std::mem::discriminant;
+
+use remus::Device;
+use thiserror::Error;
+
+use super::{CpuControl, CpuState, CpuStatus, GameBoy, Ime, PpuMode, PpuState, TimerState};
+use crate::hw::cart::mbc::{MbcState, RtcState};
+use crate::hw::timer::Reload;
+
+/// Save state format version.
+pub const VERSION: u8 = 3;
+
+/// Fixed-size sections following the version, as `(name, base, len)`.
+#[rustfmt::skip]
+const SECTIONS: [(&str, usize, usize); 14] = [
+    ("CPU",   0x0000, 0x0010),
+    ("PPU",   0x0000, 0x0004),
+    ("Timer", 0x0000, 0x0004),
+    ("Clock", 0x0000, 0x0008),
+    ("MBC",   0x0000, 0x0014),
+    ("CRAM",  0x0000, 0x0082),
+    ("VRAM1", 0x8000, 0x2000),
+    ("WRAMX", 0x0000, 0x6000),
+    ("VRAM",  0x8000, 0x2000),
+    ("WRAM",  0xc000, 0x2000),
+    ("OAM",   0xfe00, 0x00a0),
+    ("I/O",   0xff00, 0x0080),
+    ("HRAM",  0xff80, 0x007f),
+    ("IE",    0xffff, 0x0001),
+];
+
+/// Fixed-size sections of a version 2 state.
+#[rustfmt::skip]
+const SECTIONS_V2: [(&str, usize, usize); 13] = [
+    ("CPU",   0x0000, 0x0010),
+    ("PPU",   0x0000, 0x0004),
+    ("Timer", 0x0000, 0x0004),
+    ("Clock", 0x0000, 0x0008),
+    ("MBC",   0x0000, 0x0014),
+    ("CRAM",  0x0000, 0x0082),
+    ("VRAM1", 0x8000, 0x2000),
+    ("VRAM",  0x8000, 0x2000),
+    ("WRAM",  0xc000, 0x2000),
+    ("OAM",   0xfe00, 0x00a0),
+    ("I/O",   0xff00, 0x0080),
+    ("HRAM",  0xff80, 0x007f),
+    ("IE",    0xffff, 0x0001),
+];
+
+/// Fixed-size sections of a version 1 state.
+#[rustfmt::skip]
+const SECTIONS_V1: [(&str, usize, usize); 7] = [
+    ("CPU",  0x0000, 0x000c),
+    ("VRAM", 0x8000, 0x2000),
+    ("WRAM", 0xc000, 0x2000),
+    ("OAM",  0xfe00, 0x00a0),
+    ("I/O",  0xff00, 0x0080),
+    ("HRAM", 0xff80, 0x007f),
+    ("IE",   0xffff, 0x0001),
+];
+
+/// Number of memory mapped sections, which end each layout.
+const MAPPED: usize = 6;
+
+/// Snapshot of the emulator's state.
+///
+/// This is the decoded form of a save state, as produced by
+/// [`GameBoy::snapshot`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Snapshot {
+    /// CPU registers.
+    pub regs: CpuState,
+    /// CPU control state.
+    pub cpu: CpuControl,
+    /// PPU internal state.
+    pub ppu: PpuState,
+    /// Timer internal state.
+    pub timer: TimerState,
+    /// Cycles elapsed since power-on (or the most recent reset), from which
+    /// frames are counted.
+    pub cycle: u64,
+    /// MBC control state.
+    pub mbc: MbcState,
+    /// Contents of the background, then object, color palette memory, each
+    /// preceded by its specification register.
+    pub cram: Vec<u8>,
+    /// Contents of the second VRAM bank.
+    pub vram1: Vec<u8>,
+    /// Contents of the upper WRAM banks (2 through 7).
+    pub wramx: Vec<u8>,
+    /// Contents of the memory mapped sections (VRAM bank 0 through IE), in
+    /// order.
+    pub mem: Vec<u8>,
+    /// Contents of the external RAM (all banks).
+    pub eram: Vec<u8>,
+}
+
+/// Difference between two save states.
+///
+/// Addresses are reported relative to the section's base: memory mapped
+/// sections use their bus address, whereas the `CPU` and `ERAM` sections use
+/// their offset within the section.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StateDiff {
+    /// Name of the differing section.
+    pub region: &'static str,
+    /// First differing address.
+    pub start: usize,
+    /// Last differing address (inclusive).
+    pub end: usize,
+}
+
+/// Captures the emulator's state.
+pub(super) fn snapshot(emu: &GameBoy) -> Snapshot {
+    let mmu = emu.mmu.borrow();
+    let vram = emu.ppu.vram.borrow();
+    let vram1 = emu.ppu.vram1.borrow();
+    let wram = emu.mem.wram.borrow();
+    let wramx = emu.mem.wramx.borrow();
+    Snapshot {
+        regs: emu.cpu.regs(),
+        cpu: emu.cpu.control(),
+        ppu: emu.ppu.state(),
+        timer: emu.timer.state(),
+        cycle: emu.cycle as u64,
+        mbc: emu.cart.mbc_state(),
+        cram: [&emu.ppu.bcp, &emu.ppu.ocp]
+            .iter()
+            .flat_map(|cram| cram.borrow().dump())
+            .collect(),
+        vram1: (0..vram1.len()).map(|addr| vram1.read(addr)).collect(),
+        wramx: (0..wramx.len()).map(|addr| wramx.read(addr)).collect(),
+        mem: SECTIONS[SECTIONS.len() - MAPPED..]
+            .iter()
+            .flat_map(|&(_, base, len)| base..base + len)
+            .map(|addr| match addr {
+                // NOTE: VRAM and WRAM are read directly, as the CGB maps the
+                //       selected bank.
+                0x8000..=0x9fff => vram.read(addr - 0x8000),
+                0xc000..=0xdfff => wram.read(addr - 0xc000),
+                _ => mmu.read(addr),
+            })
+            .collect(),
+        eram: emu.cart.save_ram(),
+    }
+}
+
+/// Restores the emulator's state.
+pub(super) fn restore(emu: &mut GameBoy, snap: &Snapshot) -> Result<(), Error> {
+    // Check sizes
+    let total = SECTIONS[SECTIONS.len() - MAPPED..]
+        .iter()
+        .map(|(_, _, len)| len)
+        .sum::<usize>();
+    if snap.mem.len() != total {
+        return Err(Error::SizeMismatch(total, snap.mem.len()));
+    }
+    if snap.cram.len() != 0x82 {
+        return Err(Error::SizeMismatch(0x82, snap.cram.len()));
+    }
+    let expected = emu.ppu.vram1.borrow().len();
+    if snap.vram1.len() != expected {
+        return Err(Error::SizeMismatch(expected, snap.vram1.len()));
+    }
+    let expected = emu.mem.wramx.borrow().len();
+    if snap.wramx.len() != expected {
+        return Err(Error::SizeMismatch(expected, snap.wramx.len()));
+    }
+    let expected = emu.cart.ram_size();
+    if snap.eram.len() != expected {
+        return Err(Error::SizeMismatch(expected, snap.eram.len()));
+    }
+    // Check MBC
+    let mbc = emu.cart.mbc_state();
+    let rtc = |mbc: &MbcState| matches!(mbc, MbcState::Mbc3 { rtc: Some(_), .. });
+    if discriminant(&mbc) != discriminant(&snap.mbc) || rtc(&mbc) != rtc(&snap.mbc) {
+        return Err(Error::MbcMismatch);
+    }
+
+    // Restore color palettes
+    let (bcp, ocp) = snap.cram.split_at(0x41);
+    emu.ppu.bcp.borrow_mut().load(bcp);
+    emu.ppu.ocp.borrow_mut().load(ocp);
+
+    // Restore both VRAM banks
+    let mut vram = emu.ppu.vram.borrow_mut();
+    let mut vram1 = emu.ppu.vram1.borrow_mut();
+    for (addr, &byte) in snap.vram1.iter().enumerate() {
+        vram1.write(addr, byte);
+    }
+
+    // Restore all WRAM banks
+    let mut wram = emu.mem.wram.borrow_mut();
+    let mut wramx = emu.mem.wramx.borrow_mut();
+    for (addr, &byte) in snap.wramx.iter().enumerate() {
+        wramx.write(addr, byte);
+    }
+
+    // Restore mapped memory
+    let mut mmu = emu.mmu.borrow_mut();
+    let mut data = snap.mem.iter();
+    for (_, base, len) in &SECTIONS[SECTIONS.len() - MAPPED..] {
+        for (addr, &byte) in (*base..base + len).zip(&mut data) {
+            match addr {
+                // NOTE: VRAM and WRAM are written directly, as the CGB maps
+                //       the selected bank.
+                0x8000..=0x9fff => vram.write(addr - 0x8000, byte),
+                0xc000..=0xdfff => wram.write(addr - 0xc000, byte),
+                // NOTE: Restoring the DMA register would start a new transfer.
+                0xff46 => (),
+                // NOTE: Color palettes are restored directly, as writing their
+                //       data ports would advance the palette index.
+                0xff68..=0xff6b => (),
+                // NOTE: Registers whose writes have side effects are restored
+                //       directly. As its progress is not saved, a serial
+                //       transfer in progress restarts.
+                0xff02 => **emu.serial.ctl.borrow().sc.borrow_mut() = byte,
+                0xff04 => **emu.timer.ctl.borrow().div.borrow_mut() = byte,
+                0xff05 => **emu.timer.ctl.borrow().tima.borrow_mut() = byte,
+                _ => mmu.write(addr, byte),
+            }
+        }
+    }
+    drop(mmu);
+    drop(vram);
+    drop(vram1);
+    drop(wram);
+    drop(wramx);
+
+    // Restore external RAM
+    emu.cart.load_ram(&snap.eram);
+
+    // Restore internal state
+    emu.cpu.set_regs(snap.regs);
+    emu.cpu.set_control(snap.cpu);
+    emu.timer.set_state(snap.timer);
+    emu.cycle = snap.cycle as usize;
+    emu.cart.set_mbc_state(&snap.mbc);
+    // NOTE: The PPU is restored last, as it replays the current scanline from
+    //       the restored memory.
+    emu.ppu.set_state(snap.ppu);
+
+    Ok(())
+}
+
+/// Serializes the emulator's state.
+pub(super) fn save(emu: &GameBoy) -> Vec<u8> {
+    let snap = snapshot(emu);
+    let mut state = vec![VERSION];
+
+    // Save CPU state
+    let CpuState {
+        af,
+        bc,
+        de,
+        hl,
+        sp,
+        pc,
+    } = snap.regs;
+    for word in [af, bc, de, hl, sp, pc] {
+        state.extend(word.to_le_bytes());
+    }
+    let (status, opcode) = match snap.cpu.status {
+        CpuStatus::Enabled => (0, 0),
+        CpuStatus::Halted => (1, 0),
+        CpuStatus::Stopped => (2, 0),
+        CpuStatus::Break => (3, 0),
+        CpuStatus::Locked(opcode) => (4, opcode),
+    };
+    state.extend([snap.cpu.ime as u8, status, opcode, snap.cpu.halt_bug as u8]);
+
+    // Save PPU state
+    state.push(snap.ppu.mode as u8);
+    state.extend(snap.ppu.dot.to_le_bytes());
+    state.push(snap.ppu.winln);
+
+    // Save timer state
+    state.extend(snap.timer.div.to_le_bytes());
+    state.extend(match snap.timer.reload {
+        Reload::Idle => [0, 0],
+        Reload::Overflow(delay) => [1, delay],
+        Reload::Reload(delay) => [2, delay],
+    });
+
+    // Save cycle count
+    state.extend(snap.cycle.to_le_bytes());
+
+    // Save MBC state
+    let mut mbc = [0; 0x14];
+    match snap.mbc {
+        MbcState::None => (),
+        MbcState::Mbc1 {
+            bank1,
+            bank2,
+            mode,
+            ena,
+        } => mbc[..5].copy_from_slice(&[1, bank1, bank2, mode as u8, ena as u8]),
+        MbcState::Mbc3 {
+            bank1,
+            sel,
+            latch,
+            ena,
+            rtc,
+        } => {
+            mbc[..5].copy_from_slice(&[3, bank1, sel, latch, ena as u8]);
+            if let Some(rtc) = rtc {
+                mbc[5] = 0x01 | (rtc.halt as u8) << 6 | (rtc.carry as u8) << 7;
+                mbc[6..14].copy_from_slice(&rtc.secs.to_le_bytes());
+                mbc[14..19].copy_from_slice(&rtc.latched);
+            }
+        }
+    }
+    state.extend(mbc);
+
+    // Save color palettes
+    state.extend(snap.cram);
+
+    // Save VRAM bank 1
+    state.extend(snap.vram1);
+
+    // Save WRAM banks 2-7
+    state.extend(snap.wramx);
+
+    // Save mapped memory
+    state.extend(snap.mem);
+
+    // Save external RAM
+    state.extend(snap.eram);
+
+    state
+}
+
+/// Restores the emulator's serialized state.
+pub(super) fn load(emu: &mut GameBoy, state: &[u8]) -> Result<(), Error> {
+    // Check version
+    let version = version(state)?;
+    // Check length
+    let layout = layout(version);
+    let total = layout.iter().map(|(_, _, len)| len).sum::<usize>();
+    let expected = 1 + total + emu.cart.ram_size();
+    if state.len() != expected {
+        return Err(Error::SizeMismatch(expected, state.len()));
+    }
+
+    // Decode the state
+    let snap = match version {
+        // NOTE: Version 1 states lack internal state, which is taken from the
+        //       emulator as-is.
+        1 => Snapshot {
+            regs: regs(&state[1..]),
+            mem: state[1 + layout[0].2..1 + total].to_vec(),
+            eram: state[1 + total..].to_vec(),
+            ..snapshot(emu)
+        },
+        // NOTE: Version 2 states lack the upper WRAM banks, which are
+        //       likewise taken from the emulator as-is.
+        2 => Snapshot {
+            wramx: snapshot(emu).wramx,
+            ..decode(state, version, total)?
+        },
+        _ => decode(state, version, total)?,
+    };
+
+    restore(emu, &snap)
+}
+
+/// Decodes a serialized state of version 2 or later.
+fn decode(state: &[u8], version: u8, total: usize) -> Result<Snapshot, Error> {
+    // Locate each section
+    let layout = layout(version);
+    let mut sections = layout.iter().scan(1, |offset, (_, _, len)| {
+        let range = *offset..*offset + len;
+        *offset += len;
+        Some(&state[range])
+    });
+    let mut section = || sections.next().unwrap();
+
+    // Decode CPU state
+    let cpu = section();
+    let ime = match cpu[12] {
+        0 => Ime::Disabled,
+        1 => Ime::Enabled,
+        2 => Ime::WillEnable,
+        _ => return Err(Error::Corrupt("CPU")),
+    };
+    let status = match cpu[13] {
+        0 => CpuStatus::Enabled,
+        1 => CpuStatus::Halted,
+        2 => CpuStatus::Stopped,
+        3 => CpuStatus::Break,
+        4 => CpuStatus::Locked(cpu[14]),
+        _ => return Err(Error::Corrupt("CPU")),
+    };
+    let regs = regs(cpu);
+    let cpu = CpuControl {
+        ime,
+        status,
+        halt_bug: cpu[15] != 0,
+    };
+
+    // Decode PPU state
+    let ppu = section();
+    let ppu = PpuState {
+        mode: match ppu[0] {
+            0b00 => PpuMode::HBlank,
+            0b01 => PpuMode::VBlank,
+            0b10 => PpuMode::Scan,
+            0b11 => PpuMode::Draw,
+            _ => return Err(Error::Corrupt("PPU")),
+        },
+        dot: u16::from_le_bytes([ppu[1], ppu[2]]),
+        winln: ppu[3],
+    };
+
+    // Decode timer state
+    let timer = section();
+    let timer = TimerState {
+        div: u16::from_le_bytes([timer[0], timer[1]]),
+        reload: match timer[2] {
+            0 => Reload::Idle,
+            1 => Reload::Overflow(timer[3]),
+            2 => Reload::Reload(timer[3]),
+            _ => return Err(Error::Corrupt("Timer")),
+        },
+    };
+
+    // Decode cycle count
+    let cycle = u64::from_le_bytes(section().try_into().unwrap());
+
+    // Decode MBC state
+    let mbc = section();
+    let mbc = match mbc[0] {
+        0 => MbcState::None,
+        1 => MbcState::Mbc1 {
+            bank1: mbc[1],
+            bank2: mbc[2],
+            mode: mbc[3] != 0,
+            ena: mbc[4] != 0,
+        },
+        3 => MbcState::Mbc3 {
+            bank1: mbc[1],
+            sel: mbc[2],
+            latch: mbc[3],
+            ena: mbc[4] != 0,
+            rtc: (mbc[5] & 0x01 != 0).then(|| RtcState {
+                secs: u64::from_le_bytes(mbc[6..14].try_into().unwrap()),
+                halt: mbc[5] & 0x40 != 0,
+                carry: mbc[5] & 0x80 != 0,
+                latched: mbc[14..19].try_into().unwrap(),
+            }),
+        },
+        _ => return Err(Error::Corrupt("MBC")),
+    };
+
+    // Decode color palettes
+    let cram = section().to_vec();
+
+    // Decode VRAM bank 1
+    let vram1 = section().to_vec();
+
+    // Decode WRAM banks 2-7
+    let wramx = match version {
+        2 => Vec::new(),
+        _ => section().to_vec(),
+    };
+
+    // Decode memory
+    let offset = 1 + layout[..layout.len() - MAPPED]
+        .iter()
+        .map(|(_, _, len)| len)
+        .sum::<usize>();
+
+    Ok(Snapshot {
+        regs,
+        cpu,
+        ppu,
+        timer,
+        cycle,
+        mbc,
+        cram,
+        vram1,
+        wramx,
+        mem: state[offset..1 + total].to_vec(),
+        eram: state[1 + total..].to_vec(),
+    })
+}
+
+/// Decodes the CPU registers at the start of a CPU section.
+fn regs(cpu: &[u8]) -> CpuState {
+    let mut words = cpu[..12]
+        .chunks(2)
+        .map(|word| u16::from_le_bytes([word[0], word[1]]));
+    let mut word = || words.next().unwrap();
+    CpuState {
+        af: word(),
+        bc: word(),
+        de: word(),
+        hl: word(),
+        sp: word(),
+        pc: word(),
+    }
+}
+
+/// Compares two serialized states.
+pub(super) fn diff(a: &[u8], b: &[u8]) -> Result<Vec<StateDiff>, Error> {
+    // Check versions
+    let (va, vb) = (version(a)?, version(b)?);
+    if va != vb {
+        return Err(Error::VersionMismatch(va, vb));
+    }
+    // Check lengths
+    if a.len() != b.len() {
+        return Err(Error::SizeMismatch(a.len(), b.len()));
+    }
+
+    // Split states into sections
+    let layout = layout(va);
+    let total = layout.iter().map(|(_, _, len)| len).sum::<usize>();
+    let eram = a.len() - 1 - total;
+    let sections = layout.iter().copied().chain([("ERAM", 0x0000, eram)]).scan(
+        1,
+        |offset, (name, base, len)| {
+            let range = *offset..*offset + len;
+            *offset += len;
+            Some((name, base, range))
+        },
+    );
+
+    // Find contiguous runs of differing bytes
+    let mut diffs: Vec<StateDiff> = Vec::new();
+    for (region, base, range) in sections {
+        let start = range.start;
+        for index in range.filter(|&index| a[index] != b[index]) {
+            let addr = base + (index - start);
+            match diffs.last_mut() {
+                Some(diff) if diff.region == region && diff.end + 1 == addr => diff.end = addr,
+                _ => diffs.push(StateDiff {
+                    region,
+                    start: addr,
+                    end: addr,
+                }),
+            }
+        }
+    }
+
+    Ok(diffs)
+}
+
+/// Gets the fixed-size sections of a state version.
+fn layout(version: u8) -> &'static [(&'static str, usize, usize)] {
+    match version {
+        1 => &SECTIONS_V1,
+        2 => &SECTIONS_V2,
+        _ => &SECTIONS,
+    }
+}
+
+/// Checks the version of a serialized state.
+fn version(state: &[u8]) -> Result<u8, Error> {
+    match state.first() {
+        Some(&version @ 1..=VERSION) => {
+            let total = layout(version).iter().map(|(_, _, len)| len).sum::<usize>();
+            if state.len() > total {
+                Ok(version)
+            } else {
+                Err(Error::Truncated)
+            }
+        }
+        None => Err(Error::Truncated),
+        Some(&version) => Err(Error::UnsupportedVersion(version)),
+    }
+}
+
+/// A type specifying general categories of save state error.
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("truncated save state")]
+    Truncated,
+    #[error("unsupported save state version: {0}")]
+    UnsupportedVersion(u8),
+    #[error("mismatched save state versions: {0} != {1}")]
+    VersionMismatch(u8, u8),
+    #[error("mismatched save state sizes: {0} != {1}")]
+    SizeMismatch(usize, usize),
+    #[error("corrupt save state section: {0}")]
+    Corrupt(&'static str),
+    #[error("save state is for a different MBC")]
+    MbcMismatch,
+}
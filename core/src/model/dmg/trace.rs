@@ -0,0 +1,210 @@
+//! Binary instruction traces.
+//!
+//! A trace records each instruction executed, serialized as follows:
+//!
+//! ```text
+//! ┌────────┬─────────┬───────────────────────────────┐
+//! │  SIZE  │  NAME   │          DESCRIPTION          │
+//! ├────────┼─────────┼───────────────────────────────┤
+//! │    1 B │ Version │ Format version                │
+//! │    2 B │      PC │ Address of the instruction    │
+//! │    1 B │  Opcode │ Opcode of the instruction     │
+//! │    1 B │    Mask │ Registers changed (bitset)    │
+//! │ 0-10 B │   Delta │ Changed AF, BC, DE, HL, SP    │
+//! │    ... │     ... │ (repeated for each entry)     │
+//! └────────┴─────────┴───────────────────────────────┘
+//! ```
+//!
+//! Registers are only included when they differ from the previous entry (or
+//! from zero, for the first entry).
+
+use std::io::{self, Read, Write};
+
+use thiserror::Error;
+
+use super::CpuState;
+
+/// Trace format version.
+pub const VERSION: u8 = 1;
+
+/// Traced instruction.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct TraceEntry {
+    /// Values of the CPU's registers before execution.
+    pub regs: CpuState,
+    /// Opcode of the instruction.
+    pub opcode: u8,
+}
+
+/// Binary trace writer.
+#[derive(Debug)]
+pub struct TraceWriter<W: Write> {
+    sink: W,
+    prev: CpuState,
+}
+
+impl<W: Write> TraceWriter<W> {
+    /// Constructs a new `TraceWriter`, writing the trace's header to `sink`.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the header could not be written.
+    pub fn new(mut sink: W) -> io::Result<Self> {
+        sink.write_all(&[VERSION])?;
+        Ok(Self {
+            sink,
+            prev: CpuState::default(),
+        })
+    }
+
+    /// Records an entry to the trace.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the entry could not be written.
+    pub fn record(&mut self, entry: &TraceEntry) -> io::Result<()> {
+        let mut buf = [0; 14];
+        buf[0..2].copy_from_slice(&entry.regs.pc.to_le_bytes());
+        buf[2] = entry.opcode;
+        let mut len = 4;
+        // Include only the changed registers
+        let regs = words(&self.prev).into_iter().zip(words(&entry.regs));
+        for (bit, (prev, word)) in regs.enumerate() {
+            if prev != word {
+                buf[3] |= 1 << bit;
+                buf[len..len + 2].copy_from_slice(&word.to_le_bytes());
+                len += 2;
+            }
+        }
+        self.prev = entry.regs;
+        self.sink.write_all(&buf[..len])
+    }
+
+    /// Unwraps the underlying sink.
+    pub fn into_inner(self) -> W {
+        self.sink
+    }
+}
+
+/// Binary trace reader.
+///
+/// Reconstructs each entry of a trace written by [`TraceWriter`].
+#[derive(Debug)]
+pub struct TraceReader<R: Read> {
+    src: R,
+    prev: CpuState,
+}
+
+impl<R: Read> TraceReader<R> {
+    /// Constructs a new `TraceReader`, reading the trace's header from `src`.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the header could not be read, or if the trace's version is
+    /// unsupported.
+    pub fn new(mut src: R) -> Result<Self, Error> {
+        let mut version = [0];
+        src.read_exact(&mut version)?;
+        match version {
+            [VERSION] => Ok(Self {
+                src,
+                prev: CpuState::default(),
+            }),
+            [version] => Err(Error::UnsupportedVersion(version)),
+        }
+    }
+
+    /// Reads the next entry, if any.
+    fn entry(&mut self) -> Result<Option<TraceEntry>, Error> {
+        // Check for the end of the trace
+        let mut head = [0; 4];
+        if self.src.read(&mut head[..1])? == 0 {
+            return Ok(None);
+        }
+        self.src.read_exact(&mut head[1..])?;
+
+        // Apply the changed registers
+        let mut regs = words(&self.prev);
+        for (bit, word) in regs.iter_mut().enumerate() {
+            if head[3] & (1 << bit) != 0 {
+                let mut buf = [0; 2];
+                self.src.read_exact(&mut buf)?;
+                *word = u16::from_le_bytes(buf);
+            }
+        }
+        let [af, bc, de, hl, sp] = regs;
+        let regs = CpuState {
+            af,
+            bc,
+            de,
+            hl,
+            sp,
+            pc: u16::from_le_bytes([head[0], head[1]]),
+        };
+        self.prev = regs;
+
+        Ok(Some(TraceEntry {
+            regs,
+            opcode: head[2],
+        }))
+    }
+}
+
+impl<R: Read> Iterator for TraceReader<R> {
+    type Item = Result<TraceEntry, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.entry().transpose()
+    }
+}
+
+/// Gets the registers included in an entry's delta, ordered by their bit.
+fn words(regs: &CpuState) -> [u16; 5] {
+    [regs.af, regs.bc, regs.de, regs.hl, regs.sp]
+}
+
+/// A type specifying general categories of trace error.
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("could not read trace")]
+    Io(#[from] io::Error),
+    #[error("unsupported trace version: {0}")]
+    UnsupportedVersion(u8),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trace_works() {
+        let entries: Vec<_> = (0..0x100u16)
+            .map(|idx| TraceEntry {
+                regs: CpuState {
+                    af: idx / 3 * 0x10,
+                    bc: idx / 5,
+                    de: idx / 7,
+                    hl: idx,
+                    sp: 0xfffe - idx / 11 * 2,
+                    pc: 0x0100 + idx,
+                },
+                opcode: idx as u8,
+            })
+            .collect();
+
+        // Record the trace
+        let mut writer = TraceWriter::new(Vec::new()).unwrap();
+        for entry in &entries {
+            writer.record(entry).unwrap();
+        }
+        let trace = writer.into_inner();
+        assert!(trace.len() < 1 + entries.len() * 14);
+
+        // Reload it
+        let reader = TraceReader::new(&trace[..]).unwrap();
+        assert_eq!(reader.collect::<Result<Vec<_>, _>>().unwrap(), entries);
+        // Reject truncated traces
+        let reader = TraceReader::new(&trace[..trace.len() - 1]).unwrap();
+        assert!(reader.last().unwrap().is_err());
+    }
+}
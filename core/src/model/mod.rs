@@ -1,3 +1,15 @@
 //! Game Boy models.
 
 pub mod dmg;
+
+/// Game Boy hardware models.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum Model {
+    /// DMG-01: Game Boy.
+    #[default]
+    Dmg,
+    /// MGB-001: Game Boy Pocket.
+    Mgb,
+    /// CGB-001: Game Boy Color.
+    Cgb,
+}
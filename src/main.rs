@@ -7,7 +7,7 @@ use color_eyre::eyre::{Result, WrapErr};
 use gameboy::core::Emulator;
 use gameboy::dmg::cart::{Cartridge, Header};
 use gameboy::dmg::{Button, GameBoy, Screen, SCREEN};
-use log::{debug, info};
+use log::{debug, info, warn};
 use minifb::{Key, Scale, ScaleMode, Window, WindowOptions};
 use remus::Machine;
 
@@ -67,15 +67,19 @@ fn main() -> Result<()> {
     // Initialize the cartridge
     let cart = Cartridge::new(&rom)
         .with_context(|| format!("failed to load cartridge: `{}`", args.rom.display()))?;
+    // Warn of any compatibility issues
+    for issue in cart.validate().issues {
+        warn!("Cartridge issue: {issue}");
+    }
     // Extract ROM title from cartridge
-    let title = match cart.header().title.replace('\0', " ").trim() {
-        "" => "Game Boy",
+    let title = match cart.title() {
+        title if title.is_empty() => "Game Boy".to_string(),
         title => title,
-    }
-    .to_string();
+    };
 
     // Create emulator instance
     let mut emu = GameBoy::new(cart);
+    emu.set_shade_palette(std::array::from_fn(|idx| args.pal[idx].into()));
 
     // Create a framebuffer window
     let mut win = Window::new(
@@ -101,11 +105,8 @@ fn main() -> Result<()> {
         emu.cycle();
 
         // Redraw the screen (if needed)
-        emu.redraw(|screen: &Screen| {
-            let buf: Vec<_> = screen
-                .iter()
-                .map(|&pix| args.pal[pix as usize].into())
-                .collect();
+        emu.redraw(|_: &Screen| {
+            let buf = emu.frame_rgba();
             win.update_with_buffer(&buf, SCREEN.width, SCREEN.height)
                 .unwrap()
         });